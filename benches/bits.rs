@@ -0,0 +1,54 @@
+//! Benchmarks of the bit-packed grid kernels, comparing the scalar versions
+//! with the dispatching ones.
+//!
+//! Without the `simd` feature both run the same scalar code, so compare
+//! them with `cargo bench --features simd --bench bits`.
+
+use std::hint::black_box;
+use aoc2015::util::bits::{life_row, life_row_scalar, popcount, popcount_scalar};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of 128-bit words of a row, enough for 1000 lights.
+const WORDS: usize = 8;
+
+/// The number of rows of the benchmarked grids.
+const ROWS: usize = 1000;
+
+/// Returns pseudo-random words from a xorshift generator.
+fn random_words(count: usize) -> Vec<u128> {
+    let mut state = 0x2015_0618u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..count).map(|_| (next() as u128) << 64 | next() as u128).collect()
+}
+
+fn popcounts(c: &mut Criterion) {
+    let words = random_words(WORDS * ROWS);
+    let mut group = c.benchmark_group("popcount");
+    group.bench_function("scalar", |b| b.iter(|| popcount_scalar(black_box(&words))));
+    group.bench_function("dispatch", |b| b.iter(|| popcount(black_box(&words))));
+    group.finish();
+}
+
+fn life_rows(c: &mut Criterion) {
+    let words = random_words(WORDS * ROWS);
+    let rows: Vec<_> = words.chunks_exact(WORDS).collect();
+    let mut next = vec![0; WORDS];
+    let mut group = c.benchmark_group("life_row");
+    for (name, kernel) in [("scalar", life_row_scalar as fn(&[u128], &[u128], &[u128], &mut [u128])), ("dispatch", life_row)] {
+        group.bench_function(name, |b| b.iter(|| {
+            for rows in rows.windows(3) {
+                kernel(rows[0], rows[1], rows[2], &mut next);
+            }
+            black_box(&next);
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, popcounts, life_rows);
+criterion_main!(benches);
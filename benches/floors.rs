@@ -0,0 +1,30 @@
+//! Benchmarks of the implementations of day 1 on a generated input of a
+//! million instructions: the iterator and loop over characters, the
+//! branch-free accumulation over bytes, and the chunked counts of
+//! parentheses, which use AVX2 with `--features simd`.
+
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::gen::generate;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use aoc2015::year2015::ex01::a_stream;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of instructions of the generated input, about a hundred
+/// times as many as in the puzzle inputs.
+const INSTRUCTIONS: usize = 1 << 20;
+
+fn implementations(c: &mut Criterion) {
+    let input = generate(1, INSTRUCTIONS, 2015).unwrap();
+    let solution = days::find(YEAR, 1).unwrap();
+    let mut group = c.benchmark_group(format!("ex01/{}", Part::A));
+    for implementation in solution.implementations() {
+        group.bench_function(implementation.name, |b| b.iter(|| (implementation.solve)(black_box(&input))));
+    }
+    group.bench_function("stream", |b| b.iter(|| a_stream(black_box(input.as_bytes()))));
+    group.finish();
+}
+
+criterion_group!(benches, implementations);
+criterion_main!(benches);
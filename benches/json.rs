@@ -0,0 +1,29 @@
+//! Benchmarks of the implementations of day 12 on a generated document:
+//! the hand-written [`Json`](aoc2015::year2015::ex12::Json) tree, a
+//! `serde_json::Value` tree, and the scanner building no tree at all.
+
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::gen::generate;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of values of the generated document, several times more
+/// than in the puzzle inputs.
+const VALUES: usize = 20_000;
+
+fn implementations(c: &mut Criterion) {
+    let input = generate(12, VALUES, 2015).unwrap();
+    let solution = days::find(YEAR, 12).unwrap();
+    for part in [Part::A, Part::B] {
+        let mut group = c.benchmark_group(format!("ex12/{part}"));
+        for implementation in solution.implementations().iter().filter(|implementation| implementation.part == part) {
+            group.bench_function(implementation.name, |b| b.iter(|| (implementation.solve)(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, implementations);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Benchmarks of the implementations of day 6 on a generated input of as
+//! many instructions as the puzzle inputs: updating the bitfields of the
+//! grid one instruction at a time or in parallel bands, and sweeping the
+//! regions of the compressed coordinates, then updating a brightness per
+//! light or packed brightness lanes, which use AVX2 with
+//! `--features simd`.
+
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::gen::generate;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of instructions of the generated input, as in the puzzle
+/// inputs.
+const INSTRUCTIONS: usize = 300;
+
+fn implementations(c: &mut Criterion) {
+    let input = generate(6, INSTRUCTIONS, 2015).unwrap();
+    let solution = days::find(YEAR, 6).unwrap();
+    for part in [Part::A, Part::B] {
+        let mut group = c.benchmark_group(format!("ex06/{part}"));
+        for implementation in solution.implementations().iter().filter(|implementation| implementation.part == part) {
+            group.bench_function(implementation.name, |b| b.iter(|| (implementation.solve)(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, implementations);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+//! Benchmarks of the implementations of day 10 on a generated seed: the
+//! steps allocating a new sequence each, those alternating between two
+//! buffers, and the counts of Conway's elements.
+
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::gen::generate;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of digits of the generated seed, as in the puzzle inputs.
+const DIGITS: usize = 10;
+
+fn implementations(c: &mut Criterion) {
+    let input = generate(10, DIGITS, 2015).unwrap();
+    let solution = days::find(YEAR, 10).unwrap();
+    for part in [Part::A, Part::B] {
+        let mut group = c.benchmark_group(format!("ex10/{part}"));
+        group.sample_size(10);
+        for implementation in solution.implementations().iter().filter(|implementation| implementation.part == part) {
+            group.bench_function(implementation.name, |b| b.iter(|| (implementation.solve)(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, implementations);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+//! Benchmarks of the implementations of day 2 on a generated input of ten
+//! million presents: parsing and adding up one line at a time, and parsing
+//! every present in one pass then adding up in parallel.
+
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::gen::generate;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The number of presents of the generated input, ten thousand times as
+/// many as in the puzzle inputs.
+const PRESENTS: usize = 10_000_000;
+
+fn implementations(c: &mut Criterion) {
+    let input = generate(2, PRESENTS, 2015).unwrap();
+    let solution = days::find(YEAR, 2).unwrap();
+    for part in [Part::A, Part::B] {
+        let mut group = c.benchmark_group(format!("ex02/{part}"));
+        group.sample_size(10);
+        for implementation in solution.implementations().iter().filter(|implementation| implementation.part == part) {
+            group.bench_function(implementation.name, |b| b.iter(|| (implementation.solve)(black_box(&input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, implementations);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+//! Benchmarks of every implemented day with a cached input.
+//!
+//! Days are taken from the registry, so new days are benchmarked as soon as
+//! they are registered. Run a single day with `cargo bench -- ex06`.
+
+use std::fs;
+use std::hint::black_box;
+use aoc2015::days;
+use aoc2015::input::cache_path;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn solutions(c: &mut Criterion) {
+    for solution in days::registry(YEAR) {
+        let day = solution.day();
+        let Ok(input) = fs::read_to_string(cache_path(YEAR, day)) else { continue };
+        let input = input.trim_end();
+        let mut group = c.benchmark_group(format!("ex{day:02}"));
+        // Some days take seconds per run, keep the number of samples low.
+        group.sample_size(10);
+        for part in [Part::A, Part::B] {
+            group.bench_function(part.to_string(), |b| b.iter(|| solution.solve(part, black_box(input))));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Benchmarks of the travelling salesman algorithms on complete graphs of
+//! increasing sizes, showing where Held–Karp overtakes brute force.
+
+use std::hint::black_box;
+use aoc2015::util::rng::XorShift;
+use aoc2015::util::tsp::Algorithm;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Returns a complete graph of `n` nodes with random weights.
+fn complete_graph(n: usize) -> Vec<Vec<Option<u32>>> {
+    let mut rng = XorShift::new(0x2015_1209);
+    (0..n).map(|_| (0..n).map(|_| Some(rng.range(1..=1000) as u32)).collect()).collect()
+}
+
+fn cycles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("best_cycle");
+    // Brute force takes most of a second per run at 12 nodes.
+    group.sample_size(10);
+    for n in (4..=12).step_by(2) {
+        let weights = complete_graph(n);
+        for algorithm in [Algorithm::BruteForce, Algorithm::HeldKarp] {
+            group.bench_with_input(BenchmarkId::new(format!("{algorithm:?}"), n), &weights, |b, weights| {
+                b.iter(|| algorithm.best_cycle(black_box(weights), u32::min))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, cycles);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The answer to a part of a puzzle.
+///
+/// Most answers are numbers, signed or not, but some are text, such as the
+/// password of day 11. Numeric answers compare equal when they hold the
+/// same value, regardless of their signedness.
+///
+/// # Examples
+/// ```
+/// use aoc2015::answer::Answer;
+/// assert_eq!(Answer::from(42u32), Answer::from(42i64));
+/// assert_eq!(Answer::from("abcdffaa").to_string(), "abcdffaa");
+/// assert_eq!("-3".parse::<Answer>(), Ok(Answer::Int(-3)));
+/// ```
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    /// A signed number.
+    Int(i64),
+    /// An unsigned number.
+    UInt(u64),
+    /// Some text.
+    Text(String),
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::UInt(a), Answer::UInt(b)) => a == b,
+            (Answer::Int(a), Answer::UInt(b)) | (Answer::UInt(b), Answer::Int(a)) => u64::try_from(*a) == Ok(*b),
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::UInt(n) => n.fmt(f),
+            Answer::Text(text) => f.pad(text),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Parses an answer as an unsigned number, a signed number or text,
+    /// whichever fits first.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match (s.parse(), s.parse()) {
+            (Ok(n), _) => Answer::UInt(n),
+            (_, Ok(n)) => Answer::Int(n),
+            _ => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::$variant(value as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Int: i16, i32, i64);
+impl_from!(UInt: u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use toml::{Table, Value};
+use crate::runner::Report;
+
+/// Parses a duration made of a decimal number and a unit, such as `1s`,
+/// `250ms` or `1.5m`.
+///
+/// # Errors
+/// Returns a description of the problem if the number or the unit is
+/// invalid, or the duration is negative or too long.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().map_err(|_| format!("invalid duration '{text}', expected a number followed by a unit"))?;
+    let seconds = match unit.trim() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        "m" => 60.0,
+        unit => return Err(format!("invalid unit '{unit}' in duration '{text}', expected one of ns, us, ms, s, m")),
+    };
+    Duration::try_from_secs_f64(number * seconds).map_err(|err| format!("invalid duration '{text}': {err}"))
+}
+
+/// The runtimes a run must stay within.
+#[derive(Debug, Default)]
+pub struct Budget {
+    /// The budget of the whole run.
+    pub total: Option<Duration>,
+    /// The budget of each day, for both of its parts.
+    pub days: BTreeMap<u32, Duration>,
+}
+
+impl Budget {
+    /// Reads the budgets of a TOML file, which may hold the `total` budget
+    /// of the run and the budget of each day:
+    ///
+    /// ```toml
+    /// total = "5s"
+    /// day04 = "2s"
+    /// day10 = "200ms"
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the file cannot be read, is not valid
+    /// TOML, or holds an unknown key or an invalid duration.
+    pub fn read(path: &Path) -> io::Result<Budget> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display()));
+        let table: Table = fs::read_to_string(path)?.parse().map_err(|err: toml::de::Error| invalid(err.to_string()))?;
+        let mut budget = Budget::default();
+        for (key, value) in &table {
+            let Value::String(text) = value else { return Err(invalid(format!("{key} is not a string"))) };
+            let duration = parse_duration(text).map_err(&invalid)?;
+            match key.strip_prefix("day").map(str::parse::<u32>) {
+                _ if key == "total" => budget.total = Some(duration),
+                Some(Ok(day)) if (1..=25).contains(&day) => { budget.days.insert(day, duration); }
+                _ => return Err(invalid(format!("unknown key {key}, expected total or dayNN"))),
+            }
+        }
+        Ok(budget)
+    }
+
+    /// Returns how the run of a [`Report`] exceeds this budget, one
+    /// description for the whole run and for each day over budget.
+    pub fn check(&self, report: &Report) -> Vec<String> {
+        let mut exceeded = Vec::new();
+        if let Some(budget) = self.total.filter(|&budget| report.total() > budget) {
+            exceeded.push(format!("the run took {:.3?}, over its budget of {budget:.3?}", report.total()));
+        }
+        for (day, duration) in report.day_totals() {
+            if let Some(&budget) = self.days.get(&day).filter(|&&budget| duration > budget) {
+                exceeded.push(format!("day {day} took {duration:.3?}, over its budget of {budget:.3?}"));
+            }
+        }
+        exceeded
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use aoc2015::answer::Answer;
+use aoc2015::input::year_dir;
+use aoc2015::solution::Part;
+use aoc2015::util::checkpoint::{Checkpoint, CheckpointStore};
+use aoc2015::util::md5;
+
+/// The directory holding the answers computed by past runs, with a
+/// subdirectory per year after 2015.
+const CACHE_DIR: &str = ".aoc-cache";
+
+/// Whether the runner reuses the answers of past runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Computes every answer, and stores it for later runs.
+    #[default]
+    Store,
+    /// Reuses the stored answer of a part run before on the same input,
+    /// computing and storing the others.
+    Reuse,
+}
+
+/// What identifies a computed answer: the same implementation of a part
+/// run on the same input finds the same answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    /// The name of the alternate implementation, or `default`.
+    pub implementation: String,
+    /// The hex MD5 digest of the input.
+    pub input: String,
+}
+
+impl Key {
+    /// Returns the key of a part run on `input`.
+    pub fn new(year: u32, day: u32, part: Part, implementation: Option<&str>, input: &str) -> Self {
+        let input = md5::digest(input.as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect();
+        Key { year, day, part, implementation: implementation.unwrap_or("default").to_string(), input }
+    }
+}
+
+/// An answer stored in the cache, along with the time it took to compute.
+struct Entry {
+    key: Key,
+    answer: String,
+    duration: Duration,
+}
+
+/// Returns the path of the cache file of a day of a year.
+fn cache_path(year: u32, day: u32) -> PathBuf {
+    year_dir(CACHE_DIR, year).join(format!("ex{day:02}.txt"))
+}
+
+/// Reads the cached answers of a day of a year, one tab-separated
+/// `part implementation input nanoseconds answer` line each, skipping
+/// unreadable lines.
+fn read_entries(year: u32, day: u32) -> io::Result<Vec<Entry>> {
+    let contents = match fs::read_to_string(cache_path(year, day)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(contents.lines()
+        .filter_map(|line| {
+            let [part, implementation, input, nanos, answer] = line.splitn(5, '\t').collect::<Vec<_>>()[..] else { return None };
+            Some(Entry {
+                key: Key { year, day, part: part.parse().ok()?, implementation: implementation.to_string(), input: input.to_string() },
+                answer: answer.to_string(),
+                duration: Duration::from_nanos(nanos.parse().ok()?),
+            })
+        })
+        .collect())
+}
+
+/// Returns the answer stored for `key`, and how long it took to compute.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the cache file of the day cannot be read.
+pub fn lookup(key: &Key) -> io::Result<Option<(Answer, Duration)>> {
+    Ok(read_entries(key.year, key.day)?.into_iter()
+        .find(|entry| entry.key == *key)
+        .map(|entry| (entry.answer.parse().unwrap_or_else(|never| match never {}), entry.duration)))
+}
+
+/// Stores the answer computed for `key`, replacing the one stored before.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the cache file of the day cannot be read or
+/// written.
+pub fn store(key: &Key, answer: &Answer, duration: Duration) -> io::Result<()> {
+    let mut entries = read_entries(key.year, key.day)?;
+    entries.retain(|entry| entry.key != *key);
+    entries.push(Entry { key: key.clone(), answer: answer.to_string(), duration });
+    let contents: String = entries.iter()
+        .map(|Entry { key, answer, duration }| {
+            format!("{}\t{}\t{}\t{}\t{answer}\n", key.part, key.implementation, key.input, duration.as_nanos())
+        })
+        .collect();
+    let path = cache_path(key.year, key.day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// The checkpoints saved by the search of a part on an input, in the
+/// `checkpoints/` directory of the cache of its year, so that a run
+/// interrupted before finding the answer can be resumed.
+///
+/// Failing to read or write a checkpoint is only logged, as it never
+/// prevents computing the answer.
+pub struct CheckpointFile {
+    path: PathBuf,
+    /// Whether to resume from the checkpoint saved by a past run, rather
+    /// than only saving new ones.
+    resume: bool,
+}
+
+impl CheckpointFile {
+    /// Returns the checkpoint file of the search identified by `key`.
+    pub fn new(key: &Key, resume: bool) -> Self {
+        let name = format!("ex{:02}{}-{}-{}.txt", key.day, key.part, key.implementation, key.input);
+        CheckpointFile { path: year_dir(CACHE_DIR, key.year).join("checkpoints").join(name), resume }
+    }
+
+    /// Removes the checkpoint of a search that completed.
+    pub fn remove(&self) {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => tracing::warn!("unable to remove the checkpoint {}: {err}", self.path.display()),
+            _ => {}
+        }
+    }
+}
+
+impl CheckpointStore for CheckpointFile {
+    fn load(&self) -> Option<Checkpoint> {
+        if !self.resume { return None; }
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                tracing::warn!("unable to read the checkpoint {}: {err}", self.path.display());
+                return None;
+            }
+        };
+        Checkpoint::decode(&text)
+            .inspect_err(|err| tracing::warn!("ignoring the checkpoint {}: {err}", self.path.display()))
+            .ok()
+    }
+
+    /// Writes the checkpoint to a temporary file renamed over the previous
+    /// one, so that a run interrupted while saving keeps a whole checkpoint.
+    fn save(&self, checkpoint: &Checkpoint) {
+        let temporary = self.path.with_extension("tmp");
+        let written = self.path.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&temporary, checkpoint.encode()))
+            .and_then(|()| fs::rename(&temporary, &self.path));
+        match written {
+            Ok(()) => tracing::debug!(path = %self.path.display(), "saved a checkpoint"),
+            Err(err) => tracing::warn!("unable to save the checkpoint {}: {err}", self.path.display()),
+        }
+    }
+}
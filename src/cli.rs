@@ -0,0 +1,389 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use aoc2015::config::CONFIG_FILE;
+use aoc2015::days;
+use aoc2015::gen::DEFAULT_SIZE;
+use aoc2015::input::InputSource;
+use aoc2015::solution::Part;
+use clap::builder::PossibleValuesParser;
+use clap::error::ErrorKind;
+use clap::{Arg, ArgGroup, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use crate::budget::parse_duration;
+
+/// Command-line interface of the Advent of Code runner, for 2015 unless
+/// `--year` selects another year.
+#[derive(Parser, Debug)]
+#[command(about = "Solutions to Advent of Code 2015")]
+#[command(group(ArgGroup::new("selection").required(true).args(["day", "all"])))]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The day to run.
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: Option<u32>,
+
+    /// The part of the day to run, `a` or `b`. Both parts are run if
+    /// omitted, and anything else is the INPUT of both parts, such as in
+    /// `aoc15 14 -`.
+    #[arg(value_name = "PART", value_parser = part_or_input, requires = "day")]
+    pub part_or_input: Option<PartOrInput>,
+
+    /// The file to read the input of the day from instead of
+    /// `data/exercise_NN.txt`, or `-` for the standard input.
+    #[arg(value_name = "INPUT", requires = "part_or_input", conflicts_with_all = ["input", "inputs"])]
+    pub source: Option<PathBuf>,
+
+    /// Runs every implemented day.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Runs the days concurrently on a pool of worker threads.
+    #[arg(long, requires = "all")]
+    pub parallel: bool,
+
+    /// The year of the puzzles, for running its days as for the commands,
+    /// after which it goes, such as `aoc15 wait 1 --year 2016`.
+    #[arg(long, global = true, default_value_t = days::DEFAULT_YEAR, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Reads the input of the day from this file instead of
+    /// `data/exercise_NN.txt`, or from the standard input for `-`.
+    #[arg(long, value_name = "FILE", requires = "day", conflicts_with = "inputs")]
+    pub input: Option<PathBuf>,
+
+    /// Runs the day on every file of this directory, printing the answers
+    /// of each.
+    #[arg(long, value_name = "DIR", requires = "day", conflicts_with = "watch")]
+    pub inputs: Option<PathBuf>,
+
+    /// Checks the examples of the puzzle text before running each day.
+    #[arg(long)]
+    pub examples: bool,
+
+    /// Only checks the examples of the puzzle text of each day, those its
+    /// module documentation demonstrates, without reading its input.
+    #[arg(long, conflicts_with_all = ["examples", "submit", "input", "inputs", "watch", "source"])]
+    pub doc_examples: bool,
+
+    /// Runs the alternate implementation with this name instead of the
+    /// default one, for the parts that have alternate implementations.
+    #[arg(long = "impl", value_name = "NAME", requires = "day")]
+    pub implementation: Option<String>,
+
+    /// Checks that every implementation of each part finds the same answer.
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Prints how each answer was found, for the days that explain it, such
+    /// as the best route of day 9. Answers are computed again instead of
+    /// being reused from the cache.
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Writes a picture of each part that has one, as a PGM image, to this
+    /// directory.
+    #[arg(long, value_name = "DIR")]
+    pub render: Option<PathBuf>,
+
+    /// Prints the description of the puzzle of the day as Markdown instead
+    /// of solving it, downloaded once and cached under `puzzles/` for
+    /// working offline.
+    #[arg(long, requires = "day", conflicts_with_all = ["examples", "submit", "render", "inputs", "watch"])]
+    pub open: bool,
+
+    /// Animates the grid of the day in the terminal instead of solving it.
+    #[cfg(feature = "viz")]
+    #[arg(long, requires = "day", conflicts_with_all = ["examples", "submit", "render", "inputs"])]
+    pub viz: bool,
+
+    /// Writes the animation of the lights of day 18 to this file as an
+    /// animated GIF instead of solving it.
+    #[cfg(feature = "export")]
+    #[arg(long, value_name = "FILE", requires = "day", conflicts_with_all = ["examples", "submit", "render", "inputs"])]
+    pub gif: Option<PathBuf>,
+
+    /// The side, in pixels, of the square drawn for each light of the GIF.
+    #[cfg(feature = "export")]
+    #[arg(long, default_value_t = 4, requires = "gif", value_parser = clap::value_parser!(u16).range(1..=64))]
+    pub scale: u16,
+
+    /// Samples the CPU profile of each part while it runs, and writes it to
+    /// this directory, `profiles/` by default, as folded stacks and as an
+    /// SVG flamegraph. Answers are computed again instead of being reused
+    /// from the cache, and only the warm-up run of `--repeat` is profiled.
+    #[cfg(feature = "profile")]
+    #[arg(long, value_name = "DIR", num_args = 0..=1, require_equals = true, default_missing_value = "profiles", conflicts_with_all = ["parallel", "watch"])]
+    pub profile: Option<PathBuf>,
+
+    /// Reuses the answers stored in `.aoc-cache/` by past runs of the same
+    /// implementation of a part on the same input, instead of computing
+    /// them again.
+    #[arg(long)]
+    pub cached: bool,
+
+    /// Computes every answer again, even with `--cached`, replacing the
+    /// stored ones.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Resumes the long searches of days 4 and 20 from the checkpoints
+    /// saved in `.aoc-cache/checkpoints/` by interrupted runs on the same
+    /// input, instead of starting them over.
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Runs each part this many more times after a first warm-up run, and
+    /// reports the minimum, median, mean and standard deviation of their
+    /// runtimes. Cached answers are not reused.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "watch")]
+    pub repeat: Option<u32>,
+
+    /// The TOML file of the parameters of the puzzles, such as
+    /// `race_duration = 1000` for day 14, to run the solvers on those of
+    /// the examples. The parameters it does not set, or all if it does not
+    /// exist, are those of the puzzles.
+    #[arg(long, value_name = "FILE", default_value = CONFIG_FILE)]
+    pub config: PathBuf,
+
+    /// The seed of the randomized strategies of the solutions, such as the
+    /// restarts of day 19, for reproducing a run. A fixed seed is used
+    /// otherwise.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Fails the run if it takes longer than this in total, such as `1s` or
+    /// `250ms`.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub budget: Option<Duration>,
+
+    /// Fails the run if it exceeds the budgets of this TOML file, which
+    /// holds the `total` budget and those of days such as `day04 = "2s"`.
+    #[arg(long, value_name = "FILE")]
+    pub budgets: Option<PathBuf>,
+
+    /// Runs the day again whenever its input changes, until interrupted.
+    #[arg(long, requires = "day", conflicts_with_all = ["submit", "output"])]
+    pub watch: bool,
+
+    /// Submits the answers to adventofcode.com once computed.
+    #[arg(long, requires = "day")]
+    pub submit: bool,
+
+    /// Logs what the solvers do to standard error: debug events with -v,
+    /// trace events with -vv. Without it, `RUST_LOG` selects the events.
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The format of the results.
+    #[arg(long, value_enum, default_value_t = Output::Text)]
+    pub output: Output,
+
+    /// Prints the results without colors, as does setting the `NO_COLOR`
+    /// environment variable. Colors are only used on terminals anyway.
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// The argument following the day, which is either the part to run or the
+/// input of both parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartOrInput {
+    Part(Part),
+    Input(PathBuf),
+}
+
+/// Parses the argument following the day, `a` or `b` being parts and
+/// anything else an input, so that a file named `a` reads as `./a`.
+fn part_or_input(value: &str) -> Result<PartOrInput, String> {
+    Ok(value.parse().map_or_else(|_| PartOrInput::Input(PathBuf::from(value)), PartOrInput::Part))
+}
+
+impl Cli {
+    /// Parses the command line, exiting with a usage error if it is invalid,
+    /// including when the input given in place of the part is also given
+    /// another way.
+    pub fn parse_args() -> Self {
+        let cli = Cli::parse();
+        if let Some(PartOrInput::Input(path)) = &cli.part_or_input {
+            let conflict = [
+                (cli.source.is_some(), "[INPUT]"),
+                (cli.input.is_some(), "--input <FILE>"),
+                (cli.inputs.is_some(), "--inputs <DIR>"),
+                (cli.doc_examples, "--doc-examples"),
+            ].into_iter().find_map(|(given, arg)| given.then_some(arg));
+            if let Some(arg) = conflict {
+                let message = format!("the input '{}' cannot be used with '{arg}'", path.display());
+                Cli::command().error(ErrorKind::ArgumentConflict, message).exit();
+            }
+        }
+        cli
+    }
+
+    /// Returns the part to run, or `None` to run both.
+    pub fn part(&self) -> Option<Part> {
+        match self.part_or_input {
+            Some(PartOrInput::Part(part)) => Some(part),
+            _ => None,
+        }
+    }
+
+    /// Returns where to read the input of the day from, as selected by
+    /// `--input` or the `INPUT` argument, which may follow the day or its
+    /// part.
+    pub fn input_source(&self) -> InputSource {
+        let input = match &self.part_or_input {
+            Some(PartOrInput::Input(path)) => Some(path.as_path()),
+            _ => self.input.as_deref().or(self.source.as_deref()),
+        };
+        input.map_or(InputSource::Data, InputSource::from_path)
+    }
+}
+
+/// Writes the completion script of a shell, whose day arguments only offer
+/// the implemented days of `year` instead of any day from 1 to 25.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the script cannot be written.
+pub fn write_completions(shell: Shell, year: u32, out: &mut dyn Write) -> io::Result<()> {
+    let days: Vec<String> = days::registry(year).iter().map(|solution| solution.day().to_string()).collect();
+    let parser = PossibleValuesParser::new(&days);
+    // Arguments are changed in place, as replacing them would reorder the
+    // positional ones.
+    let offer = move |arg: Arg| match arg.get_id().as_str() {
+        "day" => arg.value_parser(parser.clone()),
+        "part_or_input" => arg.value_parser(["a", "b"]),
+        _ => arg,
+    };
+    let mut command = Cli::command().mut_args(offer.clone()).mut_subcommand("check-input", |command| command.mut_args(offer));
+    clap_complete::generate(shell, &mut command, "aoc15", out);
+    if shell == Shell::Fish {
+        // The fish generator only completes options, not positional arguments.
+        let days = days.join(" ");
+        writeln!(out, "complete -c aoc15 -n \"__fish_aoc15_needs_command\" -f -a \"{days}\" -d 'Day'")?;
+        writeln!(out, "complete -c aoc15 -n \"__fish_aoc15_using_subcommand check-input\" -f -a \"{days}\" -d 'Day'")?;
+    }
+    Ok(())
+}
+
+/// A command run instead of solving puzzles.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Creates the module of a new day from a template and registers it.
+    New {
+        /// The day to create.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Prints a synthetic input of a day of 2015, generated from a seed.
+    Gen {
+        /// The day of the input.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// The size of the input, such as the number of lines, whose exact
+        /// meaning depends on the day.
+        #[arg(long, default_value_t = DEFAULT_SIZE)]
+        size: usize,
+
+        /// The seed of the generator. The same seed and size always produce
+        /// the same input.
+        #[arg(long, default_value_t = 2015)]
+        seed: u64,
+    },
+    /// Parses the input of a day without solving it, and points at the
+    /// first malformed line if any.
+    CheckInput {
+        /// The day of the input.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// The file to check instead of `data/exercise_NN.txt`, or `-` for
+        /// the standard input.
+        #[arg(value_name = "INPUT")]
+        input: Option<PathBuf>,
+    },
+    /// Checks every day without `cargo test`: the examples of the puzzle
+    /// texts, whether the alternate implementations agree on the real
+    /// inputs, and the golden answers.
+    Selftest {
+        /// The TOML file of golden answers, mapping each day such as
+        /// `[day01]` to the answers of its parts. Skipped if missing.
+        #[arg(long, value_name = "FILE", default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Runs every day and prints a Markdown table of the answers, runtimes
+    /// and notes on the solutions.
+    Report {
+        /// Reuses the answers stored in `.aoc-cache/` by past runs.
+        #[arg(long)]
+        cached: bool,
+    },
+    /// Sleeps until the puzzle of a day unlocks at midnight Eastern Standard
+    /// Time, then downloads its input.
+    Wait {
+        /// The day to wait for.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Creates the module of the day once its input is downloaded, as
+        /// the `new` command does.
+        #[arg(long)]
+        new: bool,
+    },
+    /// Prints a private leaderboard, fetched at most every 15 minutes and
+    /// cached under `data/` in between.
+    Leaderboard {
+        /// The id of the leaderboard, as in its URL.
+        id: u64,
+    },
+    /// Serves the solutions over HTTP until interrupted: `POST
+    /// /solve/{day}/{part}` with the input as body answers with the JSON
+    /// `answer` and `duration_ms`.
+    #[cfg(feature = "serve")]
+    Serve {
+        /// The address to listen on.
+        #[arg(long, default_value = "127.0.0.1:2015")]
+        address: String,
+    },
+    /// Prints the completion script of a shell, which completes the days
+    /// implemented at the time it is generated.
+    Completions {
+        /// The shell to complete for.
+        shell: Shell,
+    },
+    /// Prints the manual page of the command line tool, in roff.
+    Manpage,
+    /// Encrypts the plaintext inputs under `data/` with the key of
+    /// `AOC_INPUT_KEY`, replacing them by their sealed copies.
+    #[cfg(feature = "seal")]
+    Seal {
+        /// The days whose inputs to seal, every day if omitted.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        days: Vec<u32>,
+    },
+    /// Decrypts the sealed inputs under `data/` with the key of
+    /// `AOC_INPUT_KEY`, replacing them by their plaintext copies.
+    #[cfg(feature = "seal")]
+    Unseal {
+        /// The days whose inputs to unseal, every day if omitted.
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        days: Vec<u32>,
+    },
+}
+
+/// The format in which the runner prints its results.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// One line per part, followed by a summary table.
+    Text,
+    /// A single JSON document, printed once every day has run.
+    Json,
+    /// A Markdown table of the answers, runtimes and notes on the
+    /// solutions, printed once every day has run, as the `report` command
+    /// does.
+    Markdown,
+}
@@ -0,0 +1,142 @@
+//! The parameters the puzzles of 2015 fix besides their inputs, such as the
+//! duration of the reindeer race of day 14, read from `config.toml` so
+//! that the solvers can run on the smaller parameters of the examples
+//! without recompiling:
+//!
+//! ```toml
+//! race_duration = 1000
+//! eggnog_liters = 25
+//! ```
+//!
+//! Parameters missing from the file keep the values of the puzzles, and so
+//! does every parameter if the file does not exist.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use toml::Table;
+use crate::error::AocError;
+use crate::year2015::{ex01, ex06, ex14, ex17, ex18};
+
+/// The file the runner reads the [`PuzzleConfig`] from.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// The parameters of the puzzles, handed to the solvers through their
+/// [`Context`](crate::context::Context).
+///
+/// Only the solvers themselves use them: the alternate implementations
+/// always solve the puzzles with their own parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleConfig {
+    /// The floor whose first position the second part of day 1 finds.
+    pub basement_floor: i32,
+    /// The side of the square grid of lights of day 6.
+    pub light_grid_size: usize,
+    /// The duration of the race of day 14, in seconds.
+    pub race_duration: u32,
+    /// The liters of eggnog of day 17.
+    pub eggnog_liters: usize,
+    /// The number of steps of the animation of day 18, whose grid has the
+    /// size of its input.
+    pub animation_steps: usize,
+    /// The number of presents of day 20, instead of that of the input.
+    pub target_presents: Option<u32>,
+}
+
+impl PuzzleConfig {
+    /// The parameters of the puzzles.
+    pub const PUZZLE: PuzzleConfig = PuzzleConfig {
+        basement_floor: ex01::BASEMENT,
+        light_grid_size: ex06::SIZE,
+        race_duration: ex14::RACE_DURATION,
+        eggnog_liters: ex17::TARGET_LITERS,
+        animation_steps: ex18::STEPS,
+        target_presents: None,
+    };
+
+    /// Returns whether these are the parameters of the puzzles, for which
+    /// answers can be cached.
+    pub fn is_puzzle(&self) -> bool {
+        *self == PuzzleConfig::PUZZLE
+    }
+
+    /// Parses the parameters set by a TOML document, the others keeping
+    /// the values of the puzzles.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::config::PuzzleConfig;
+    /// let config = PuzzleConfig::parse("race_duration = 1000\ntarget_presents = 70").unwrap();
+    /// assert_eq!((config.race_duration, config.target_presents), (1000, Some(70)));
+    /// assert_eq!(config.eggnog_liters, PuzzleConfig::PUZZLE.eggnog_liters);
+    /// assert_eq!(PuzzleConfig::parse("\nrace = 1000").unwrap_err().to_string(), "line 2: unknown parameter race");
+    /// assert_eq!(PuzzleConfig::parse("race_duration = -1").unwrap_err().to_string(), "line 1: invalid race_duration -1");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the document is not valid TOML, or
+    /// holds an unknown parameter or an invalid value.
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let table: Table = text.parse().map_err(|err: toml::de::Error| {
+            let line = err.span().map_or(1, |span| text[..span.start].lines().count().max(1));
+            AocError::parse(line, err.message())
+        })?;
+        let mut config = PuzzleConfig::PUZZLE;
+        for (key, value) in &table {
+            let line = text.lines().position(|line| line.trim_start().starts_with(key.as_str())).map_or(1, |index| index + 1);
+            let invalid = || AocError::parse(line, format!("invalid {key} {value}"));
+            let number = || value.as_integer().ok_or_else(invalid);
+            match key.as_str() {
+                "basement_floor" => config.basement_floor = number()?.try_into().map_err(|_| invalid())?,
+                "light_grid_size" => config.light_grid_size = number()?.try_into().map_err(|_| invalid())?,
+                "race_duration" => config.race_duration = number()?.try_into().map_err(|_| invalid())?,
+                "eggnog_liters" => config.eggnog_liters = number()?.try_into().map_err(|_| invalid())?,
+                "animation_steps" => config.animation_steps = number()?.try_into().map_err(|_| invalid())?,
+                "target_presents" => config.target_presents = Some(number()?.try_into().map_err(|_| invalid())?),
+                _ => return Err(AocError::parse(line, format!("unknown parameter {key}"))),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Reads the parameters of a TOML file, as [`PuzzleConfig::parse`]
+    /// does.
+    ///
+    /// # Returns
+    /// The parameters, those of the puzzles if the file does not exist.
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the file cannot be read or parsed.
+    pub fn read(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => PuzzleConfig::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(PuzzleConfig::PUZZLE),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Describes the parameters that differ from those of the puzzles, such
+    /// as `race_duration = 1000`.
+    pub fn changes(&self) -> Vec<String> {
+        let puzzle = PuzzleConfig::PUZZLE;
+        let presents = |config: &PuzzleConfig| config.target_presents.map(|presents| presents.to_string());
+        [
+            ("basement_floor", Some(self.basement_floor.to_string()), Some(puzzle.basement_floor.to_string())),
+            ("light_grid_size", Some(self.light_grid_size.to_string()), Some(puzzle.light_grid_size.to_string())),
+            ("race_duration", Some(self.race_duration.to_string()), Some(puzzle.race_duration.to_string())),
+            ("eggnog_liters", Some(self.eggnog_liters.to_string()), Some(puzzle.eggnog_liters.to_string())),
+            ("animation_steps", Some(self.animation_steps.to_string()), Some(puzzle.animation_steps.to_string())),
+            ("target_presents", presents(self), presents(&puzzle)),
+        ]
+        .into_iter()
+        .filter(|(_, value, puzzle)| value != puzzle)
+        .filter_map(|(key, value, _)| Some(format!("{key} = {}", value?)))
+        .collect()
+    }
+}
+
+impl Default for PuzzleConfig {
+    fn default() -> Self {
+        PuzzleConfig::PUZZLE
+    }
+}
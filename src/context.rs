@@ -0,0 +1,53 @@
+//! The context a solver runs in: where it reports its progress, where it
+//! saves its checkpoints, the seed of its randomness, so that the solvers
+//! with randomized strategies are reproducible, and the parameters of the
+//! puzzles.
+
+use crate::config::PuzzleConfig;
+use crate::progress::{NoProgress, ProgressSink};
+use crate::util::checkpoint::{CheckpointStore, NoCheckpoint};
+use crate::util::rng::XorShift;
+
+/// What a [`Solution`](crate::solution::Solution) is handed to run a part,
+/// besides its input.
+///
+/// # Examples
+/// ```
+/// use aoc2015::context::Context;
+/// let context = Context { seed: 7, ..Context::default() };
+/// assert_eq!(context.rng().next_u64(), Context { seed: 7, ..Context::default() }.rng().next_u64());
+/// assert_ne!(context.rng().next_u64(), Context::default().rng().next_u64());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    /// The [`ProgressSink`] receiving the progress of long searches.
+    pub progress: &'a dyn ProgressSink,
+    /// The [`CheckpointStore`] of the part and input being solved.
+    pub checkpoint: &'a dyn CheckpointStore,
+    /// The seed of the randomized strategies, [`Context::DEFAULT_SEED`]
+    /// unless another one is chosen.
+    pub seed: u64,
+    /// The parameters of the puzzles, [`PuzzleConfig::PUZZLE`] unless
+    /// others are configured.
+    pub config: &'a PuzzleConfig,
+}
+
+impl Context<'_> {
+    /// The seed used when none is chosen, the same as that of the input
+    /// generators.
+    pub const DEFAULT_SEED: u64 = 2015;
+
+    /// Returns a generator seeded from [`Context::seed`], the same sequence
+    /// on every call.
+    pub fn rng(&self) -> XorShift {
+        XorShift::new(self.seed)
+    }
+}
+
+impl Default for Context<'_> {
+    /// A context reporting no progress, saving no checkpoints, using the
+    /// [`Context::DEFAULT_SEED`] and the parameters of the puzzles.
+    fn default() -> Self {
+        Context { progress: &NoProgress, checkpoint: &NoCheckpoint, seed: Context::DEFAULT_SEED, config: &PuzzleConfig::PUZZLE }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::solution::Solution;
+use crate::year2015;
+
+/// The solutions of one year of Advent of Code.
+#[derive(Clone, Copy)]
+pub struct Year {
+    /// The year of the puzzles.
+    pub year: u32,
+    /// Returns the [`Solution`] of every implemented day, ordered by day.
+    pub days: fn() -> Vec<Box<dyn Solution>>,
+}
+
+/// Every year with solutions, ordered by year. A new year is a `yearNNNN`
+/// module registered here.
+pub const YEARS: &[Year] = &[
+    Year { year: year2015::YEAR, days: year2015::days },
+];
+
+/// The year run when none is selected.
+pub const DEFAULT_YEAR: u32 = year2015::YEAR;
+
+/// Returns the [`Solution`] of every implemented day of a year, ordered by
+/// day, none if the year has no solutions.
+///
+/// # Examples
+/// ```
+/// use aoc2015::days::registry;
+/// assert_eq!(registry(2015).len(), 24);
+/// assert!(registry(2015).iter().all(|solution| solution.year() == 2015));
+/// assert!(registry(2016).is_empty());
+/// ```
+pub fn registry(year: u32) -> Vec<Box<dyn Solution>> {
+    YEARS.iter().find(|entry| entry.year == year).map_or_else(Vec::new, |entry| (entry.days)())
+}
+
+/// Returns the [`Solution`] for the provided day, if it is implemented.
+///
+/// # Arguments
+/// * `year` - The year of the day.
+/// * `day` - The day to look up.
+pub fn find(year: u32, day: u32) -> Option<Box<dyn Solution>> {
+    registry(year).into_iter().find(|solution| solution.day() == day)
+}
@@ -0,0 +1,98 @@
+//! Diagnostics pointing at the offending part of a malformed input, in the
+//! style of compiler errors:
+//!
+//! ```text
+//! error: invalid instruction 'x'
+//!  --> line 1, column 4
+//!   |
+//! 1 | (()x)
+//!   |    ^
+//! ```
+
+use std::fmt;
+use crate::error::AocError;
+
+/// The most characters of a line shown by a [`Diagnostic`]. Longer lines,
+/// such as the single line of day 1, are cut around the offending part.
+const MAX_WIDTH: usize = 72;
+
+/// A parse error located in the input it was raised for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic<'a> {
+    /// The 1-based number of the offending line.
+    pub line: usize,
+    /// The 1-based column of the first offending character, if known.
+    pub column: Option<usize>,
+    /// The number of offending characters, underlined from `column`.
+    pub width: usize,
+    /// The offending line, or `None` if the error is past the end of the
+    /// input, such as a missing line.
+    pub source: Option<&'a str>,
+    /// What is wrong with the line.
+    pub message: &'a str,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Locates an [`AocError::Parse`] in the input it was raised for.
+    ///
+    /// Errors without a column are located from their message instead: the
+    /// last text quoted in the message that appears in the line, such as
+    /// the `abc` of `invalid number 'abc'`, is taken as the offending part.
+    ///
+    /// # Returns
+    /// The [`Diagnostic`], or `None` if `err` is not an [`AocError::Parse`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::diagnostic::Diagnostic;
+    /// use aoc2015::error::AocError;
+    /// let input = "2x3x4\n1x1xten";
+    /// let err = AocError::parse(2, "invalid number 'ten'");
+    /// let diagnostic = Diagnostic::locate(input, &err).unwrap();
+    /// assert_eq!((diagnostic.line, diagnostic.column, diagnostic.width), (2, Some(5), 3));
+    /// assert_eq!(diagnostic.to_string(), "error: invalid number 'ten'\n --> line 2, column 5\n  |\n2 | 1x1xten\n  |     ^^^\n");
+    /// ```
+    pub fn locate(input: &'a str, err: &'a AocError) -> Option<Self> {
+        let AocError::Parse { line, column, message } = err else { return None };
+        let source = line.checked_sub(1).and_then(|index| input.lines().nth(index));
+        let (column, width) = match (column, source) {
+            (Some(column), _) => (Some(*column), 1),
+            (None, Some(source)) => match quoted(message).filter_map(|quote| Some((source.find(quote)?, quote))).last() {
+                Some((start, quote)) => (Some(source[..start].chars().count() + 1), quote.chars().count()),
+                None => (None, 0),
+            },
+            (None, None) => (None, 0),
+        };
+        Some(Diagnostic { line: *line, column, width, source, message })
+    }
+}
+
+/// Returns the non-empty texts between single quotes in `message`, in order.
+fn quoted(message: &str) -> impl Iterator<Item = &str> {
+    message.split('\'').skip(1).step_by(2).filter(|quote| !quote.is_empty())
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "error: {}", self.message)?;
+        match self.column {
+            Some(column) => writeln!(f, " --> line {}, column {column}", self.line)?,
+            None => writeln!(f, " --> line {}", self.line)?,
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{gutter} |")?;
+        let Some(source) = self.source else { return Ok(()) };
+        let chars: Vec<char> = source.chars().collect();
+        // The offending part, or else the start of the line, stays in view.
+        let focus = self.column.map_or(0, |column| column.saturating_sub(1)).min(chars.len());
+        let start = if chars.len() <= MAX_WIDTH { 0 } else { focus.saturating_sub(MAX_WIDTH / 2).min(chars.len() - MAX_WIDTH) };
+        let end = chars.len().min(start + MAX_WIDTH);
+        let (before, after) = (if start > 0 { "…" } else { "" }, if end < chars.len() { "…" } else { "" });
+        writeln!(f, "{} | {before}{}{after}", self.line, chars[start..end].iter().collect::<String>())?;
+        if self.column.is_some() {
+            let carets = self.width.clamp(1, (end - focus).max(1));
+            writeln!(f, "{gutter} | {}{}", " ".repeat(usize::from(start > 0) + focus - start), "^".repeat(carets))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// An error raised while obtaining or solving a puzzle input.
+#[derive(Debug)]
+pub enum AocError {
+    /// A line of the input could not be parsed.
+    Parse {
+        /// The 1-based number of the offending line.
+        line: usize,
+        /// The 1-based column of the offending character, in characters,
+        /// when the parser knows it.
+        column: Option<usize>,
+        /// What is wrong with the line.
+        message: String,
+    },
+    /// The input was parsed but admits no answer.
+    Unsolvable(String),
+    /// The input of a day is not cached and could not be downloaded.
+    MissingInput {
+        /// The day of the input.
+        day: u32,
+        /// Why the input could not be obtained.
+        reason: String,
+    },
+    /// An example of the puzzle text gives the wrong answer.
+    Example {
+        /// The day of the example.
+        day: u32,
+        /// How the answer differs from the expected one.
+        message: String,
+    },
+    /// An alternate implementation is unknown or disagrees with the others.
+    Implementation {
+        /// The day of the implementation.
+        day: u32,
+        /// What is wrong with the implementation.
+        message: String,
+    },
+    /// An I/O operation failed.
+    Io(io::Error),
+}
+
+impl AocError {
+    /// Creates an [`AocError::Parse`] for the provided line.
+    pub fn parse(line: usize, message: impl fmt::Display) -> Self {
+        AocError::Parse { line, column: None, message: message.to_string() }
+    }
+
+    /// Creates an [`AocError::Parse`] for the provided line and column.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::error::AocError;
+    /// assert_eq!(AocError::parse_at(1, 4, "invalid instruction 'x'").to_string(), "line 1, column 4: invalid instruction 'x'");
+    /// ```
+    pub fn parse_at(line: usize, column: usize, message: impl fmt::Display) -> Self {
+        AocError::Parse { line, column: Some(column), message: message.to_string() }
+    }
+
+    /// Creates an [`AocError::Unsolvable`] with the provided explanation.
+    pub fn unsolvable(message: impl fmt::Display) -> Self {
+        AocError::Unsolvable(message.to_string())
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse { line, column: None, message } => write!(f, "line {line}: {message}"),
+            AocError::Parse { line, column: Some(column), message } => write!(f, "line {line}, column {column}: {message}"),
+            AocError::Unsolvable(message) => write!(f, "no solution: {message}"),
+            AocError::MissingInput { day, reason } => write!(f, "no input for day {day}: {reason}"),
+            AocError::Example { day, message } => write!(f, "example of day {day} failed: {message}"),
+            AocError::Implementation { day, message } => write!(f, "implementation of day {day}: {message}"),
+            AocError::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+/// Parses every line of `input`, attaching the line number to any error.
+///
+/// # Arguments
+/// * `input` - The input to parse.
+/// * `parse` - Parses a single line.
+///
+/// # Examples
+/// ```
+/// use aoc2015::error::{parse_lines, AocError};
+/// let numbers: Vec<u32> = parse_lines("1\n2", |line| line.parse()).unwrap();
+/// assert_eq!(numbers, [1, 2]);
+/// let err = parse_lines("1\nx", |line| line.parse::<u32>()).unwrap_err();
+/// assert!(matches!(err, AocError::Parse { line: 2, .. }));
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] for the first line that fails to parse.
+pub fn parse_lines<'a, T, E: fmt::Display>(input: &'a str, mut parse: impl FnMut(&'a str) -> Result<T, E>) -> Result<Vec<T>, AocError> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|err| AocError::parse(index + 1, err)))
+        .collect::<Result<Vec<_>, _>>()
+        .inspect(|items| tracing::debug!(lines = items.len(), "parsed the input"))
+}
+
+/// Parses every line read from `reader`, attaching the line number to any
+/// error.
+///
+/// Unlike [`parse_lines`], the lines are read and parsed one at a time as
+/// the returned iterator advances, so only the current line is ever held
+/// in memory.
+///
+/// # Arguments
+/// * `reader` - The reader of the input to parse.
+/// * `parse` - Parses a single line.
+///
+/// # Examples
+/// ```
+/// use aoc2015::error::{parse_stream, AocError};
+/// let mut numbers = parse_stream("1\nx".as_bytes(), |line| line.parse::<u32>());
+/// assert_eq!(numbers.next().unwrap().unwrap(), 1);
+/// assert!(matches!(numbers.next().unwrap(), Err(AocError::Parse { line: 2, .. })));
+/// ```
+///
+/// # Errors
+/// Yields an [`AocError::Parse`] for each line that fails to parse, and an
+/// [`AocError::Io`] if a line cannot be read.
+pub fn parse_stream<T, E: fmt::Display>(
+    reader: impl BufRead,
+    mut parse: impl FnMut(&str) -> Result<T, E>,
+) -> impl Iterator<Item = Result<T, AocError>> {
+    reader.lines()
+        .enumerate()
+        .map(move |(index, line)| parse(&line?).map_err(|err| AocError::parse(index + 1, err)))
+}
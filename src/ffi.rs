@@ -0,0 +1,163 @@
+//! C-compatible bindings to the solver registry, to call the solutions from
+//! C or Python benchmark harnesses.
+//!
+//! The library built as a `cdylib` exports a single function, declared in C
+//! as:
+//!
+//! ```c
+//! int32_t aoc2015_solve(uint32_t day, char part,
+//!                       const uint8_t *input_ptr, size_t input_len,
+//!                       uint8_t *out_buf, size_t *out_len);
+//! ```
+
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use crate::days;
+use crate::error::AocError;
+use crate::solution::Part;
+use crate::year2015;
+
+/// The status returned by [`aoc2015_solve`].
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The answer was written to the output buffer.
+    Ok = 0,
+    /// A pointer is null.
+    NullPointer = 1,
+    /// The day is not implemented.
+    UnknownDay = 2,
+    /// The part is neither `'a'` nor `'b'`.
+    UnknownPart = 3,
+    /// The input is not valid UTF-8.
+    InvalidUtf8 = 4,
+    /// The input is malformed.
+    Parse = 5,
+    /// The input admits no answer.
+    Unsolvable = 6,
+    /// The solver failed in another way.
+    Failed = 7,
+    /// The solver panicked.
+    Panicked = 8,
+    /// The answer does not fit in the output buffer.
+    BufferTooSmall = 9,
+}
+
+impl From<&AocError> for ErrorCode {
+    fn from(err: &AocError) -> Self {
+        match err {
+            AocError::Parse { .. } => ErrorCode::Parse,
+            AocError::Unsolvable(_) => ErrorCode::Unsolvable,
+            _ => ErrorCode::Failed,
+        }
+    }
+}
+
+/// Solves a part of the puzzle of a day, writing the answer as UTF-8 to a
+/// buffer owned by the caller.
+///
+/// # Arguments
+/// * `day` - The day of the puzzle, of 2015.
+/// * `part` - The part to solve, `'a'` or `'b'`.
+/// * `input_ptr` - The puzzle input, as UTF-8 bytes.
+/// * `input_len` - The number of bytes of the input.
+/// * `out_buf` - Receives the answer, without a terminating nul byte.
+/// * `out_len` - The capacity of `out_buf` on entry, and the length of the
+///   answer on return.
+///
+/// # Returns
+/// [`ErrorCode::Ok`], or the reason of the failure as an [`ErrorCode`].
+/// When the solver fails, as much of the error message as fits is written
+/// to `out_buf` instead of the answer. With [`ErrorCode::BufferTooSmall`],
+/// `out_len` is set to the length the buffer needs and nothing is written.
+///
+/// # Safety
+/// `input_ptr` must point to `input_len` readable bytes, `out_len` to a
+/// valid `size_t` and `out_buf` to `*out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2015_solve(
+    day: u32,
+    part: c_char,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if input_ptr.is_null() || out_buf.is_null() || out_len.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    // SAFETY: the caller guarantees the validity of the pointers.
+    let (input, out, out_len) = unsafe {
+        (slice::from_raw_parts(input_ptr, input_len), slice::from_raw_parts_mut(out_buf, *out_len), &mut *out_len)
+    };
+    let (code, text) = solve(day, part as u8, input);
+    match code {
+        ErrorCode::Ok if text.len() > out.len() => {
+            *out_len = text.len();
+            ErrorCode::BufferTooSmall as i32
+        }
+        _ => {
+            // Error messages are truncated rather than reported as too long.
+            let written = text.len().min(out.len());
+            out[..written].copy_from_slice(&text.as_bytes()[..written]);
+            *out_len = written;
+            code as i32
+        }
+    }
+}
+
+/// Solves a part of the puzzle of a day, catching panics so that they do
+/// not unwind into foreign code.
+///
+/// # Returns
+/// The [`ErrorCode`], along with the answer or the error message.
+fn solve(day: u32, part: u8, input: &[u8]) -> (ErrorCode, String) {
+    let Some(solution) = days::find(year2015::YEAR, day) else {
+        return (ErrorCode::UnknownDay, format!("day {day} is not implemented"));
+    };
+    let part = match part {
+        b'a' | b'A' => Part::A,
+        b'b' | b'B' => Part::B,
+        _ => return (ErrorCode::UnknownPart, format!("unknown part {:?}", char::from(part))),
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return (ErrorCode::InvalidUtf8, "the input is not valid UTF-8".to_string());
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, input.trim_end()))) {
+        Ok(Ok(answer)) => (ErrorCode::Ok, answer.to_string()),
+        Ok(Err(err)) => (ErrorCode::from(&err), err.to_string()),
+        Err(_) => (ErrorCode::Panicked, format!("day {day} panicked")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calls [`aoc2015_solve`] with an output buffer of `capacity` bytes.
+    fn call(day: u32, part: u8, input: &str, capacity: usize) -> (i32, usize, Vec<u8>) {
+        let mut buffer = vec![0; capacity];
+        let mut len = capacity;
+        // SAFETY: the pointers come from live slices of the right lengths.
+        let code = unsafe {
+            aoc2015_solve(day, part as c_char, input.as_ptr(), input.len(), buffer.as_mut_ptr(), &mut len)
+        };
+        (code, len, buffer)
+    }
+
+    #[test]
+    fn solves() {
+        let (code, len, buffer) = call(1, b'b', "()())", 16);
+        assert_eq!(code, ErrorCode::Ok as i32);
+        assert_eq!(&buffer[..len], b"5");
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(call(15, b'a', "", 16).0, ErrorCode::UnknownDay as i32);
+        assert_eq!(call(1, b'c', "", 16).0, ErrorCode::UnknownPart as i32);
+        assert_eq!(call(7, b'a', "1 -> b", 64).0, ErrorCode::Unsolvable as i32);
+        assert_eq!(call(1, b'a', "((((((((((", 1), (ErrorCode::BufferTooSmall as i32, 2, vec![0]));
+    }
+}
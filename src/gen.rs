@@ -0,0 +1,417 @@
+//! Deterministic generators of synthetic puzzle inputs of 2015, to
+//! benchmark how the solutions scale and to exercise the parsers without
+//! publishing real inputs.
+//!
+//! Every generated input is valid: it parses and both parts have an
+//! answer. The meaning of the size depends on the day, and sizes the
+//! solutions cannot handle are capped:
+//!
+//! | Day | Size |
+//! |-----|------|
+//! | 1, 3 | Number of instructions |
+//! | 2 | Number of boxes |
+//! | 4, 11, 21, 22 | Ignored |
+//! | 5, 8 | Number of strings |
+//! | 6 | Number of instructions |
+//! | 7 | Number of wires |
+//! | 9 | Number of cities, at most 20 |
+//! | 10 | Number of digits of the seed, at most 10 |
+//! | 12 | Number of values in the document |
+//! | 13 | Number of guests, at most 19 as the second part adds one |
+//! | 14 | Number of reindeer |
+//! | 16 | Number of Sues |
+//! | 17 | Number of containers, at most 200 |
+//! | 18 | Side of the grid |
+//! | 19 | Number of replacements fabricating the molecule |
+//! | 20 | Number of presents |
+//! | 23 | Number of instructions computing the starting values |
+//! | 24 | Number of packages, rounded up to a multiple of 12, from 24 to 48 |
+//! | 25 | Largest row and column |
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use crate::util::rng::XorShift;
+use crate::util::tsp::MAX_NODES;
+use crate::year2015::ex16::{Comparison, RANGES, READOUT};
+
+/// The size of the inputs generated when none is chosen.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// The most digits of a seed of day 10, whose length grows by about 30%
+/// at each of the 50 steps.
+const MAX_SEED_DIGITS: usize = 10;
+
+/// The most containers of day 17, far below those whose combinations
+/// overflow.
+const MAX_CONTAINERS: usize = 200;
+
+/// The most blocks of 12 packages of day 24, beyond which the quantum
+/// entanglements overflow.
+const MAX_PACKAGE_BLOCKS: usize = 4;
+
+/// Generates an input of the provided day.
+///
+/// # Arguments
+/// * `day` - The day of the input.
+/// * `size` - The size of the input, whose meaning depends on the day.
+/// * `seed` - The seed of the generator: the same seed and size always
+///   produce the same input.
+///
+/// # Returns
+/// The input, or `None` if the day is not implemented.
+///
+/// # Examples
+/// ```
+/// use aoc2015::gen::generate;
+/// let input = generate(2, 3, 7).unwrap();
+/// assert_eq!(input.lines().count(), 3);
+/// assert_eq!(aoc2015::gen::generate(2, 3, 7).unwrap(), input);
+/// ```
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<String> {
+    let mut rng = XorShift::new(seed);
+    let rng = &mut rng;
+    let input = match day {
+        1 => (0..size).map(|_| *rng.choose(&['(', ')'])).collect(),
+        2 => lines(size, |_| format!("{}x{}x{}", rng.range(1..=30), rng.range(1..=30), rng.range(1..=30))),
+        3 => (0..size).map(|_| *rng.choose(&['^', 'v', '<', '>'])).collect(),
+        4 | 11 => letters(rng, 8),
+        5 => lines(size, |_| letters(rng, 16)),
+        6 => lines(size, |_| light_instruction(rng)),
+        7 => circuit(rng, size.max(2)),
+        8 => lines(size, |_| string_literal(rng)),
+        9 => lines_of_pairs(size.clamp(2, MAX_NODES), |from, to| format!("{} to {} = {}", name(from), name(to), rng.range(10..=150))),
+        10 => (0..size.clamp(1, MAX_SEED_DIGITS)).map(|_| char::from(b'0' + rng.range(1..=3) as u8)).collect(),
+        12 => {
+            // The document is an array taking values until the budget is spent.
+            let (mut document, mut budget) = (String::from('['), size.max(1));
+            while budget > 0 {
+                if document.len() > 1 { document.push(','); }
+                json_value(rng, &mut budget, 0, &mut document);
+            }
+            document.push(']');
+            document
+        }
+        13 => {
+            let guests = size.clamp(2, MAX_NODES - 1);
+            lines(guests * (guests - 1), |line| {
+                let (guest, neighbor) = (line / (guests - 1), line % (guests - 1));
+                let neighbor = if neighbor >= guest { neighbor + 1 } else { neighbor };
+                let (sign, amount) = (*rng.choose(&["gain", "lose"]), rng.range(0..=100));
+                format!("{} would {sign} {amount} happiness units by sitting next to {}.", name(guest), name(neighbor))
+            })
+        }
+        14 => lines(size.max(1), |index| format!(
+            "{} can fly {} km/s for {} seconds, but then must rest for {} seconds.",
+            name(index), rng.range(2..=30), rng.range(2..=20), rng.range(20..=180),
+        )),
+        16 => sues(rng, size.max(2)),
+        17 => lines(size.min(MAX_CONTAINERS), |_| rng.range(5..=50).to_string()),
+        18 => {
+            let side = size.max(1);
+            lines(side, |_| (0..side).map(|_| *rng.choose(&['#', '.'])).collect())
+        }
+        19 => molecule(rng, size.max(1)),
+        20 => rng.range(size.max(10) as u64..=2 * size.max(10) as u64).to_string(),
+        21 => format!("Hit Points: {}\nDamage: {}\nArmor: {}", rng.range(90..=110), rng.range(7..=9), rng.range(1..=3)),
+        22 => format!("Hit Points: {}\nDamage: {}", rng.range(50..=70), rng.range(8..=10)),
+        23 => program(rng, size),
+        24 => packages(rng, size.div_ceil(12).clamp(2, MAX_PACKAGE_BLOCKS)),
+        25 => format!(
+            "To continue, please consult the code grid in the manual.  Enter the code at row {}, column {}.",
+            rng.range(1..=size.max(1) as u64), rng.range(1..=size.max(1) as u64),
+        ),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Joins `count` lines produced by `line` from their index.
+fn lines(count: usize, line: impl FnMut(usize) -> String) -> String {
+    (0..count).map(line).collect::<Vec<_>>().join("\n")
+}
+
+/// Joins one line produced by `line` for every unordered pair of `count`
+/// items.
+fn lines_of_pairs(count: usize, mut line: impl FnMut(usize, usize) -> String) -> String {
+    let pairs: Vec<_> = (0..count).flat_map(|from| (from + 1..count).map(move |to| (from, to))).collect();
+    lines(pairs.len(), |index| line(pairs[index].0, pairs[index].1))
+}
+
+/// Returns `count` random lowercase letters.
+fn letters(rng: &mut XorShift, count: usize) -> String {
+    (0..count).map(|_| char::from(b'a' + rng.below(26) as u8)).collect()
+}
+
+/// Returns a distinct capitalized name of at least 2 letters for every
+/// index, such as `Ab`.
+fn name(index: usize) -> String {
+    let mut name = String::from(char::from(b'A' + (index % 26) as u8));
+    let mut rest = index / 26;
+    loop {
+        name.push(char::from(b'a' + (rest % 26) as u8));
+        rest /= 26;
+        if rest == 0 { return name; }
+    }
+}
+
+/// Returns a random instruction of day 6.
+fn light_instruction(rng: &mut XorShift) -> String {
+    let op = rng.choose(&["turn on", "turn off", "toggle"]);
+    let (x0, x1) = (rng.range(0..=999), rng.range(0..=999));
+    let (y0, y1) = (rng.range(0..=999), rng.range(0..=999));
+    format!("{op} {},{} through {},{}", x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1))
+}
+
+/// Returns a circuit of `wires` wires for day 7, the first one being `b`
+/// and the last one `a`.
+///
+/// Every gate reads wires declared before it, so the circuit has no cycle,
+/// and picks them at random, so its depth stays logarithmic.
+fn circuit(rng: &mut XorShift, wires: usize) -> String {
+    let wire = |index: usize| match index {
+        0 => "b".to_string(),
+        index if index == wires - 1 => "a".to_string(),
+        index => name(index).to_lowercase(),
+    };
+    let mut lines: Vec<_> = (0..wires).map(|index| {
+        let input = |rng: &mut XorShift| match index {
+            0 => rng.range(0..=65535).to_string(),
+            _ if rng.below(8) == 0 => rng.range(0..=65535).to_string(),
+            _ => wire(rng.below(index as u64) as usize),
+        };
+        let expression = match rng.below(6) {
+            0 => input(rng),
+            1 => format!("NOT {}", input(rng)),
+            2 => format!("{} AND {}", input(rng), input(rng)),
+            3 => format!("{} OR {}", input(rng), input(rng)),
+            4 => format!("{} LSHIFT {}", input(rng), rng.range(1..=15)),
+            _ => format!("{} RSHIFT {}", input(rng), rng.range(1..=15)),
+        };
+        format!("{expression} -> {}", wire(index))
+    }).collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+/// Returns a random string literal of day 8, with every kind of escape.
+fn string_literal(rng: &mut XorShift) -> String {
+    let mut literal = String::from('"');
+    for _ in 0..rng.range(0..=20) {
+        match rng.below(10) {
+            0 => literal.push_str("\\\\"),
+            1 => literal.push_str("\\\""),
+            2 => write!(literal, "\\x{:02x}", rng.below(256)).unwrap(),
+            _ => literal.push(char::from(b'a' + rng.below(26) as u8)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Appends a random JSON value of day 12 to `document`, using up to
+/// `budget` numbers, strings, arrays and objects.
+fn json_value(rng: &mut XorShift, budget: &mut usize, depth: usize, document: &mut String) {
+    *budget = budget.saturating_sub(1);
+    let container = *budget > 0 && depth < 32 && rng.below(3) == 0;
+    if !container {
+        if rng.below(3) == 0 {
+            let text = if rng.below(4) == 0 { "red".to_string() } else { letters(rng, 4) };
+            write!(document, "\"{text}\"").unwrap();
+        } else {
+            write!(document, "{}", rng.range(0..=400) as i64 - 100).unwrap();
+        }
+        return;
+    }
+    match rng.below(2) {
+        0 => {
+            document.push('[');
+            for index in 0..rng.range(1..=8) {
+                if *budget == 0 { break; }
+                if index > 0 { document.push(','); }
+                json_value(rng, budget, depth + 1, document);
+            }
+            document.push(']');
+        }
+        _ => {
+            // Member names are distinct, as parsers disagree on duplicates.
+            let mut names: Vec<char> = ('a'..='z').collect();
+            rng.shuffle(&mut names);
+            let count = rng.range(1..=8) as usize;
+            document.push('{');
+            for (index, name) in names[..count].iter().enumerate() {
+                if *budget == 0 { break; }
+                if index > 0 { document.push(','); }
+                write!(document, "\"{name}\":").unwrap();
+                json_value(rng, budget, depth + 1, document);
+            }
+            document.push('}');
+        }
+    }
+}
+
+/// Returns `count` Sues of day 16 with 3 random compounds each, one of them
+/// matching the readout of the first part and another the second part.
+fn sues(rng: &mut XorShift, count: usize) -> String {
+    let first = rng.below(count as u64) as usize;
+    let second = (first + 1 + rng.below(count as u64 - 1) as usize) % count;
+    lines(count, |index| {
+        let mut compounds = READOUT.to_vec();
+        rng.shuffle(&mut compounds);
+        let attributes: Vec<_> = compounds[..3].iter()
+            .map(|&(compound, readout)| {
+                let rule = RANGES.iter().find(|(name, _)| *name == compound).map(|&(_, rule)| rule);
+                let value = match (index == first, index == second, rule) {
+                    (true, _, _) | (_, true, None) => readout,
+                    (_, true, Some(Comparison::Greater)) => readout + rng.range(1..=5) as u32,
+                    // The readouts of the compounds with fewer units are not 0.
+                    (_, true, Some(_)) => rng.below(u64::from(readout)) as u32,
+                    _ => rng.range(0..=10) as u32,
+                };
+                format!("{compound}: {value}")
+            })
+            .collect();
+        format!("Sue {}: {}", index + 1, attributes.join(", "))
+    })
+}
+
+/// Returns the replacements and the medicine molecule of day 19, the
+/// molecule being fabricated from `e` with `steps` replacements.
+///
+/// Every replacement wraps atoms between `Rn`, `Y` and `Ar`, which no
+/// replacement produces from anything else, and no two replacements
+/// produce the same atoms. Reducing the molecule back to `e` can then only
+/// undo the innermost replacements, so the greedy reduction never gets
+/// stuck.
+fn molecule(rng: &mut XorShift, steps: usize) -> String {
+    const ATOMS: [&str; 8] = ["Al", "B", "Ca", "F", "H", "Mg", "O", "Ti"];
+    let mut produced = HashSet::new();
+    let mut rules = Vec::new();
+    let sources = std::iter::repeat_n("e", 3).chain(ATOMS.into_iter().flat_map(|atom| std::iter::repeat_n(atom, 3)));
+    for from in sources {
+        let to = loop {
+            let to = match rng.below(2) {
+                0 => format!("{}Rn{}Ar", rng.choose(&ATOMS), rng.choose(&ATOMS)),
+                _ => format!("{}Rn{}Y{}Ar", rng.choose(&ATOMS), rng.choose(&ATOMS), rng.choose(&ATOMS)),
+            };
+            if produced.insert(to.clone()) { break to; }
+        };
+        rules.push((from, to));
+    }
+    rng.shuffle(&mut rules);
+
+    let mut atoms = vec!["e".to_string()];
+    for _ in 0..steps {
+        let index = loop {
+            let index = rng.below(atoms.len() as u64) as usize;
+            if !matches!(atoms[index].as_str(), "Rn" | "Y" | "Ar") { break index; }
+        };
+        let candidates: Vec<_> = rules.iter().filter(|(from, _)| *from == atoms[index]).collect();
+        let (_, to) = rng.choose(&candidates);
+        atoms.splice(index..=index, split_atoms(to));
+    }
+    let rules = lines(rules.len(), |index| format!("{} => {}", rules[index].0, rules[index].1));
+    format!("{rules}\n\n{}", atoms.concat())
+}
+
+/// Splits a molecule into its atoms, each an uppercase letter optionally
+/// followed by a lowercase one.
+fn split_atoms(molecule: &str) -> Vec<String> {
+    let mut atoms: Vec<String> = Vec::new();
+    for letter in molecule.chars() {
+        match atoms.last_mut() {
+            Some(atom) if letter.is_ascii_lowercase() => atom.push(letter),
+            _ => atoms.push(letter.to_string()),
+        }
+    }
+    atoms
+}
+
+/// Returns a program of day 23 in the shape of the puzzle: two branches of
+/// `size` instructions compute a starting value of register `a` from 0 for
+/// the first part and from 1 for the second, then a loop counts the steps
+/// of the Collatz sequence of that value in register `b`.
+fn program(rng: &mut XorShift, size: usize) -> String {
+    /// The largest value a branch computes, so that the sequence does not
+    /// overflow.
+    const LARGEST: u64 = 1_000_000_000;
+    let mut branch = |mut value: u64| {
+        let mut instructions = Vec::new();
+        for _ in 0..size {
+            if value > 0 && value * 3 <= LARGEST && rng.below(2) == 0 {
+                value *= 3;
+                instructions.push("tpl a");
+            } else if value < LARGEST {
+                value += 1;
+                instructions.push("inc a");
+            }
+        }
+        if value == 0 { instructions.push("inc a"); }
+        instructions
+    };
+    let (first, second) = (branch(0), branch(1));
+    let mut program = vec![format!("jio a, +{}", first.len() + 2)];
+    program.extend(first.into_iter().map(String::from));
+    program.push(format!("jmp +{}", second.len() + 1));
+    program.extend(second.into_iter().map(String::from));
+    program.extend(["jio a, +8", "inc b", "jie a, +4", "tpl a", "inc a", "jmp +2", "hlf a", "jmp -7"].map(String::from));
+    program.join("\n")
+}
+
+/// Returns the weights of `12 * blocks` packages of day 24, all different,
+/// `blocks` being at least 2.
+///
+/// The packages are drawn as 12 blocks of the same total weight, so they
+/// split into 3 or 4 groups of equal weight. All but one weight of every
+/// block are distinct numbers below twice as many as needed, and the last
+/// one balances the block above them all, the blocks being drawn again in
+/// the rare case two of them would need the same balance.
+fn packages(rng: &mut XorShift, blocks: usize) -> String {
+    let small = 24 * (blocks as u64 - 1);
+    let total = small * blocks as u64 + 1;
+    let mut pool: Vec<u64> = (1..=small).collect();
+    loop {
+        rng.shuffle(&mut pool);
+        let mut weights = Vec::new();
+        let mut balances = HashSet::new();
+        for block in pool.chunks(blocks - 1).take(12) {
+            let balance = total - block.iter().sum::<u64>();
+            balances.insert(balance);
+            weights.extend_from_slice(block);
+            weights.push(balance);
+        }
+        if balances.len() == 12 {
+            weights.sort_unstable();
+            return lines(weights.len(), |index| weights[index].to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2015;
+
+    #[test]
+    fn inputs_are_solvable() {
+        // The nonce search of day 4 is as slow whatever the key.
+        for solution in year2015::days().into_iter().filter(|solution| solution.day() != 4) {
+            let input = generate(solution.day(), DEFAULT_SIZE, 2015).unwrap();
+            for part in [crate::solution::Part::A, crate::solution::Part::B] {
+                assert!(solution.solve(part, &input).is_ok(), "day {} part {part}:\n{input}", solution.day());
+            }
+        }
+    }
+
+    #[test]
+    fn molecule_takes_as_many_steps_as_generated() {
+        assert_eq!(crate::year2015::ex19::b(&generate(19, 50, 1).unwrap()).unwrap(), 50);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        for day in 1..=25 {
+            assert_eq!(generate(day, 20, 1), generate(day, 20, 1));
+        }
+        assert_ne!(generate(6, 20, 1), generate(6, 20, 2));
+    }
+}
@@ -0,0 +1,308 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use crate::error::AocError;
+
+/// The environment variable holding the adventofcode.com session token.
+const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The user agent identifying this tool to adventofcode.com.
+pub(crate) const USER_AGENT: &str = "github.com/jlopez/aoc2015";
+
+/// The year whose files predate the support of several years, and are
+/// kept at the root of their directories.
+const FIRST_YEAR: u32 = 2015;
+
+/// The offset from UTC of the unlock times of the puzzles, Eastern Standard
+/// Time, which holds all of December.
+const UNLOCK_OFFSET_SECONDS: i32 = -5 * 3600;
+
+/// Returns the time zone of the unlock times of the puzzles, in which
+/// adventofcode.com shows its times.
+pub(crate) fn puzzle_zone() -> FixedOffset {
+    FixedOffset::east_opt(UNLOCK_OFFSET_SECONDS).unwrap_or_else(|| unreachable!("the offset is within a day"))
+}
+
+/// Returns when the puzzle of a day unlocks: at midnight Eastern Standard
+/// Time on that day of December.
+///
+/// # Returns
+/// The unlock time, or `None` if `day` is not a day of December.
+///
+/// # Examples
+/// ```
+/// use aoc2015::input::unlock_time;
+/// let unlock = unlock_time(2015, 1).unwrap();
+/// assert_eq!(unlock.to_rfc3339(), "2015-12-01T00:00:00-05:00");
+/// assert_eq!(unlock.with_timezone(&chrono::Utc).to_rfc3339(), "2015-12-01T05:00:00+00:00");
+/// assert_eq!(unlock_time(2015, 32), None);
+/// ```
+pub fn unlock_time(year: i32, day: u32) -> Option<DateTime<FixedOffset>> {
+    let midnight = NaiveDate::from_ymd_opt(year, 12, day)?.and_hms_opt(0, 0, 0)?;
+    puzzle_zone().from_local_datetime(&midnight).single()
+}
+
+/// Returns how long is left until the puzzle of a day unlocks, which is
+/// zero once it is unlocked.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use chrono::{TimeZone, Utc};
+/// use aoc2015::input::time_until_unlock;
+/// let now = Utc.with_ymd_and_hms(2015, 12, 7, 4, 59, 30).unwrap();
+/// assert_eq!(time_until_unlock(2015, 7, now), Some(Duration::from_secs(30)));
+/// assert_eq!(time_until_unlock(2015, 6, now), Some(Duration::ZERO));
+/// ```
+pub fn time_until_unlock(year: i32, day: u32, now: DateTime<Utc>) -> Option<Duration> {
+    let left = unlock_time(year, day)?.with_timezone(&Utc) - now;
+    Some(left.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Returns the directory under `root`, such as `data`, holding the files of
+/// a year: `root` itself for 2015, whose files predate the support of
+/// several years, and a `YEAR` subdirectory for the others.
+///
+/// # Examples
+/// ```
+/// use std::path::Path;
+/// use aoc2015::input::year_dir;
+/// assert_eq!(year_dir("data", 2015), Path::new("data"));
+/// assert_eq!(year_dir("data", 2016), Path::new("data/2016"));
+/// ```
+pub fn year_dir(root: &str, year: u32) -> PathBuf {
+    match year {
+        FIRST_YEAR => PathBuf::from(root),
+        year => Path::new(root).join(year.to_string()),
+    }
+}
+
+/// Returns the path of the cached input of the provided day.
+///
+/// # Arguments
+/// * `year` - The year of the input.
+/// * `exercise` - The day of the input.
+pub fn cache_path(year: u32, exercise: u32) -> PathBuf {
+    year_dir("data", year).join(format!("exercise_{:02}.txt", exercise))
+}
+
+/// Returns the session token used to authenticate against adventofcode.com.
+///
+/// The token is read from the `AOC_SESSION` environment variable, or from
+/// `~/.config/aoc/session` if the variable is not set.
+///
+/// # Returns
+/// The session token, or `None` if none is configured.
+pub(crate) fn session_token() -> Option<String> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        return Some(token.trim().to_string());
+    }
+    let home = env::var_os("HOME")?;
+    let path = PathBuf::from(home).join(".config/aoc/session");
+    fs::read_to_string(path).ok().map(|token| token.trim().to_string())
+}
+
+/// Returns the session token, failing if none is configured.
+///
+/// # Errors
+/// Returns a [`io::ErrorKind::NotFound`] error explaining how to configure
+/// the token.
+pub(crate) fn require_session_token() -> io::Result<String> {
+    session_token().ok_or_else(|| io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no session token, set {SESSION_VAR} or write it to ~/.config/aoc/session"),
+    ))
+}
+
+/// Downloads the input of the provided day from adventofcode.com.
+///
+/// # Arguments
+/// * `year` - The year of the input.
+/// * `exercise` - The day of the input.
+///
+/// # Errors
+/// Returns an error if no session token is configured or the request fails.
+fn download(year: u32, exercise: u32) -> io::Result<String> {
+    let token = require_session_token()?;
+    let url = format!("https://adventofcode.com/{year}/day/{exercise}/input");
+    ureq::get(&url)
+        .set("Cookie", &format!("session={token}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(io::Error::other)?
+        .into_string()
+}
+
+/// Reads the sealed input of the provided day, if the `seal` feature is
+/// enabled.
+///
+/// # Errors
+/// Returns an [`io::Error`] as described by
+/// [`read_sealed`](crate::seal::read_sealed).
+fn read_sealed(year: u32, exercise: u32) -> io::Result<Option<String>> {
+    #[cfg(feature = "seal")]
+    {
+        crate::seal::read_sealed(year, exercise)
+    }
+    #[cfg(not(feature = "seal"))]
+    {
+        let _ = (year, exercise);
+        Ok(None)
+    }
+}
+
+/// Caches a downloaded input under `data/`, sealed if the `seal` feature is
+/// enabled and its key is set.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the key is invalid or the file cannot be
+/// written.
+fn store(year: u32, exercise: u32, input: &str) -> io::Result<()> {
+    let path = cache_path(year, exercise);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    #[cfg(feature = "seal")]
+    if let Some(key) = crate::seal::Key::from_env()? {
+        return fs::write(crate::seal::sealed_path(year, exercise), key.seal(input.as_bytes()));
+    }
+    fs::write(&path, input)
+}
+
+/// Returns the file holding the cached input of the provided day: its
+/// sealed copy if it has no plaintext one, if the `seal` feature is enabled.
+fn data_path(year: u32, exercise: u32) -> PathBuf {
+    let path = cache_path(year, exercise);
+    #[cfg(feature = "seal")]
+    if !path.exists() && crate::seal::sealed_path(year, exercise).exists() {
+        return crate::seal::sealed_path(year, exercise);
+    }
+    path
+}
+
+/// Reads the input of the provided day.
+///
+/// The input is read from its cached copy under `data/` if present, either
+/// in plaintext or sealed, and is otherwise downloaded and cached for the
+/// next runs, so the network is only needed the first time a day is run.
+///
+/// # Arguments
+/// * `year` - The year of the input.
+/// * `exercise` - The day of the input.
+///
+/// # Returns
+/// The input without trailing whitespace.
+///
+/// # Errors
+/// Returns an [`AocError::MissingInput`] if the input is not cached and
+/// cannot be downloaded, or an [`AocError::Io`] if the cache cannot be read,
+/// unsealed or written.
+pub fn read_exercise_input(year: u32, exercise: u32) -> Result<String, AocError> {
+    let path = cache_path(year, exercise);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => match read_sealed(year, exercise)? {
+            Some(input) => input,
+            None => {
+                let input = download(year, exercise)
+                    .map_err(|err| AocError::MissingInput { day: exercise, reason: err.to_string() })?;
+                store(year, exercise, &input)?;
+                input
+            }
+        },
+        Err(err) => return Err(err.into()),
+    };
+    Ok(input.trim_end().to_string())
+}
+
+/// Reads a puzzle input from a file other than the cached input of a day,
+/// such as the input of another account or an example.
+///
+/// # Returns
+/// The input without trailing whitespace.
+///
+/// # Errors
+/// Returns an [`AocError::Io`] naming the file if it cannot be read.
+pub fn read_input_file(path: &Path) -> Result<String, AocError> {
+    let input = fs::read_to_string(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+    Ok(input.trim_end().to_string())
+}
+
+/// Returns the files of a directory of inputs, sorted by name, skipping
+/// hidden files and subdirectories.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the directory cannot be listed.
+pub fn input_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && !entry.file_name().to_string_lossy().starts_with('.') {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Where the input of a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The cached input of the day under `data/`, possibly sealed, and
+    /// downloaded if missing.
+    #[default]
+    Data,
+    /// A file, such as the input of another account or an example.
+    File(PathBuf),
+    /// The standard input, such as the output of a script or the clipboard.
+    Stdin,
+}
+
+impl InputSource {
+    /// Returns the source of the input at `path`, `-` standing for the
+    /// standard input.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use aoc2015::input::InputSource;
+    /// assert_eq!(InputSource::from_path(Path::new("-")), InputSource::Stdin);
+    /// assert_eq!(InputSource::from_path(Path::new("alice.txt")), InputSource::File("alice.txt".into()));
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        if path == Path::new("-") { InputSource::Stdin } else { InputSource::File(path.to_path_buf()) }
+    }
+
+    /// Returns the file the input of a day of a year is read from, or
+    /// `None` for the standard input.
+    pub fn path(&self, year: u32, day: u32) -> Option<PathBuf> {
+        match self {
+            InputSource::Data => Some(data_path(year, day)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input of a day of a year from this source.
+    ///
+    /// # Returns
+    /// The input without trailing whitespace.
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the input cannot be read, as described by
+    /// [`read_exercise_input`] and [`read_input_file`].
+    pub fn read(&self, year: u32, day: u32) -> Result<String, AocError> {
+        match self {
+            InputSource::Data => read_exercise_input(year, day),
+            InputSource::File(path) => read_input_file(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input.trim_end().to_string())
+            }
+        }
+    }
+}
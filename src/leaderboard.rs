@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::error::AocError;
+use crate::input::{puzzle_zone, require_session_token, year_dir, USER_AGENT};
+
+/// How long a fetched leaderboard is reused before it is fetched again, as
+/// adventofcode.com asks not to poll its API more often.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A member of a private leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    /// The id of the account.
+    pub id: u64,
+    /// The name of the account, or `None` if it is anonymous.
+    pub name: Option<String>,
+    /// The number of stars earned.
+    pub stars: u32,
+    /// The score on the leaderboard.
+    pub local_score: u64,
+    /// When the last star was earned, or `None` if there is none.
+    pub last_star: Option<DateTime<Utc>>,
+    /// When each part of each day was solved, by day.
+    pub days: BTreeMap<u32, [Option<DateTime<Utc>>; 2]>,
+}
+
+impl Member {
+    /// Returns the name of the member, as adventofcode.com shows it.
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// A private leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    /// The id of the account owning the leaderboard, which is also the id
+    /// of the leaderboard.
+    pub owner_id: u64,
+    /// The year of the event.
+    pub event: String,
+    /// The members, by decreasing score, then by decreasing stars and by
+    /// the time of their last star.
+    pub members: Vec<Member>,
+}
+
+/// Returns a field of a JSON object.
+fn field<'a>(object: &'a Value, name: &str) -> Result<&'a Value, String> {
+    object.get(name).ok_or_else(|| format!("missing field {name}"))
+}
+
+/// Returns a field of a JSON object holding a non-negative integer.
+fn integer(object: &Value, name: &str) -> Result<u64, String> {
+    field(object, name)?.as_u64().ok_or_else(|| format!("field {name} is not a non-negative integer"))
+}
+
+/// Converts a Unix timestamp, in seconds, into a time.
+fn timestamp(seconds: u64) -> Result<DateTime<Utc>, String> {
+    i64::try_from(seconds).ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or_else(|| format!("invalid timestamp {seconds}"))
+}
+
+/// Parses a member of the JSON document of a leaderboard.
+fn parse_member(member: &Value) -> Result<Member, String> {
+    let mut days = BTreeMap::new();
+    if let Some(levels) = member.get("completion_day_level").and_then(Value::as_object) {
+        for (day, parts) in levels {
+            let day: u32 = day.parse().map_err(|_| format!("invalid day '{day}'"))?;
+            let mut solved = [None, None];
+            for (slot, level) in solved.iter_mut().zip(["1", "2"]) {
+                if let Some(part) = parts.get(level) {
+                    *slot = Some(timestamp(integer(part, "get_star_ts")?)?);
+                }
+            }
+            days.insert(day, solved);
+        }
+    }
+    let last_star = match integer(member, "last_star_ts")? {
+        0 => None,
+        seconds => Some(timestamp(seconds)?),
+    };
+    Ok(Member {
+        id: integer(member, "id")?,
+        name: field(member, "name")?.as_str().map(str::to_string),
+        stars: u32::try_from(integer(member, "stars")?).map_err(|err| err.to_string())?,
+        local_score: integer(member, "local_score")?,
+        last_star,
+        days,
+    })
+}
+
+impl Leaderboard {
+    /// Parses the JSON document of a private leaderboard, as served by
+    /// `https://adventofcode.com/YEAR/leaderboard/private/view/ID.json`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::leaderboard::Leaderboard;
+    /// let json = r#"{"owner_id": 1, "event": "2015", "members": {
+    ///     "1": {"id": 1, "name": "alice", "stars": 1, "local_score": 2, "last_star_ts": 1448946300,
+    ///           "completion_day_level": {"1": {"1": {"get_star_ts": 1448946300, "star_index": 0}}}},
+    ///     "2": {"id": 2, "name": null, "stars": 2, "local_score": 4, "last_star_ts": 1448946000,
+    ///           "completion_day_level": {"1": {"1": {"get_star_ts": 1448945000, "star_index": 0},
+    ///                                          "2": {"get_star_ts": 1448946000, "star_index": 1}}}}}}"#;
+    /// let leaderboard = Leaderboard::parse(json).unwrap();
+    /// let names: Vec<String> = leaderboard.members.iter().map(|member| member.display_name()).collect();
+    /// assert_eq!(names, ["(anonymous user #2)", "alice"]);
+    /// assert_eq!(leaderboard.members[1].days[&1][1], None);
+    /// assert!(Leaderboard::parse(r#"{"owner_id": 1}"#).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the document is not valid
+    /// JSON or lacks a field.
+    pub fn parse(json: &str) -> Result<Leaderboard, String> {
+        let document: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let members = field(&document, "members")?.as_object().ok_or("field members is not an object")?;
+        let mut members = members.values().map(parse_member).collect::<Result<Vec<_>, _>>()?;
+        members.sort_by(|a, b| b.local_score.cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.last_star.cmp(&b.last_star))
+            .then(a.id.cmp(&b.id)));
+        Ok(Leaderboard {
+            owner_id: integer(&document, "owner_id")?,
+            event: field(&document, "event")?.as_str().ok_or("field event is not a string")?.to_string(),
+            members,
+        })
+    }
+
+    /// Renders this leaderboard as a table, one line per member with their
+    /// rank, score, stars, the time of their last star in the time zone of
+    /// the puzzles, and their stars by day: `*` for both parts, `+` for the
+    /// first one only and `.` for none.
+    pub fn render(&self) -> String {
+        let mut table = String::new();
+        writeln!(table, "{:>4}  {:>5}  {:>5}  {:25}  {:19}  Name", "Rank", "Score", "Stars", "         1111111111222222", "Last star (EST)").unwrap();
+        writeln!(table, "{:>4}  {:>5}  {:>5}  1234567890123456789012345", "", "", "").unwrap();
+        for (rank, member) in self.members.iter().enumerate() {
+            let days: String = (1..=25)
+                .map(|day| match member.days.get(&day) {
+                    Some([Some(_), Some(_)]) => '*',
+                    Some([Some(_), None]) => '+',
+                    _ => '.',
+                })
+                .collect();
+            let last_star = member.last_star
+                .map_or_else(|| "-".to_string(), |time| time.with_timezone(&puzzle_zone()).format("%Y-%m-%d %H:%M:%S").to_string());
+            writeln!(table, "{:>4}  {:>5}  {:>5}  {days}  {last_star:19}  {}", rank + 1, member.local_score, member.stars, member.display_name()).unwrap();
+        }
+        table
+    }
+}
+
+/// Returns the path of the cached copy of a leaderboard of a year.
+fn cache_path(year: u32, id: u64) -> PathBuf {
+    year_dir("data", year).join(format!("leaderboard_{id}.json"))
+}
+
+/// Returns the cached copy of a leaderboard of a year and its age, if any.
+fn read_cache(year: u32, id: u64) -> io::Result<Option<(String, Duration)>> {
+    let path = cache_path(year, id);
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let modified = fs::metadata(&path)?.modified()?;
+    let age = SystemTime::now().duration_since(modified).unwrap_or(Duration::ZERO);
+    Ok(Some((json, age)))
+}
+
+/// Fetches a private leaderboard of a year from adventofcode.com.
+///
+/// The response is cached in `data/leaderboard_ID.json`, or
+/// `data/YEAR/leaderboard_ID.json` for the years after 2015, and reused for
+/// [`REFRESH_INTERVAL`], so the API is polled at most every 15 minutes
+/// however often this runs. The cached copy is also used if the request
+/// fails.
+///
+/// # Arguments
+/// * `year` - The year of the event.
+/// * `id` - The id of the leaderboard.
+///
+/// # Returns
+/// The [`Leaderboard`], and the age of its cached copy if it was not
+/// fetched now.
+///
+/// # Errors
+/// Returns an [`AocError::Io`] if the leaderboard is not cached or its
+/// cached copy is stale, and no session token is configured or the request
+/// fails, or if the document cannot be parsed.
+pub fn fetch(year: u32, id: u64) -> Result<(Leaderboard, Option<Duration>), AocError> {
+    let cached = read_cache(year, id)?;
+    let (json, age) = match cached {
+        Some((json, age)) if age < REFRESH_INTERVAL => (json, Some(age)),
+        cached => {
+            let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+            let fetched = require_session_token().and_then(|token| {
+                ureq::get(&url)
+                    .set("Cookie", &format!("session={token}"))
+                    .set("User-Agent", USER_AGENT)
+                    .call()
+                    .map_err(io::Error::other)?
+                    .into_string()
+            });
+            match (fetched, cached) {
+                (Ok(json), _) => {
+                    // Checked before caching, as a document that does not
+                    // parse would be reused for the whole interval.
+                    Leaderboard::parse(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                    let path = cache_path(year, id);
+                    if let Some(dir) = path.parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    fs::write(path, &json)?;
+                    (json, None)
+                }
+                (Err(err), Some((json, age))) => {
+                    tracing::warn!("cannot fetch leaderboard {id}, using its cached copy: {err}");
+                    (json, Some(age))
+                }
+                (Err(err), None) => return Err(err.into()),
+            }
+        }
+    };
+    let leaderboard = Leaderboard::parse(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("leaderboard {id}: {err}")))?;
+    Ok((leaderboard, age))
+}
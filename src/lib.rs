@@ -0,0 +1,39 @@
+//! Solutions to [Advent of Code 2015](https://adventofcode.com/2015), on a
+//! core that is not tied to the year.
+//!
+//! Each year lives in its own `yearNNNN` module, holding an `exNN` module
+//! per day that exposes `a` and `b` solvers for both parts of the puzzle,
+//! plus a `Solver` implementing [`Solution`]. Every day of every year can
+//! then be run generically through [`days::registry`], on inputs read by
+//! [`input`] and with the helpers of [`util`], none of which depend on the
+//! year.
+//!
+//! [`Solution`]: solution::Solution
+
+pub mod answer;
+pub mod config;
+pub mod context;
+pub mod days;
+pub mod diagnostic;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod gen;
+#[cfg(feature = "net")]
+pub mod input;
+#[cfg(feature = "net")]
+pub mod leaderboard;
+pub mod progress;
+#[cfg(feature = "net")]
+pub mod puzzle;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "seal")]
+pub mod seal;
+pub mod solution;
+#[cfg(feature = "net")]
+pub mod submit;
+pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod year2015;
@@ -1,199 +1,447 @@
-use std::fs;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use clap::CommandFactory;
+use aoc2015::{days, gen, leaderboard, puzzle, year2015};
+use aoc2015::config::PuzzleConfig;
+use aoc2015::diagnostic::Diagnostic;
+use aoc2015::error::AocError;
+use aoc2015::input::{input_files, read_input_file, InputSource};
+use aoc2015::solution::{Part, Solution};
+use aoc2015::submit::{submit, Verdict};
+use cli::{Cli, Command, Output};
+use tracing_subscriber::EnvFilter;
+use budget::Budget;
+use cache::CacheMode;
+use output::{Emitter, Style};
+use runner::{run_day, run_day_on, run_parallel, Outcome, Report, RunOptions};
 
-mod ex06;
+mod budget;
+mod cache;
+mod cli;
+mod mem;
+mod output;
+#[cfg(feature = "profile")]
+mod profile;
+mod report;
+mod runner;
+mod scaffold;
+mod selftest;
+#[cfg(feature = "serve")]
+mod serve;
+#[cfg(feature = "viz")]
+mod viz;
+mod wait;
+mod watch;
 
-fn read_exercise_input(exercise: u32) -> String {
-    let filename = format!("data/exercise_{:02}.txt", exercise);
-    fs::read_to_string(&filename)
-        .expect(&format!("Unable to read exercise input {}", filename))
-        .trim_end()
-        .to_string()
-}
-
-fn main() {
-    let input = read_exercise_input(1);
-    println!("ex01a_purist: {}", ex01a_purist(&input));
-    println!("ex01a_readable: {}", ex01a_readable(&input));
-    println!("ex01b: {}", ex01b(&input));
-
-    let input = read_exercise_input(2);
-    println!("ex02a: {}", ex02a(&input));
-    println!("ex02b: {}", ex02b(&input));
-
-    let input = read_exercise_input(3);
-    println!("ex03a: {}", ex03a(&input));
-    println!("ex03b: {}", ex03b(&input));
-
-    // println!("ex04a: {}", ex04a("iwrupvqb"));
-    // println!("ex04b: {}", ex04b("iwrupvqb"));
-
-    let input = read_exercise_input(5);
-    println!("ex05a: {}", ex05a(&input));
-    println!("ex05b: {}", ex05b(&input));
-
-    let input = read_exercise_input(6);
-    println!("ex06a: {}", ex06::a(&input));
-    println!("ex06b: {}", ex06::b(&input));
-}
-
-fn ex01a_purist(input: &str) -> i32 {
-    input.chars().fold(0, |acc, code| acc + ex01_decoder(code))
-}
-
-fn ex01a_readable(input: &str) -> i32 {
-    let mut floor = 0;
-    for code in input.chars() {
-        floor += ex01_decoder(code);
+/// Adds the [`Outcome`]s of a day to the [`Report`], handing them to the
+/// [`Emitter`] as they come.
+///
+/// `input` names the input file the day ran on, when it runs on several.
+fn record(day: u32, input: Option<&str>, result: Result<Vec<Outcome>, AocError>, emitter: &mut dyn Emitter, report: &mut Report) {
+    match result {
+        Ok(outcomes) => {
+            for mut outcome in outcomes {
+                outcome.input = input.map(str::to_string);
+                emitter.outcome(&outcome);
+                report.push(outcome);
+            }
+        }
+        Err(err) => {
+            emitter.failure(day, input, &err);
+            report.push_failed_day(day, err);
+        }
     }
-    floor
 }
 
-fn ex01_decoder(code: char) -> i32 {
-    match code {
-        '(' => 1,
-        ')' => -1,
-        _ => unreachable!("Invalid input {code}"),
+/// Submits every answer of the [`Report`], printing the verdicts.
+///
+/// # Returns
+/// Whether every submitted answer is correct.
+fn submit_answers(year: u32, report: &Report) -> bool {
+    let mut correct = true;
+    for outcome in report.outcomes() {
+        let Ok(answer) = &outcome.answer else { continue };
+        let name = format!("ex{:02}{}", outcome.day, outcome.part);
+        match submit(year, outcome.day, outcome.part, answer) {
+            Ok((verdict, cached)) => {
+                let cached = if cached { " (already submitted)" } else { "" };
+                eprintln!("{name}: {answer} is {verdict}{cached}");
+                correct &= matches!(verdict, Verdict::Correct | Verdict::AlreadySolved);
+            }
+            Err(err) => {
+                eprintln!("{name}: unable to submit {answer}: {err}");
+                correct = false;
+            }
+        }
     }
+    correct
 }
 
-fn ex01b(input: &str) -> usize {
-    const TARGET: i32 = -1;
-    let mut floor = 0;
-    for (index, code) in input.chars().enumerate() {
-        floor += ex01_decoder(code);
-        if floor == TARGET {
-            return index + 1;
+/// Checks the examples of the puzzle text of each day, printing how many
+/// pass.
+///
+/// Days whose examples need other parameters than the puzzle, such as
+/// fewer steps, have none to check here, and are only covered by the
+/// doctests of their module.
+///
+/// # Returns
+/// Whether every example passes.
+fn check_doc_examples(solutions: &[Box<dyn Solution>], parts: &[Part]) -> bool {
+    let mut passed = true;
+    for solution in solutions {
+        let name = format!("ex{:02}", solution.day());
+        match runner::check_examples(solution.as_ref(), parts) {
+            Ok(0) => println!("{name}: no examples to check, see the doctests of aoc2015::year{}::{name}", solution.year()),
+            Ok(count) => println!("{name}: {count} example{} passed", if count == 1 { "" } else { "s" }),
+            Err(err) => {
+                eprintln!("error: {err}");
+                passed = false;
+            }
         }
     }
-    panic!("Not doable!");
+    passed
 }
 
-fn ex02_parse_line(line: &str) -> [u32; 3] {
-    let dimensions: Vec<u32> = line.splitn(3, 'x')
-        .map(|d| d.parse().expect("Bad input"))
-        .collect();
-    [dimensions[0], dimensions[1], dimensions[2]]
+/// Writes the animation of the lights of day 18 on an input to a GIF file,
+/// one frame per step, the corners being stuck on for part b.
+///
+/// # Errors
+/// Returns an [`AocError`] if the input cannot be parsed, or the file
+/// cannot be written.
+#[cfg(feature = "export")]
+fn export_gif(part: Part, input: &str, path: &std::path::Path, scale: u16) -> Result<(), AocError> {
+    use aoc2015::year2015::ex18;
+    let frames = ex18::states(input, part == Part::B)?.take(ex18::STEPS + 1).map(|grid| grid.image());
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    aoc2015::util::image::write_gif(frames, scale.into(), std::time::Duration::from_millis(100), file)?;
+    eprintln!("ex18{part}: animation written to {}", path.display());
+    Ok(())
 }
 
-fn ex02a(input: &str) -> u32 {
-    fn wrapping_paper(line: &str) -> u32 {
-        let [w, h, l] = ex02_parse_line(line);
-        let mut areas = [w * h, w * l, h * l];
-        areas.sort();
-        areas[0] * 3 + areas[1] * 2 + areas[2] * 2
+/// Seals the plaintext inputs of the provided days of a year under
+/// `data/`, or unseals their sealed inputs, every day if none is provided.
+///
+/// # Returns
+/// Whether every input could be converted.
+#[cfg(feature = "seal")]
+fn convert_inputs(year: u32, days: &[u32], seal: bool) -> ExitCode {
+    use aoc2015::seal::{self, Key};
+    let key = match Key::require() {
+        Ok(key) => key,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days = if days.is_empty() { (1..=25).collect() } else { days.to_vec() };
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        let (from, result) = if seal {
+            (aoc2015::input::cache_path(year, day), seal::seal_file(&key, year, day))
+        } else {
+            (seal::sealed_path(year, day), seal::unseal_file(&key, year, day))
+        };
+        match result {
+            Ok(true) => eprintln!("{} {}", if seal { "sealed" } else { "unsealed" }, from.display()),
+            Ok(false) => {}
+            Err(err) => {
+                eprintln!("error: day {day}: {err}");
+                status = ExitCode::FAILURE;
+            }
+        }
     }
-    input.lines().map(|line| wrapping_paper(line)).sum()
+    status
 }
 
-fn ex02b(input: &str) -> u32 {
-    fn ribbon(line: &str) -> u32 {
-        let [w, h, l] = ex02_parse_line(line);
-        let mut half_perimeters = [w + h, w + l, h + l];
-        half_perimeters.sort();
-        2 * half_perimeters[0] + w * h * l
+/// Parses the input of a day without solving it, printing a diagnostic
+/// pointing at the first malformed line if any.
+///
+/// # Returns
+/// Whether the input could be read and parsed.
+fn check_input(year: u32, day: u32, source: &InputSource) -> ExitCode {
+    let Some(solution) = days::find(year, day) else {
+        eprintln!("Day {day} of {year} is not implemented yet");
+        return ExitCode::FAILURE;
+    };
+    let input = match source.read(year, day) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match solution.check_input(&input) {
+        Some(Ok(())) => {
+            let lines = input.lines().count();
+            println!("Day {day}: {lines} line{} parsed, no error found", if lines == 1 { "" } else { "s" });
+            ExitCode::SUCCESS
+        }
+        Some(Err(err)) => {
+            match Diagnostic::locate(&input, &err) {
+                Some(diagnostic) => eprint!("{diagnostic}"),
+                None => eprintln!("error: {err}"),
+            }
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("Day {day} has no parser to check its input with");
+            ExitCode::FAILURE
+        }
     }
-    input.lines().map(|line| ribbon(line)).sum()
 }
 
-fn ex03a(input: &str) -> u32 {
-    _ex03(input, 1)
+/// Prints the tracing events of the solvers and of the runner to standard
+/// error, at the level selected by `--verbose` or else by `RUST_LOG`,
+/// warnings only by default.
+fn init_tracing(verbose: u8) {
+    let filter = match verbose {
+        0 => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        1 => EnvFilter::new("aoc2015=debug,aoc15=debug"),
+        _ => EnvFilter::new("aoc2015=trace,aoc15=trace"),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
 }
 
-fn ex03b(input: &str) -> u32 {
-    _ex03(input, 2)
-}
-
-fn _ex03(input: &str, santas: usize) -> u32 {
-    let mut houses = vec![vec![true]];
-    let mut x = 1;
-    let mut y = 1;
-    let mut ox = 1;
-    let mut oy = 1;
-    let mut visited = 1;
-
-    for santa in 0..santas {
-        for code in input.chars().skip(santa).step_by(santas) {
-            match code {
-                '^' => y += 1,
-                'v' => y -= 1,
-                '<' => x -= 1,
-                '>' => x += 1,
-                _ => unreachable!("Invalid code {code}"),
-            };
-            if y == 0 { y = 1; oy += 1; houses.insert(0, vec![]); }
-            if x == 0 { x = 1; ox += 1; for row in &mut houses { row.insert(0, false); } }
-
-            if y > houses.len() { houses.push(vec![]); }
-            let row = &mut houses[y - 1];
-            if x > row.len() { row.resize(x, false); }
-            if !row[x - 1] { visited += 1; }
-            row[x - 1] = true;
+fn main() -> ExitCode {
+    let cli = Cli::parse_args();
+    init_tracing(cli.verbose);
+    output::init_color(cli.no_color);
+    match cli.command {
+        Some(Command::New { day }) => return match scaffold::new_day(cli.year, day) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::Gen { .. }) if cli.year != year2015::YEAR => {
+            eprintln!("Synthetic inputs are only generated for {}", year2015::YEAR);
+            return ExitCode::FAILURE;
+        }
+        Some(Command::Gen { day, size, seed }) => return match gen::generate(day, size, seed) {
+            Some(input) => {
+                println!("{input}");
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("Day {day} is not implemented yet");
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::CheckInput { day, input }) => {
+            return check_input(cli.year, day, &input.as_deref().map_or(InputSource::Data, InputSource::from_path));
         }
-        x = ox; y = oy;
+        Some(Command::Selftest { answers }) => return match selftest::selftest(cli.year, &answers) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: {}: {err}", answers.display());
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::Report { cached }) => {
+            let cache = if cached { CacheMode::Reuse } else { CacheMode::Store };
+            let options = RunOptions { cache, ..RunOptions::default() };
+            let solutions = days::registry(cli.year);
+            let mut emitter = output::emitter(Output::Markdown, cli.year, solutions.iter().map(|solution| solution.day()).collect());
+            let mut report = Report::default();
+            for solution in &solutions {
+                record(solution.day(), None, run_day(solution.as_ref(), &[Part::A, Part::B], options), emitter.as_mut(), &mut report);
+            }
+            emitter.finish(&report);
+            return if report.succeeded() { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+        }
+        Some(Command::Wait { day, new }) => return match wait::wait(cli.year, day, new) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::Leaderboard { id }) => return match leaderboard::fetch(cli.year, id) {
+            Ok((leaderboard, age)) => {
+                print!("{}", leaderboard.render());
+                if let Some(age) = age {
+                    eprintln!("fetched {} min ago, refreshed at most every {} min", age.as_secs() / 60, leaderboard::REFRESH_INTERVAL.as_secs() / 60);
+                }
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::Completions { shell }) => return match cli::write_completions(shell, cli.year, &mut io::stdout()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Some(Command::Manpage) => return match clap_mangen::Man::new(Cli::command()).render(&mut io::stdout()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        #[cfg(feature = "serve")]
+        Some(Command::Serve { address }) => return match serve::serve(cli.year, &address) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {address}: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        #[cfg(feature = "seal")]
+        Some(Command::Seal { days }) => return convert_inputs(cli.year, &days, true),
+        #[cfg(feature = "seal")]
+        Some(Command::Unseal { days }) => return convert_inputs(cli.year, &days, false),
+        None => {}
     }
-    visited
-}
-
-// fn ex04a(input: &str) -> u32 {
-//     let mut n = 0;
-//     loop {
-//         let input = format!("{input}{n}");
-//         let hash = md5::compute(input.as_bytes());
-//         if hash[0] == 0 && hash[1] == 0 && hash[2] < 0x10 { break n; }
-//         n += 1;
-//     }
-// }
-//
-// fn ex04b(input: &str) -> u32 {
-//     let mut n = 0;
-//     loop {
-//         let input = format!("{input}{n}");
-//         let hash = md5::compute(input.as_bytes());
-//         if hash[0] == 0 && hash[1] == 0 && hash[2] == 0 { break n; }
-//         n += 1;
-//     }
-// }
-//
-
-const BAD_WORDS: [&str; 4] = ["ab", "cd", "pq", "xy"];
-fn ex05a(input: &str) -> u32 {
-    fn is_nice(input: &&str) -> bool {
-        !BAD_WORDS.iter().any(|word| input.contains(word)) &&
-            std::iter::zip(input.chars(), input.chars().skip(1)).any(|(a, b)| a == b) &&
-            input.chars().filter(|ch| "aeiou".contains(*ch)).collect::<Vec<_>>().len() >= 3
+    if let (true, Some(day)) = (cli.open, cli.day) {
+        return match puzzle::read_puzzle(cli.year, day) {
+            Ok(markdown) => {
+                print!("{markdown}");
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
     }
-    input.lines().filter(is_nice).count() as u32
-}
-
-fn ex05b(input: &str) -> u32 {
-    fn is_nice(line: &&str) -> bool {
-        c1(line) && c2(line)
+    #[cfg(feature = "viz")]
+    if let (true, Some(day)) = (cli.viz, cli.day) {
+        if cli.year != year2015::YEAR || !viz::DAYS.contains(&day) {
+            eprintln!("Day {day} of {} has no grid to visualize", cli.year);
+            return ExitCode::FAILURE;
+        }
+        let part = cli.part().unwrap_or(Part::A);
+        return match cli.input_source().read(cli.year, day).and_then(|input| viz::visualize(day, part, &input)) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
     }
-
-    fn c1(line: &&str) -> bool {
-        match line.char_indices().rev().nth(2) {
-            None => return false,
-            Some((last_index, _)) => {
-                for (start_index, _) in line[0..last_index].char_indices() {
-                    let end_index = start_index + line[start_index..].char_indices().nth(2).unwrap().0;
-                    if line[end_index..].contains(&line[start_index..end_index]) { return true; }
-                }
+    #[cfg(feature = "export")]
+    if let (Some(path), Some(day)) = (&cli.gif, cli.day) {
+        if cli.year != year2015::YEAR || day != 18 {
+            eprintln!("Only the lights of day 18 of {} can be exported", year2015::YEAR);
+            return ExitCode::FAILURE;
+        }
+        let part = cli.part().unwrap_or(Part::A);
+        return match cli.input_source().read(cli.year, day).and_then(|input| export_gif(part, &input, path, cli.scale)) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    let solutions = match cli.day {
+        Some(day) => match days::find(cli.year, day) {
+            Some(solution) => vec![solution],
+            None => {
+                eprintln!("Day {day} of {} is not implemented yet", cli.year);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => days::registry(cli.year),
+    };
+    if solutions.is_empty() {
+        eprintln!("No day of {} is implemented yet", cli.year);
+        return ExitCode::FAILURE;
+    }
+    let mut budget = match &cli.budgets {
+        Some(path) => match Budget::read(path) {
+            Ok(budget) => budget,
+            Err(err) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
             }
+        },
+        None => Budget::default(),
+    };
+    budget.total = cli.budget.or(budget.total);
+    let config = match PuzzleConfig::read(&cli.config) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {}: {err}", cli.config.display());
+            return ExitCode::FAILURE;
         }
-        false
+    };
+    if !config.is_puzzle() {
+        eprintln!("{}", Style::Dim.paint(format!("{}: {}", cli.config.display(), config.changes().join(", "))));
     }
-
-    fn c2(line: &&str) -> bool {
-        match line.char_indices().rev().nth(1) {
-            None => return false,
-            Some((last_index, _)) => {
-                for (start_index, ch) in line[0..last_index].char_indices() {
-                    if line[start_index..].chars().nth(2).unwrap() == ch { return true; }
-                }
+    let parts = Part::selection(cli.part());
+    if cli.doc_examples {
+        return if check_doc_examples(&solutions, &parts) { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+    let options = RunOptions {
+        examples: cli.examples,
+        // Progress lines of concurrent days would overwrite each other.
+        progress: !cli.parallel && cli.output == Output::Text && io::stderr().is_terminal(),
+        implementation: cli.implementation.as_deref(),
+        verify: cli.verify,
+        render: cli.render.as_deref(),
+        cache: if cli.cached && !cli.force { CacheMode::Reuse } else { CacheMode::Store },
+        explain: cli.explain,
+        resume: cli.resume,
+        repeat: cli.repeat,
+        seed: cli.seed,
+        config: Some(&config),
+        #[cfg(feature = "profile")]
+        profile: cli.profile.as_deref(),
+    };
+    let source = cli.input_source();
+    if cli.watch {
+        let solution = solutions[0].as_ref();
+        let Some(path) = source.path(solution.year(), solution.day()) else {
+            eprintln!("error: the standard input cannot be watched");
+            return ExitCode::FAILURE;
+        };
+        watch::watch(solution, &parts, &source, &path, options);
+    }
+    let mut emitter = output::emitter(cli.output, cli.year, solutions.iter().map(|solution| solution.day()).collect());
+    let emitter = emitter.as_mut();
+    let mut report = Report::default();
+    if source != InputSource::Data {
+        let solution = solutions[0].as_ref();
+        let result = source.read(solution.year(), solution.day()).and_then(|input| run_day_on(solution, &parts, &input, options));
+        record(solution.day(), None, result, emitter, &mut report);
+    } else if let Some(dir) = &cli.inputs {
+        let files = match input_files(dir) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("error: {}: {err}", dir.display());
+                return ExitCode::FAILURE;
             }
+        };
+        let solution = solutions[0].as_ref();
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            let result = read_input_file(&file).and_then(|input| run_day_on(solution, &parts, &input, options));
+            record(solution.day(), Some(&name), result, emitter, &mut report);
+        }
+    } else if cli.parallel {
+        run_parallel(&solutions, &parts, options, |day, result| record(day, None, result, emitter, &mut report));
+    } else {
+        for solution in &solutions {
+            record(solution.day(), None, run_day(solution.as_ref(), &parts, options), emitter, &mut report);
         }
-        false
     }
-
-    input.lines().filter(is_nice).count() as u32
+    emitter.finish(&report);
+    let exceeded = budget.check(&report);
+    for message in &exceeded {
+        eprintln!("budget exceeded: {message}");
+    }
+    let submitted = !cli.submit || submit_answers(cli.year, &report);
+    if report.succeeded() && exceeded.is_empty() && submitted { ExitCode::SUCCESS } else { ExitCode::FAILURE }
 }
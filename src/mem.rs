@@ -0,0 +1,114 @@
+use std::fmt;
+#[cfg(feature = "mem-profile")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "mem-profile")]
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// The heap usage of a computation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The largest number of bytes allocated at once during the computation,
+    /// beyond those already allocated before it.
+    pub peak_bytes: usize,
+    /// The number of allocations and reallocations done.
+    pub allocations: u64,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.peak_bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "peak {} B, {} allocations", self.peak_bytes, self.allocations),
+            _ => write!(f, "peak {size:.1} {}, {} allocations", UNITS[unit], self.allocations),
+        }
+    }
+}
+
+/// The bytes currently allocated.
+#[cfg(feature = "mem-profile")]
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The largest value of [`CURRENT`] since the last measurement started.
+#[cfg(feature = "mem-profile")]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// The allocations done since the last measurement started.
+#[cfg(feature = "mem-profile")]
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting the bytes and the allocations it hands
+/// out.
+#[cfg(feature = "mem-profile")]
+struct Counting;
+
+#[cfg(feature = "mem-profile")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[cfg(feature = "mem-profile")]
+impl Counting {
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "mem-profile")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() { Counting::grow(layout.size()); }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() { Counting::grow(layout.size()); }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Counting::grow(new_size);
+        }
+        new
+    }
+}
+
+/// Runs `compute`, measuring its heap usage if the `mem-profile` feature
+/// is enabled.
+///
+/// The counters are shared by every thread, so the usage of computations
+/// running concurrently is mixed up, and includes that of their worker
+/// threads.
+///
+/// # Returns
+/// The result of `compute`, and its [`MemoryUsage`] if it was measured.
+pub fn measure<T>(compute: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    #[cfg(feature = "mem-profile")]
+    {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        let result = compute();
+        let usage = MemoryUsage {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        };
+        (result, Some(usage))
+    }
+    #[cfg(not(feature = "mem-profile"))]
+    (compute(), None)
+}
@@ -0,0 +1,149 @@
+//! How the runner prints the results of a run: an [`Emitter`] for each
+//! [`Output`] format, and the ANSI colors of the text format.
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use aoc2015::error::AocError;
+use crate::cli::Output;
+use crate::report::Markdown;
+use crate::runner::{Outcome, Report};
+
+/// Whether to color the output, as decided by [`init_color`].
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// The runtime from which a part is highlighted as slow.
+pub const SLOW: Duration = Duration::from_secs(1);
+
+/// Decides whether to color the output: only when standard output is a
+/// terminal, unless `--no-color` or a non-empty `NO_COLOR` environment
+/// variable disables it.
+pub fn init_color(no_color: bool) {
+    let disabled = no_color || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    COLOR.store(!disabled && io::stdout().is_terminal(), Ordering::Relaxed);
+}
+
+/// A style of the text output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// For answers.
+    Green,
+    /// For slow runtimes.
+    Yellow,
+    /// For errors.
+    Red,
+    /// For what was skipped or reused.
+    Dim,
+}
+
+impl Style {
+    /// Returns the ANSI escape sequence selecting this style.
+    fn escape(self) -> &'static str {
+        match self {
+            Style::Green => "\x1b[32m",
+            Style::Yellow => "\x1b[33m",
+            Style::Red => "\x1b[31m",
+            Style::Dim => "\x1b[2m",
+        }
+    }
+
+    /// Displays `value` in this style, when the output is colored.
+    pub fn paint<T: fmt::Display>(self, value: T) -> Painted<T> {
+        Painted { style: self, value }
+    }
+}
+
+/// A value displayed in a [`Style`] when the output is colored, and as is
+/// otherwise.
+///
+/// The width and alignment of the format apply to the value, so that the
+/// escape sequences do not count towards the padding.
+pub struct Painted<T> {
+    style: Style,
+    value: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !COLOR.load(Ordering::Relaxed) { return self.value.fmt(f); }
+        f.write_str(self.style.escape())?;
+        self.value.fmt(f)?;
+        f.write_str("\x1b[0m")
+    }
+}
+
+/// Prints the results of a run as they come, in some [`Output`] format.
+pub trait Emitter {
+    /// Receives the [`Outcome`] of a part as soon as it is known.
+    fn outcome(&mut self, outcome: &Outcome);
+
+    /// Receives a day that could not be run, `input` naming its input file
+    /// when the day runs on several. The error is printed to standard
+    /// error by default.
+    fn failure(&mut self, day: u32, input: Option<&str>, err: &AocError) {
+        let _ = day;
+        match input {
+            Some(input) => eprintln!("{} {input}: {err}", Style::Red.paint("error:")),
+            None => eprintln!("{} {err}", Style::Red.paint("error:")),
+        }
+    }
+
+    /// Receives the [`Report`] of the whole run once it is over.
+    fn finish(&mut self, report: &Report);
+}
+
+/// Returns the [`Emitter`] of an [`Output`] format.
+///
+/// # Arguments
+/// * `output` - The format to print the results in.
+/// * `year` - The year of the run.
+/// * `days` - The days of the run, which the Markdown table lists even
+///   when they have nothing to run.
+pub fn emitter(output: Output, year: u32, days: Vec<u32>) -> Box<dyn Emitter> {
+    match output {
+        Output::Text => Box::new(Text),
+        Output::Json => Box::new(Json),
+        Output::Markdown => Box::new(Markdown::new(year, days)),
+    }
+}
+
+/// Prints a line per part as soon as it completes, followed by a summary.
+pub struct Text;
+
+impl Emitter for Text {
+    fn outcome(&mut self, outcome: &Outcome) {
+        let mut line = format!("ex{:02}{}", outcome.day, outcome.part);
+        if let Some(input) = &outcome.input { line += &format!(" ({input})"); }
+        match &outcome.answer {
+            Ok(answer) => {
+                let runtime = format!("{:>12}", format!("{:.3?}", outcome.duration));
+                let runtime = if outcome.duration >= SLOW { Style::Yellow.paint(runtime).to_string() } else { runtime };
+                line += &format!(": {:<16} {runtime}", Style::Green.paint(answer));
+                if outcome.cached { line += &format!(" {}", Style::Dim.paint("(cached)")); }
+                if let Some(stats) = outcome.stats { line += &format!(" {}", Style::Dim.paint(format!("({stats})"))); }
+                if let Some(memory) = outcome.memory { line += &format!("  {memory}"); }
+            }
+            Err(err) => line += &format!(": {}", Style::Red.paint(format!("error: {err}"))),
+        }
+        println!("{line}");
+        for line in outcome.explanation.iter().flat_map(|explanation| explanation.lines()) {
+            println!("    {line}");
+        }
+    }
+
+    fn finish(&mut self, report: &Report) {
+        report.print_summary();
+    }
+}
+
+/// Prints a single JSON document once the run is over.
+pub struct Json;
+
+impl Emitter for Json {
+    fn outcome(&mut self, _: &Outcome) {}
+
+    fn finish(&mut self, report: &Report) {
+        report.print_json();
+    }
+}
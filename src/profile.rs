@@ -0,0 +1,86 @@
+//! CPU profiles of the parts run with `--profile`, sampled with pprof while
+//! the solver runs, without changing its code.
+//!
+//! Each profile is written twice to the profile directory: `exNNp.folded`
+//! holds the folded stacks, one `frame;frame;frame count` line per stack,
+//! that `inferno-flamegraph` or `flamegraph.pl` turn into a flamegraph, and
+//! `exNNp.svg` is that flamegraph already drawn.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use pprof::{ProfilerGuard, ProfilerGuardBuilder};
+
+/// The number of stacks sampled per second, off a round number so as not
+/// to run in lockstep with periodic work.
+const FREQUENCY: i32 = 997;
+
+/// The libraries whose frames are not sampled, as unwinding through them
+/// is unreliable.
+const BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+/// A profile being sampled, from [`Profile::start`] to
+/// [`Profile::finish`].
+pub struct Profile {
+    guard: ProfilerGuard<'static>,
+    name: String,
+}
+
+/// Converts an error of pprof to an [`io::Error`].
+fn pprof_error(err: pprof::Error) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+impl Profile {
+    /// Starts sampling the stacks of every thread, for a part named `name`
+    /// such as `ex22a`.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the profiler cannot be started, such as
+    /// when another profile is being sampled.
+    pub fn start(name: String) -> io::Result<Self> {
+        let guard = ProfilerGuardBuilder::default().frequency(FREQUENCY).blocklist(BLOCKLIST).build().map_err(pprof_error)?;
+        tracing::info!(part = name, "profiling started");
+        Ok(Profile { guard, name })
+    }
+
+    /// Stops sampling and writes the folded stacks and the flamegraph of
+    /// the profile to `dir`.
+    ///
+    /// # Errors
+    /// Returns an [`io::Error`] if the profile cannot be built or written.
+    pub fn finish(self, dir: &Path) -> io::Result<()> {
+        let report = self.guard.report().build().map_err(pprof_error)?;
+        drop(self.guard);
+        tracing::info!(part = self.name, "profiling stopped");
+        // Samples of identical stacks taken on different threads are merged,
+        // and the lines sorted, so that profiles of the same run compare.
+        let mut stacks: BTreeMap<String, isize> = BTreeMap::new();
+        for (frames, count) in &report.data {
+            let mut stack = String::new();
+            for symbol in frames.frames.iter().rev().flat_map(|frame| frame.iter().rev()) {
+                if !stack.is_empty() { stack.push(';'); }
+                let _ = write!(stack, "{symbol}");
+            }
+            *stacks.entry(stack).or_default() += count;
+        }
+        if stacks.is_empty() {
+            eprintln!("{}: too fast to be sampled, nothing profiled", self.name);
+            return Ok(());
+        }
+        fs::create_dir_all(dir)?;
+        let folded = dir.join(format!("{}.folded", self.name));
+        let mut out = BufWriter::new(File::create(&folded)?);
+        for (stack, count) in &stacks {
+            writeln!(out, "{stack} {count}")?;
+        }
+        out.flush()?;
+        let svg = folded.with_extension("svg");
+        report.flamegraph(BufWriter::new(File::create(&svg)?)).map_err(pprof_error)?;
+        let samples: isize = stacks.values().sum();
+        eprintln!("{}: {samples} samples profiled to {} and {}", self.name, folded.display(), svg.display());
+        Ok(())
+    }
+}
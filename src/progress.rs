@@ -0,0 +1,22 @@
+/// Receives progress updates from long-running solvers.
+///
+/// Solvers report how much work they have done as they go, so that a
+/// runner can render a progress bar or an iteration counter. Updates may
+/// come from several threads at once and may be frequent, so implementations
+/// should be cheap and throttle any rendering themselves.
+pub trait ProgressSink: Sync {
+    /// Reports that `done` units of work are complete.
+    ///
+    /// # Arguments
+    /// * `done` - The units of work complete so far.
+    /// * `total` - The total units of work, if known in advance.
+    fn report(&self, done: u64, total: Option<u64>);
+}
+
+/// A [`ProgressSink`] ignoring every update, for tests and benchmarks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&self, _done: u64, _total: Option<u64>) {}
+}
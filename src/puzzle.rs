@@ -0,0 +1,226 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::error::AocError;
+use crate::input::{session_token, year_dir, USER_AGENT};
+
+/// Returns the path of the cached description of the puzzle of the provided
+/// day.
+///
+/// # Arguments
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
+    year_dir("puzzles", year).join(format!("day{day:02}.md"))
+}
+
+/// Downloads the HTML page of the puzzle of the provided day, which only
+/// shows the second part once the first one is solved by the account of
+/// the session token, if any.
+///
+/// # Errors
+/// Returns an error if the request fails.
+fn download(year: u32, day: u32) -> io::Result<String> {
+    let url = format!("https://adventofcode.com/{year}/day/{day}");
+    let mut request = ureq::get(&url).set("User-Agent", USER_AGENT);
+    if let Some(token) = session_token() {
+        request = request.set("Cookie", &format!("session={token}"));
+    }
+    request.call().map_err(io::Error::other)?.into_string()
+}
+
+/// Replaces the HTML entities of `text` by the characters they stand for.
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Returns the value of an attribute of an HTML tag, such as the `href` of
+/// `a href="/2015/day/1"`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    tag[start..].find('"').map(|end| &tag[start..start + end])
+}
+
+/// Ends the current block of `markdown`, such as a paragraph, with a
+/// blank line.
+fn end_block(markdown: &mut String) {
+    markdown.truncate(markdown.trim_end_matches(' ').len());
+    while !markdown.is_empty() && !markdown.ends_with("\n\n") {
+        markdown.push('\n');
+    }
+}
+
+/// Appends the text between two tags to `markdown`, collapsing its
+/// whitespace unless it is preformatted.
+fn push_text(text: &str, preformatted: bool, markdown: &mut String) {
+    let text = decode_entities(text);
+    if preformatted {
+        markdown.push_str(&text);
+        return;
+    }
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let line_start = markdown.is_empty() || markdown.ends_with('\n');
+    if text.starts_with(char::is_whitespace) && !line_start && !markdown.ends_with(' ') {
+        markdown.push(' ');
+    }
+    markdown.push_str(&words.join(" "));
+    if text.ends_with(char::is_whitespace) && !words.is_empty() {
+        markdown.push(' ');
+    }
+}
+
+/// Converts the HTML of a puzzle description into Markdown, appending it to
+/// `markdown`.
+///
+/// Headings, paragraphs, lists, links, emphasis and code are kept, and
+/// every other tag is stripped.
+fn convert(html: &str, markdown: &mut String) {
+    let mut preformatted = false;
+    let mut links = Vec::new();
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        push_text(&rest[..open], preformatted, markdown);
+        let close = rest[open..].find('>').map_or(rest.len(), |close| open + close);
+        let tag = &rest[open + 1..close];
+        rest = rest.get(close + 1..).unwrap_or("");
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                end_block(markdown);
+                markdown.push_str("## ");
+            }
+            ("h2" | "p" | "ul", _) => end_block(markdown),
+            ("li", false) => {
+                markdown.truncate(markdown.trim_end_matches(' ').len());
+                if !markdown.is_empty() && !markdown.ends_with('\n') { markdown.push('\n'); }
+                markdown.push_str("- ");
+            }
+            ("pre", false) => {
+                end_block(markdown);
+                markdown.push_str("```\n");
+                preformatted = true;
+            }
+            ("pre", true) => {
+                if !markdown.ends_with('\n') { markdown.push('\n'); }
+                markdown.push_str("```");
+                end_block(markdown);
+                preformatted = false;
+            }
+            ("code", _) if !preformatted => markdown.push('`'),
+            ("em", _) if !preformatted => markdown.push('*'),
+            ("a", false) => {
+                markdown.push('[');
+                links.push(attribute(tag, "href").unwrap_or("").to_string());
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                let href = if href.starts_with('/') { format!("https://adventofcode.com{href}") } else { href };
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+    push_text(rest, preformatted, markdown);
+}
+
+/// Extracts the description of a puzzle from its HTML page and converts it
+/// into Markdown.
+///
+/// Only the `<article>` elements of the page are kept, one per part of the
+/// puzzle.
+///
+/// # Examples
+/// ```
+/// use aoc2015::puzzle::to_markdown;
+/// let page = "<html><nav>Events</nav><main>\
+///     <article class=\"day-desc\"><h2>--- Day 1: Not Quite Lisp ---</h2>\
+///     <p>Santa is trying to <em>deliver presents</em>, see <a href=\"/2015/day/1/input\">input</a>.</p>\
+///     <ul><li><code>(())</code> and <code>()()</code> both result in floor <code>0</code>.</li></ul>\
+///     <pre><code>&lt;x&gt;\n</code></pre></article></main></html>";
+/// assert_eq!(to_markdown(page), "## --- Day 1: Not Quite Lisp ---\n\n\
+///     Santa is trying to *deliver presents*, see [input](https://adventofcode.com/2015/day/1/input).\n\n\
+///     - `(())` and `()()` both result in floor `0`.\n\n\
+///     ```\n<x>\n```\n");
+/// ```
+pub fn to_markdown(page: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<article") {
+        let body = rest[start..].find('>').map_or(rest.len(), |end| start + end + 1);
+        let end = rest[body..].find("</article>").map_or(rest.len(), |end| body + end);
+        convert(&rest[body..end], &mut markdown);
+        rest = &rest[end..];
+        rest = rest.strip_prefix("</article>").unwrap_or(rest);
+    }
+    end_block(&mut markdown);
+    markdown.truncate(markdown.trim_end().len());
+    markdown.push('\n');
+    markdown
+}
+
+/// Returns the title of a puzzle from its description in Markdown, as
+/// returned by [`to_markdown`].
+///
+/// # Returns
+/// The title, or `None` if the description does not start with the
+/// heading of a day.
+///
+/// # Examples
+/// ```
+/// use aoc2015::puzzle::puzzle_title;
+/// let markdown = "## --- Day 6: Probably a Fire Hazard ---\n\nBecause your neighbors...\n";
+/// assert_eq!(puzzle_title(markdown), Some("Probably a Fire Hazard"));
+/// assert_eq!(puzzle_title("Because your neighbors..."), None);
+/// ```
+pub fn puzzle_title(markdown: &str) -> Option<&str> {
+    let heading = markdown.lines().next()?.strip_prefix("## --- Day ")?.strip_suffix(" ---")?;
+    heading.split_once(": ").map(|(_, title)| title)
+}
+
+/// Reads the description of the puzzle of the provided day, as Markdown.
+///
+/// The description is read from its cached copy under `puzzles/` if
+/// present, and is otherwise downloaded and cached for the next runs. A
+/// cached description lacking the second part is downloaded again when a
+/// session token is configured, in case the first part was solved since,
+/// and is kept as is if the download fails.
+///
+/// # Arguments
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+///
+/// # Errors
+/// Returns an [`AocError::Io`] if the description is not cached and cannot
+/// be downloaded, or the cache cannot be read or written.
+pub fn read_puzzle(year: u32, day: u32) -> Result<String, AocError> {
+    let path = puzzle_path(year, day);
+    let cached = match fs::read_to_string(&path) {
+        Ok(markdown) => Some(markdown),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    if let Some(markdown) = &cached {
+        if markdown.contains("--- Part Two ---") || session_token().is_none() {
+            return Ok(markdown.clone());
+        }
+    }
+    let markdown = match (download(year, day).map(|page| to_markdown(&page)), cached) {
+        (Ok(markdown), _) => markdown,
+        (Err(_), Some(cached)) => return Ok(cached),
+        (Err(err), None) => return Err(io::Error::new(err.kind(), format!("cannot download the puzzle of day {day}: {err}")).into()),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, &markdown)?;
+    Ok(markdown)
+}
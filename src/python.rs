@@ -0,0 +1,106 @@
+//! Python bindings, to call the solvers from a notebook, built as the
+//! `aoc2015` extension module with `maturin develop --features python`:
+//!
+//! ```python
+//! import aoc2015
+//! aoc2015.solve(1, "b", "()())")  # '5'
+//! aoc2015.days()[0]  # (1, 'Not Quite Lisp')
+//! ```
+//!
+//! Malformed inputs, and unknown days or parts, raise a `ValueError`, and
+//! inputs without an answer a `RuntimeError`. Panics of the solvers are
+//! raised as a `pyo3_runtime.PanicException` rather than aborting the
+//! interpreter.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use crate::days;
+use crate::error::AocError;
+use crate::solution::Part;
+use crate::year2015;
+
+/// Why a part could not be solved.
+#[derive(Debug, PartialEq)]
+enum Failure {
+    /// The day, the part or the input is invalid.
+    Invalid(String),
+    /// The solver found no answer.
+    Failed(String),
+}
+
+impl From<Failure> for PyErr {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::Invalid(message) => PyValueError::new_err(message),
+            Failure::Failed(message) => PyRuntimeError::new_err(message),
+        }
+    }
+}
+
+/// Solves a part of the puzzle of a day of 2015.
+///
+/// # Errors
+/// Returns a [`Failure`] if the day or the part is unknown, or if the
+/// input is malformed or has no answer.
+fn run(day: u32, part: &str, input: &str) -> Result<String, Failure> {
+    let solution = days::find(year2015::YEAR, day).ok_or_else(|| Failure::Invalid(format!("day {day} is not implemented")))?;
+    let part: Part = part.parse().map_err(Failure::Invalid)?;
+    match solution.solve(part, input.trim_end()) {
+        Ok(answer) => Ok(answer.to_string()),
+        Err(err @ AocError::Parse { .. }) => Err(Failure::Invalid(err.to_string())),
+        Err(err) => Err(Failure::Failed(err.to_string())),
+    }
+}
+
+/// Solves a part of the puzzle of a day of 2015.
+///
+/// # Arguments
+/// * `day` - The day of the puzzle.
+/// * `part` - The part to solve, `"a"` or `"b"`.
+/// * `input` - The puzzle input.
+///
+/// # Returns
+/// The answer, formatted as the puzzle expects it.
+///
+/// # Errors
+/// Raises a `ValueError` if the day or the part is unknown or the input is
+/// malformed, and a `RuntimeError` if the input has no answer.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u32, part: &str, input: &str) -> PyResult<String> {
+    // Solvers may run for seconds, during which other Python threads run.
+    Ok(py.detach(|| run(day, part, input))?)
+}
+
+/// Lists the implemented days of 2015.
+///
+/// # Returns
+/// The day and the title of the puzzle of each implemented day, in order.
+#[pyfunction(name = "days")]
+fn implemented_days() -> Vec<(u32, String)> {
+    days::registry(year2015::YEAR).iter().map(|solution| (solution.day(), solution.title().to_string())).collect()
+}
+
+/// The `aoc2015` Python module.
+#[pymodule]
+fn aoc2015(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(implemented_days, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves() {
+        assert_eq!(run(1, "b", "()())\n"), Ok("5".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_day_part_and_input() {
+        assert!(matches!(run(15, "a", ""), Err(Failure::Invalid(_))));
+        assert!(matches!(run(1, "c", ""), Err(Failure::Invalid(_))));
+        assert!(matches!(run(1, "a", "(x"), Err(Failure::Invalid(_))));
+    }
+}
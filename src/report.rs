@@ -0,0 +1,124 @@
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+use aoc2015::days;
+use aoc2015::solution::{Part, Parts, Solution};
+use crate::output::Emitter;
+use crate::runner::{Outcome, Report};
+
+/// The comments introducing the solution of each part in the module of a
+/// day, followed by the notes on how it is solved.
+const MARKERS: [&str; 2] = [
+    "// This is a solution to the first part of the puzzle.",
+    "// This is a solution to the second part of the puzzle.",
+];
+
+/// Returns the notes on how the parts of a day are solved: the comments
+/// following the introduction of each part in its module, joined into a
+/// single line, with their links to items dropped.
+fn notes(source: &str) -> String {
+    let mut notes = Vec::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        if !MARKERS.contains(&line.trim()) { continue; }
+        notes.extend(lines.by_ref()
+            .map_while(|line| line.trim().strip_prefix("//"))
+            .map(str::trim)
+            .filter(|note| !note.is_empty()));
+    }
+    notes.join(" ").replace("[`", "`").replace("`]", "`")
+}
+
+/// Escapes the characters of `text` that would break a cell of a Markdown
+/// table.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Formats the answer of an [`Outcome`] for a cell of the table.
+fn answer_cell(outcome: Option<&Outcome>) -> String {
+    match outcome.map(|outcome| &outcome.answer) {
+        Some(Ok(answer)) => format!("`{}`", cell(&answer.to_string())),
+        Some(Err(err)) => format!("error: {}", cell(&err.to_string())),
+        None => "-".to_string(),
+    }
+}
+
+/// The number of days of the event.
+const DAYS: u32 = 25;
+
+/// Returns an overview of the progress of the crate: the number of parts
+/// and days implemented, then the parts of every day, `*` for both, `+`
+/// for one and `.` for none.
+fn progress(solutions: &[Box<dyn Solution>]) -> String {
+    let implemented = |day| solutions.iter()
+        .find(|solution| solution.day() == day)
+        .map_or(Parts::NONE, |solution| solution.implemented());
+    let days: String = (1..=DAYS)
+        .map(|day| match implemented(day).len() {
+            2 => '*',
+            1 => '+',
+            _ => '.',
+        })
+        .collect();
+    let stars: usize = (1..=DAYS).map(|day| implemented(day).len()).sum();
+    let complete = days.chars().filter(|&day| day == '*').count();
+    format!("**Progress:** {stars}/{} parts, {complete}/{DAYS} days complete `{days}`\n", 2 * DAYS)
+}
+
+/// Prints an overview of the progress of the crate and a Markdown table of
+/// the answers, runtimes and notes on how the days of a run are solved,
+/// once the run is over.
+///
+/// The notes are read from `src/yearNNNN/exNN.rs`, relative to the working
+/// directory, and are left empty outside of the repository.
+pub struct Markdown {
+    /// The year of the run.
+    year: u32,
+    /// The days of the run, listed even when they have nothing to run.
+    days: Vec<u32>,
+}
+
+impl Markdown {
+    /// Creates a [`Markdown`] emitter listing `days` of `year`.
+    pub fn new(year: u32, days: Vec<u32>) -> Self {
+        Markdown { year, days }
+    }
+
+    /// Returns the overview and the table of the days of `report`.
+    fn table(&self, report: &Report) -> String {
+        let solutions = days::registry(self.year);
+        let mut table = progress(&solutions);
+        table.push_str("\n| Day | Part A | Part B | Runtime | Notes |\n|---|---|---|---:|---|\n");
+        let mut total = Duration::ZERO;
+        for solution in solutions.iter().filter(|solution| self.days.contains(&solution.day())) {
+            let day = solution.day();
+            let notes = fs::read_to_string(format!("src/year{}/ex{day:02}.rs", self.year)).map(|source| notes(&source)).unwrap_or_default();
+            let (a, b, runtime) = match report.failed_days().iter().find(|(failed, _)| *failed == day) {
+                Some((_, err)) => (format!("error: {}", cell(&err.to_string())), "-".to_string(), "-".to_string()),
+                None => {
+                    let outcomes: Vec<&Outcome> = report.outcomes().iter().filter(|outcome| outcome.day == day).collect();
+                    let runtime: Duration = outcomes.iter().map(|outcome| outcome.duration).sum();
+                    total += runtime;
+                    let part_cell = |part| match outcomes.iter().find(|outcome| outcome.part == part) {
+                        None if !solution.implemented().contains(part) => "not yet implemented".to_string(),
+                        outcome => answer_cell(outcome.copied()),
+                    };
+                    (part_cell(Part::A), part_cell(Part::B), format!("{runtime:.3?}"))
+                }
+            };
+            let link = format!("[{day}: {}]({})", cell(solution.title()), solution.url());
+            writeln!(table, "| {link} | {a} | {b} | {runtime} | {} |", cell(&notes)).unwrap();
+        }
+        writeln!(table, "| **Total** | | | {total:.3?} | |").unwrap();
+        table
+    }
+}
+
+impl Emitter for Markdown {
+    fn outcome(&mut self, _: &Outcome) {}
+
+    fn finish(&mut self, report: &Report) {
+        print!("{}", self.table(report));
+    }
+}
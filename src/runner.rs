@@ -0,0 +1,658 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use aoc2015::answer::Answer;
+use aoc2015::config::{PuzzleConfig, CONFIG_FILE};
+use aoc2015::context::Context;
+use aoc2015::error::AocError;
+use aoc2015::input::read_exercise_input;
+use aoc2015::progress::{NoProgress, ProgressSink};
+use aoc2015::solution::{Implementation, Part, Solution};
+use aoc2015::util::checkpoint::{CheckpointStore, NoCheckpoint};
+use aoc2015::util::image::write_pgm;
+use aoc2015::util::stats::Stats;
+use serde_json::{json, Value};
+use crate::cache::{self, CacheMode, CheckpointFile, Key};
+use crate::mem::{self, MemoryUsage};
+use crate::output::Style;
+#[cfg(feature = "profile")]
+use crate::profile::Profile;
+
+/// The outcome of running one part of a day.
+pub struct Outcome {
+    /// The day that was run.
+    pub day: u32,
+    /// The part that was run.
+    pub part: Part,
+    /// The answer of the part, or why it could not be computed.
+    pub answer: Result<Answer, AocError>,
+    /// The wall-clock time spent computing the answer, the median one when
+    /// the part was repeated.
+    pub duration: Duration,
+    /// The statistics of the runtimes of the part, when it was repeated
+    /// with [`RunOptions::repeat`].
+    pub stats: Option<Stats>,
+    /// Whether the answer was taken from the cache of a past run, along
+    /// with the duration of that run.
+    pub cached: bool,
+    /// The heap usage of the part, measured with the `mem-profile` feature.
+    pub memory: Option<MemoryUsage>,
+    /// The name of the input file the part ran on, when a day runs on
+    /// several.
+    pub input: Option<String>,
+    /// What the solution explained about the answer, when run with
+    /// [`RunOptions::explain`].
+    pub explanation: Option<String>,
+}
+
+/// How the runner runs each day.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions<'a> {
+    /// Whether to check the examples of the puzzle text first.
+    pub examples: bool,
+    /// Whether to render the progress of long-running parts.
+    pub progress: bool,
+    /// The name of the alternate implementation to run instead of the
+    /// [`Solution`] itself.
+    pub implementation: Option<&'a str>,
+    /// Whether to check that every implementation of a part finds the
+    /// same answer.
+    pub verify: bool,
+    /// The directory to write the pictures of the parts to, if any.
+    pub render: Option<&'a Path>,
+    /// Whether to reuse the answers stored by past runs.
+    pub cache: CacheMode,
+    /// Whether to collect the explanations of the solutions, which also
+    /// keeps the cached answers from being reused.
+    pub explain: bool,
+    /// Whether to resume the searches interrupted by past runs from their
+    /// checkpoints.
+    pub resume: bool,
+    /// The number of timed runs of each part, after a warm-up run whose
+    /// runtime is discarded, to report their [`Stats`]. Each part runs once
+    /// if `None`.
+    pub repeat: Option<u32>,
+    /// The seed of the randomized strategies of the solutions, the
+    /// [`Context::DEFAULT_SEED`] if `None`.
+    pub seed: Option<u64>,
+    /// The parameters of the puzzles, [`PuzzleConfig::PUZZLE`] if `None`.
+    pub config: Option<&'a PuzzleConfig>,
+    /// The directory to write the CPU profile of each part to, if any,
+    /// which also keeps the cached answers from being reused.
+    #[cfg(feature = "profile")]
+    pub profile: Option<&'a Path>,
+}
+
+/// The minimum delay between two renderings of a [`ProgressLine`].
+const PROGRESS_REFRESH: Duration = Duration::from_millis(100);
+
+/// The width of the bar of a [`ProgressLine`], in characters.
+const PROGRESS_WIDTH: u64 = 30;
+
+/// A [`ProgressSink`] rendering a progress bar, or an iteration counter
+/// when the total is unknown, on a single line of standard error.
+struct ProgressLine {
+    label: String,
+    /// When the line was last rendered, to throttle the updates.
+    rendered: Mutex<Option<Instant>>,
+}
+
+impl ProgressLine {
+    fn new(label: String) -> Self {
+        ProgressLine { label, rendered: Mutex::new(None) }
+    }
+
+    /// Erases the line if it was ever rendered.
+    fn clear(&self) {
+        if self.rendered.lock().unwrap().is_some() {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+impl ProgressSink for ProgressLine {
+    fn report(&self, done: u64, total: Option<u64>) {
+        // Updates racing with a rendering are dropped rather than waited for.
+        let Ok(mut rendered) = self.rendered.try_lock() else { return };
+        if rendered.is_some_and(|at| at.elapsed() < PROGRESS_REFRESH) { return; }
+        *rendered = Some(Instant::now());
+        match total {
+            Some(total) if total > 0 => {
+                let done = done.min(total);
+                let filled = (done * PROGRESS_WIDTH / total) as usize;
+                let bar = format!("{}{}", "#".repeat(filled), "-".repeat(PROGRESS_WIDTH as usize - filled));
+                eprint!("\r{} [{bar}] {:>3}%", self.label, done * 100 / total);
+            }
+            _ => eprint!("\r{} {done} iterations", self.label),
+        }
+    }
+}
+
+/// Runs one part of a [`Solution`], measuring how long it takes.
+///
+/// # Arguments
+/// * `solution` - The [`Solution`] to run.
+/// * `part` - The [`Part`] to run.
+/// * `input` - The input of the day.
+/// * `implementation` - The alternate [`Implementation`] to run instead
+///   of the [`Solution`] itself, if any.
+/// * `options` - Whether to render the progress of the part, whether to
+///   collect the explanation of the [`Solution`], which alternate
+///   implementations do not give, and the seed of its [`Context`].
+/// * `checkpoint` - Where the search of the [`Solution`] saves its
+///   progress, and finds the progress to resume from.
+///
+/// # Returns
+/// The [`Outcome`] of the part.
+pub fn run_part(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    implementation: Option<&Implementation>,
+    options: RunOptions,
+    checkpoint: &dyn CheckpointStore,
+) -> Outcome {
+    let _span = tracing::info_span!("part", %part).entered();
+    let line = options.progress.then(|| ProgressLine::new(format!("ex{:02}{part}", solution.day())));
+    let sink: &dyn ProgressSink = match &line {
+        Some(line) => line,
+        None => &NoProgress,
+    };
+    let config = options.config.unwrap_or(&PuzzleConfig::PUZZLE);
+    let context = Context { progress: sink, checkpoint, seed: options.seed.unwrap_or(Context::DEFAULT_SEED), config };
+    let mut explanation = options.explain.then(Vec::new);
+    #[cfg(feature = "profile")]
+    let profile = options.profile.and_then(|_| {
+        Profile::start(format!("ex{:02}{part}", solution.day()))
+            .inspect_err(|err| tracing::warn!(%part, "unable to start profiling: {err}"))
+            .ok()
+    });
+    let start = Instant::now();
+    let (answer, memory) = mem::measure(|| match (implementation, &mut explanation) {
+        (Some(implementation), _) => (implementation.solve)(input),
+        (None, Some(out)) => solution.solve_explained(part, input, &context, out),
+        (None, None) => solution.solve_in(part, input, &context),
+    });
+    let duration = start.elapsed();
+    if let Some(line) = line { line.clear(); }
+    #[cfg(feature = "profile")]
+    if let (Some(profile), Some(dir)) = (profile, options.profile) {
+        if let Err(err) = profile.finish(dir) {
+            tracing::warn!(%part, "unable to write the profile: {err}");
+        }
+    }
+    tracing::debug!(?duration, ?memory, ok = answer.is_ok(), "ran the part");
+    let explanation = explanation
+        .filter(|explanation| !explanation.is_empty())
+        .map(|explanation| String::from_utf8_lossy(&explanation).into_owned());
+    Outcome { day: solution.day(), part, answer, duration, stats: None, cached: false, memory, input: None, explanation }
+}
+
+/// Runs a part again [`RunOptions::repeat`] times after the warm-up run of
+/// `warm_up`, replacing its runtime by the median of the timed runs and
+/// adding their [`Stats`].
+///
+/// The timed runs neither render their progress, save checkpoints nor are
+/// profiled, and the first of them that fails replaces the outcome.
+fn repeat_part(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    implementation: Option<&Implementation>,
+    options: RunOptions,
+    warm_up: Outcome,
+) -> Outcome {
+    let repeat = options.repeat.unwrap_or(0);
+    let options = RunOptions {
+        progress: false,
+        explain: false,
+        #[cfg(feature = "profile")]
+        profile: None,
+        ..options
+    };
+    let mut durations = Vec::with_capacity(repeat as usize);
+    for _ in 0..repeat {
+        let outcome = run_part(solution, part, input, implementation, options, &NoCheckpoint);
+        if outcome.answer.is_err() { return outcome; }
+        durations.push(outcome.duration);
+    }
+    let stats = Stats::of(&durations);
+    tracing::debug!(%part, ?stats, "repeated the part");
+    Outcome { duration: stats.map_or(warm_up.duration, |stats| stats.median), stats, ..warm_up }
+}
+
+/// Runs one part of a [`Solution`] like [`run_part`], going through the
+/// answer cache, and repeats it if [`RunOptions::repeat`] is set.
+///
+/// A part whose answer is stored for the same implementation and input is
+/// not run again if the cache is reused, unless it is repeated. Parts run
+/// with other parameters than those of the puzzles bypass the cache and
+/// save no checkpoints, as both are keyed by the input alone. Answers
+/// found by running the part are stored. Failing to access the cache is
+/// only logged, as it never prevents computing the answer.
+///
+/// The search of the [`Solution`] itself saves checkpoints, resumed with
+/// [`RunOptions::resume`] and removed once it finds the answer.
+fn run_cached_part(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    implementation: Option<&Implementation>,
+    options: RunOptions,
+) -> Outcome {
+    let key = Key::new(solution.year(), solution.day(), part, implementation.map(|implementation| implementation.name), input);
+    #[cfg(feature = "profile")]
+    let profiled = options.profile.is_some();
+    #[cfg(not(feature = "profile"))]
+    let profiled = false;
+    let puzzle = options.config.is_none_or(PuzzleConfig::is_puzzle);
+    if options.cache == CacheMode::Reuse && !options.explain && options.repeat.is_none() && !profiled && puzzle {
+        match cache::lookup(&key) {
+            Ok(Some((answer, duration))) => {
+                tracing::debug!(%part, "reused the cached answer");
+                return Outcome { day: solution.day(), part, answer: Ok(answer), duration, stats: None, cached: true, memory: None, input: None, explanation: None };
+            }
+            Ok(None) => {}
+            Err(err) => tracing::warn!(%part, "unable to read the answer cache: {err}"),
+        }
+    }
+    let checkpoint = (implementation.is_none() && puzzle).then(|| CheckpointFile::new(&key, options.resume));
+    let store: &dyn CheckpointStore = match &checkpoint {
+        Some(checkpoint) => checkpoint,
+        None => &NoCheckpoint,
+    };
+    let mut outcome = run_part(solution, part, input, implementation, options, store);
+    if options.repeat.is_some() && outcome.answer.is_ok() {
+        outcome = repeat_part(solution, part, input, implementation, options, outcome);
+    }
+    if let (true, Ok(answer)) = (puzzle, &outcome.answer) {
+        if let Some(checkpoint) = &checkpoint { checkpoint.remove(); }
+        if let Err(err) = cache::store(&key, answer, outcome.duration) {
+            tracing::warn!(%part, "unable to store the answer in the cache: {err}");
+        }
+    }
+    outcome
+}
+
+/// Checks the examples of the selected parts of a [`Solution`].
+///
+/// # Returns
+/// The number of examples checked.
+///
+/// # Errors
+/// Returns an [`AocError::Example`] describing the first failing example.
+pub fn check_examples(solution: &dyn Solution, parts: &[Part]) -> Result<usize, AocError> {
+    let examples: Vec<_> = solution.examples().iter().filter(|example| parts.contains(&example.part)).collect();
+    examples.iter()
+        .try_for_each(|example| example.check(solution))
+        .map_err(|message| AocError::Example { day: solution.day(), message })?;
+    Ok(examples.len())
+}
+
+/// Returns the alternate [`Implementation`] of a part with the provided name.
+///
+/// # Returns
+/// The [`Implementation`], or `None` if the part has no alternate
+/// implementations, in which case the [`Solution`] itself runs.
+///
+/// # Errors
+/// Returns an [`AocError::Implementation`] listing the available names if
+/// the part has alternate implementations but none with that name.
+fn find_implementation(solution: &dyn Solution, part: Part, name: &str) -> Result<Option<&'static Implementation>, AocError> {
+    let implementations: Vec<_> = solution.implementations().iter()
+        .filter(|implementation| implementation.part == part)
+        .collect();
+    if implementations.is_empty() {
+        return Ok(None);
+    }
+    match implementations.iter().find(|implementation| implementation.name == name) {
+        Some(implementation) => Ok(Some(implementation)),
+        None => {
+            let names: Vec<_> = implementations.iter().map(|implementation| implementation.name).collect();
+            let message = format!("part {part} has no implementation '{name}', expected one of {}", names.join(", "));
+            Err(AocError::Implementation { day: solution.day(), message })
+        }
+    }
+}
+
+/// Checks that every other implementation of a part finds the same answer
+/// as the one that was run.
+///
+/// # Arguments
+/// * `solution` - The [`Solution`] whose implementations to compare.
+/// * `part` - The [`Part`] to check.
+/// * `input` - The input of the day.
+/// * `selected` - The alternate [`Implementation`] that was run, or `None`
+///   if the [`Solution`] itself was.
+/// * `expected` - The answer it found.
+///
+/// # Errors
+/// Returns an [`AocError::Implementation`] naming the first implementation
+/// that fails or finds another answer.
+pub fn verify(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    selected: Option<&Implementation>,
+    expected: &Answer,
+) -> Result<(), AocError> {
+    let default = selected.map(|_| ("default", solution.solve(part, input)));
+    let alternates = solution.implementations().iter()
+        .filter(|implementation| implementation.part == part)
+        .filter(|implementation| selected.is_none_or(|selected| selected.name != implementation.name))
+        .map(|implementation| (implementation.name, (implementation.solve)(input)));
+    for (name, answer) in default.into_iter().chain(alternates) {
+        let message = match answer {
+            Ok(answer) if answer == *expected => continue,
+            Ok(answer) => format!("part {part}: {name} found {answer}, expected {expected}"),
+            Err(err) => format!("part {part}: {name} failed: {err}"),
+        };
+        return Err(AocError::Implementation { day: solution.day(), message });
+    }
+    Ok(())
+}
+
+/// Writes the picture of a part, if its [`Solution`] renders one, to
+/// `exNNp.pgm` in `dir`.
+///
+/// # Errors
+/// Returns an [`AocError`] if the picture cannot be rendered or written.
+fn render_part(solution: &dyn Solution, part: Part, input: &str, dir: &Path) -> Result<(), AocError> {
+    let name = format!("ex{:02}{part}", solution.day());
+    let Some(image) = solution.render(part, input) else {
+        eprintln!("{name}: nothing to render");
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{name}.pgm"));
+    write_pgm(&image?, BufWriter::new(File::create(&path)?))?;
+    eprintln!("{name}: rendered to {}", path.display());
+    Ok(())
+}
+
+/// Describes the parts of `parts` that a [`Solution`] does not implement,
+/// such as `Day 6: Probably a Fire Hazard — part b not yet implemented`.
+///
+/// # Returns
+/// The description, or `None` if every part is implemented.
+pub fn missing_parts(solution: &dyn Solution, parts: &[Part]) -> Option<String> {
+    let missing: Vec<String> = parts.iter()
+        .filter(|&&part| !solution.implemented().contains(part))
+        .map(Part::to_string)
+        .collect();
+    let missing = match &missing[..] {
+        [] => return None,
+        [part] => format!("part {part}"),
+        parts => format!("parts {}", parts.join(" and ")),
+    };
+    Some(format!("Day {}: {} — {missing} not yet implemented", solution.day(), solution.title()))
+}
+
+/// Returns the parts of `parts` that a [`Solution`] implements, printing
+/// the [`missing_parts`] it skips to standard error.
+fn implemented_parts(solution: &dyn Solution, parts: &[Part]) -> Vec<Part> {
+    if let Some(missing) = missing_parts(solution, parts) {
+        eprintln!("{}", Style::Dim.paint(missing));
+    }
+    parts.iter().copied().filter(|&part| solution.implemented().contains(part)).collect()
+}
+
+/// Reads the input of a [`Solution`] and runs the selected parts on it.
+///
+/// # Arguments
+/// * `solution` - The [`Solution`] to run.
+/// * `parts` - The parts to run.
+/// * `options` - How to run the day.
+///
+/// # Returns
+/// The [`Outcome`] of each part the [`Solution`] implements, in the order
+/// of `parts`.
+///
+/// # Errors
+/// Returns an [`AocError`] if the input of the day cannot be read, or if
+/// [`run_day_on`] fails.
+pub fn run_day(solution: &dyn Solution, parts: &[Part], options: RunOptions) -> Result<Vec<Outcome>, AocError> {
+    // Days solving no selected part need no input.
+    let parts = implemented_parts(solution, parts);
+    if parts.is_empty() { return Ok(Vec::new()); }
+    let input = read_exercise_input(solution.year(), solution.day())?;
+    run_day_on(solution, &parts, &input, options)
+}
+
+/// Runs the selected parts of a [`Solution`] on an input.
+///
+/// # Arguments
+/// * `solution` - The [`Solution`] to run.
+/// * `parts` - The parts to run.
+/// * `input` - The input of the day.
+/// * `options` - How to run the day.
+///
+/// # Returns
+/// The [`Outcome`] of each part the [`Solution`] implements, in the order
+/// of `parts`.
+///
+/// # Errors
+/// Returns an [`AocError`] if no selected part has the selected
+/// implementation, alternate implementations would run with parameters
+/// other than those of the puzzles, an example fails or a picture cannot
+/// be rendered.
+pub fn run_day_on(solution: &dyn Solution, parts: &[Part], input: &str, options: RunOptions) -> Result<Vec<Outcome>, AocError> {
+    let _span = tracing::info_span!("day", day = solution.day()).entered();
+    let parts = &implemented_parts(solution, parts)[..];
+    let mut implementations = vec![None; parts.len()];
+    if let Some(name) = options.implementation {
+        for (implementation, &part) in implementations.iter_mut().zip(parts) {
+            *implementation = find_implementation(solution, part, name)?;
+        }
+        if implementations.iter().all(Option::is_none) {
+            let message = format!("no alternate implementation '{name}'");
+            return Err(AocError::Implementation { day: solution.day(), message });
+        }
+    }
+    // The alternate implementations only solve the puzzles themselves.
+    let alternates = options.implementation.is_some()
+        || options.verify && solution.implementations().iter().any(|implementation| parts.contains(&implementation.part));
+    if alternates && options.config.is_some_and(|config| !config.is_puzzle()) {
+        let message = format!("the alternate implementations ignore {CONFIG_FILE}");
+        return Err(AocError::Implementation { day: solution.day(), message });
+    }
+    if options.examples { check_examples(solution, parts)?; }
+    if let Some(dir) = options.render {
+        for &part in parts {
+            render_part(solution, part, input, dir)?;
+        }
+    }
+    Ok(parts.iter().zip(implementations)
+        .map(|(&part, implementation)| {
+            let mut outcome = run_cached_part(solution, part, input, implementation, options);
+            if let (true, Ok(answer)) = (options.verify, &outcome.answer) {
+                if let Err(err) = verify(solution, part, input, implementation, answer) {
+                    outcome.answer = Err(err);
+                }
+            }
+            outcome
+        })
+        .collect())
+}
+
+/// Runs several days concurrently on a pool of worker threads.
+///
+/// Workers pick the next pending day as soon as they are free, and each
+/// day is timed on the worker running it. The results are handed to `emit`
+/// in the order of `solutions` as soon as every earlier day has completed.
+///
+/// # Arguments
+/// * `solutions` - The [`Solution`]s to run.
+/// * `parts` - The parts to run for every day.
+/// * `options` - How to run each day.
+/// * `emit` - Receives each day along with its result of [`run_day`], in order.
+pub fn run_parallel(
+    solutions: &[Box<dyn Solution>],
+    parts: &[Part],
+    options: RunOptions,
+    mut emit: impl FnMut(u32, Result<Vec<Outcome>, AocError>),
+) {
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(solutions.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(solution) = solutions.get(index) else { break };
+                if sender.send((index, run_day(solution.as_ref(), parts, options))).is_err() { break; }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&emitted) {
+                emit(solutions[emitted].day(), result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+/// Collects the [`Outcome`]s of a run to summarize them at the end.
+#[derive(Default)]
+pub struct Report {
+    outcomes: Vec<Outcome>,
+    /// The days whose input could not be read, along with the reason.
+    failed_days: Vec<(u32, AocError)>,
+}
+
+impl Report {
+    /// Adds an [`Outcome`] to this report.
+    pub fn push(&mut self, outcome: Outcome) {
+        self.outcomes.push(outcome);
+    }
+
+    /// Returns the [`Outcome`]s of this report, in the order they were added.
+    pub fn outcomes(&self) -> &[Outcome] {
+        &self.outcomes
+    }
+
+    /// Records a day that could not be run at all.
+    pub fn push_failed_day(&mut self, day: u32, err: AocError) {
+        self.failed_days.push((day, err));
+    }
+
+    /// Returns the days that could not be run at all, along with the reason.
+    pub fn failed_days(&self) -> &[(u32, AocError)] {
+        &self.failed_days
+    }
+
+    /// Returns whether every day could be run and every [`Outcome`] of this
+    /// report has an answer.
+    pub fn succeeded(&self) -> bool {
+        self.failed_days.is_empty() && self.outcomes.iter().all(|outcome| outcome.answer.is_ok())
+    }
+
+    /// Returns the total time spent over every [`Outcome`].
+    pub fn total(&self) -> Duration {
+        self.outcomes.iter().map(|outcome| outcome.duration).sum()
+    }
+
+    /// Returns each day along with the combined duration of its parts, in
+    /// the order the days were added.
+    pub fn day_totals(&self) -> Vec<(u32, Duration)> {
+        let mut days: Vec<(u32, Duration)> = Vec::new();
+        for outcome in &self.outcomes {
+            match days.iter_mut().find(|(day, _)| *day == outcome.day) {
+                Some((_, duration)) => *duration += outcome.duration,
+                None => days.push((outcome.day, outcome.duration)),
+            }
+        }
+        days
+    }
+
+    /// Returns the day with the largest combined duration of its parts.
+    pub fn slowest_day(&self) -> Option<(u32, Duration)> {
+        self.day_totals().into_iter().max_by_key(|&(_, duration)| duration)
+    }
+
+    /// Prints the summary table of this report.
+    pub fn print_summary(&self) {
+        println!("{}", "-".repeat(37));
+        println!("{:<23} {:>12.3?}", "Total runtime", self.total());
+        if let Some((day, duration)) = self.slowest_day() {
+            println!("{:<23} {:>12.3?}", format!("Slowest day: {day}"), duration);
+        }
+    }
+
+    /// Prints this report as a JSON document.
+    ///
+    /// The document holds a `results` array with the `day`, `part`,
+    /// `answer`, `duration` (in seconds) and whether it is `cached` of each
+    /// [`Outcome`], the `stats` of its runtimes if it was repeated, its
+    /// `peak_bytes` and `allocations` if they were measured, the name of
+    /// its `input` file if the day ran on several, and its `explanation`
+    /// if any. An `error` replaces the `answer` of failed parts. A
+    /// `failed_days` array lists the days that could not be run, and
+    /// `total` is the total runtime in seconds.
+    pub fn print_json(&self) {
+        let results: Vec<Value> = self.outcomes.iter()
+            .map(|outcome| {
+                let mut result = json!({
+                    "day": outcome.day,
+                    "part": outcome.part.to_string(),
+                    "duration": outcome.duration.as_secs_f64(),
+                    "cached": outcome.cached,
+                });
+                if let Some(input) = &outcome.input {
+                    result["input"] = json!(input);
+                }
+                if let Some(stats) = outcome.stats {
+                    result["stats"] = json!({
+                        "runs": stats.count,
+                        "min": stats.min.as_secs_f64(),
+                        "median": stats.median.as_secs_f64(),
+                        "mean": stats.mean.as_secs_f64(),
+                        "stddev": stats.stddev.as_secs_f64(),
+                    });
+                }
+                if let Some(memory) = outcome.memory {
+                    result["peak_bytes"] = json!(memory.peak_bytes);
+                    result["allocations"] = json!(memory.allocations);
+                }
+                if let Some(explanation) = &outcome.explanation {
+                    result["explanation"] = json!(explanation);
+                }
+                match &outcome.answer {
+                    Ok(answer) => result["answer"] = answer_value(answer),
+                    Err(err) => result["error"] = json!(err.to_string()),
+                }
+                result
+            })
+            .collect();
+        let failed_days: Vec<Value> = self.failed_days.iter()
+            .map(|(day, err)| json!({ "day": day, "error": err.to_string() }))
+            .collect();
+        let document = json!({
+            "results": results,
+            "failed_days": failed_days,
+            "total": self.total().as_secs_f64(),
+        });
+        println!("{document:#}");
+    }
+}
+
+/// Converts an [`Answer`] to a JSON number or string.
+pub fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(n) => json!(n),
+        Answer::UInt(n) => json!(n),
+        Answer::Text(text) => json!(text),
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use aoc2015::puzzle::{puzzle_title, read_puzzle};
+
+/// The template of a new day module, where `{year}` is the year, `{day}`
+/// the day, `{nth}` its ordinal and `{title}` the title of its puzzle as a
+/// string literal.
+const TEMPLATE: &str = r#"use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Parts, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { {year} }
+    fn day(&self) -> u32 { {day} }
+    fn title(&self) -> &str { {title} }
+    fn implemented(&self) -> Parts { Parts::NONE }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    // Example { part: Part::A, input: "", answer: "" },
+    // Example { part: Part::B, input: "", answer: "" },
+];
+
+// {nth} day of Advent of Code {year}
+//
+// https://adventofcode.com/{year}/day/{day}
+//
+// This is a solution to the first part of the puzzle.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    let _ = input;
+    Err(AocError::unsolvable("part a is not solved yet"))
+}
+
+// This is a solution to the second part of the puzzle.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    let _ = input;
+    Err(AocError::unsolvable("part b is not solved yet"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+}
+"#;
+
+/// The template of the module of a new year, where `{year}` is the year and
+/// `{module}` the module of its first day.
+const YEAR_TEMPLATE: &str = r#"//! Solutions to [Advent of Code {year}](https://adventofcode.com/{year}).
+//!
+//! Each day lives in its own `exNN` module exposing `a` and `b` solvers for
+//! both parts of the puzzle, plus a `Solver` implementing [`Solution`],
+//! registered by [`days`].
+
+use crate::solution::Solution;
+
+pub mod {module};
+
+/// The year of the puzzles of this module.
+pub const YEAR: u32 = {year};
+
+/// Returns the [`Solution`] of every implemented day of {year}, ordered by day.
+pub fn days() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new({module}::Solver),
+    ]
+}
+"#;
+
+/// Returns `day` followed by its English ordinal suffix, such as `22nd`.
+fn ordinal(day: u32) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{day}{suffix}")
+}
+
+/// Creates the module of a new day from [`TEMPLATE`] in `src/yearNNNN/`
+/// and registers it in the `days` of its year.
+///
+/// The module of a year without solutions yet is created from
+/// [`YEAR_TEMPLATE`] and registered in `src/lib.rs` and in the years of
+/// `src/days.rs`.
+///
+/// The title of the day is read from the description of its puzzle, which
+/// is downloaded if it is not cached yet, and is left as a placeholder if
+/// it cannot be.
+///
+/// Paths are relative to the working directory, which must be the root of
+/// the repository.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the day already exists, or if a file cannot
+/// be read or written.
+pub fn new_day(year: u32, day: u32) -> io::Result<()> {
+    let dir = Path::new("src").join(format!("year{year}"));
+    let module = format!("ex{day:02}");
+    let path = dir.join(format!("{module}.rs"));
+    if path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())));
+    }
+    let year_path = dir.join("mod.rs");
+    let (year_source, registered) = match fs::read_to_string(&year_path) {
+        Ok(year_source) => (register_solver(&register_module(&year_source, &module, "pub mod ex")?, &module)?, None),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let lib = register_module(&fs::read_to_string("src/lib.rs")?, &format!("year{year}"), "pub mod year")?;
+            let days = register_year(&fs::read_to_string("src/days.rs")?, year)?;
+            (YEAR_TEMPLATE.replace("{year}", &year.to_string()).replace("{module}", &module), Some((lib, days)))
+        }
+        Err(err) => return Err(err),
+    };
+
+    let title = read_puzzle(year, day).ok()
+        .and_then(|markdown| puzzle_title(&markdown).map(str::to_string))
+        .unwrap_or_else(|| "Untitled".to_string());
+    let module_source = TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{nth}", &ordinal(day))
+        .replace("{title}", &format!("{title:?}"));
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, module_source)?;
+    fs::write(&year_path, year_source)?;
+    if let Some((lib, days)) = registered {
+        fs::write("src/lib.rs", lib)?;
+        fs::write("src/days.rs", days)?;
+        eprintln!("Created {} and registered {year}", year_path.display());
+    }
+    eprintln!("Created {} and registered day {day} of {year}", path.display());
+    Ok(())
+}
+
+/// Returns `source` with `pub mod <module>;` inserted among the other
+/// declarations starting with `prefix`, in order.
+fn register_module(source: &str, module: &str, prefix: &str) -> io::Result<String> {
+    insert_sorted(source, &format!("pub mod {module};"), prefix)
+        .ok_or_else(|| io::Error::other(format!("no `{prefix}` module declared where {module} belongs")))
+}
+
+/// Returns the module of a year with the `Solver` of `module` added to its
+/// `days`, in order.
+fn register_solver(year_source: &str, module: &str) -> io::Result<String> {
+    let entry = format!("        Box::new({module}::Solver),");
+    insert_sorted(year_source, &entry, "Box::new(ex")
+        .ok_or_else(|| io::Error::other("unexpected layout of the module of the year"))
+}
+
+/// Returns `src/days.rs` with the module of `year` imported and added to
+/// the years, both in order.
+fn register_year(days: &str, year: u32) -> io::Result<String> {
+    let missing = || io::Error::other("unexpected layout of src/days.rs");
+    let days = insert_sorted(days, &format!("use crate::year{year};"), "use crate::year").ok_or_else(missing)?;
+    let entry = format!("    Year {{ year: year{year}::YEAR, days: year{year}::days }},");
+    insert_sorted(&days, &entry, "Year { year: ").ok_or_else(missing)
+}
+
+/// Returns `source` with the line `entry` inserted among the lines starting
+/// with `prefix`, ignoring indentation, in order.
+///
+/// # Returns
+/// The new source, or `None` if no line starts with `prefix`.
+fn insert_sorted(source: &str, entry: &str, prefix: &str) -> Option<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let last = lines.iter().rposition(|line| line.trim_start().starts_with(prefix))?;
+    let index = lines.iter()
+        .position(|line| line.trim_start().starts_with(prefix) && *line > entry)
+        .unwrap_or(last + 1);
+    lines.insert(index, entry);
+    Some(lines.join("\n") + "\n")
+}
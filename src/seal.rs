@@ -0,0 +1,197 @@
+//! Encrypted storage of the inputs under `data/`, which Advent of Code asks
+//! not to publish.
+//!
+//! A sealed input is stored as `data/exercise_NN.txt.sealed` instead of
+//! `data/exercise_NN.txt`, encrypted with AES-256-GCM under a key read from
+//! the `AOC_INPUT_KEY` environment variable, as 64 hexadecimal digits such
+//! as those printed by `openssl rand -hex 32`. Sealed inputs are decrypted
+//! transparently when read, and downloaded inputs are sealed when the key
+//! is set.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use crate::input::cache_path;
+
+/// The environment variable holding the key of the sealed inputs.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// The bytes starting every sealed input, also authenticated with it, so
+/// that other files are recognized and rejected.
+const MAGIC: &[u8] = b"aoc15-sealed-v1\n";
+
+/// The length of the nonce following [`MAGIC`].
+const NONCE_LEN: usize = 12;
+
+/// Returns the path of the sealed input of the provided day.
+///
+/// # Arguments
+/// * `year` - The year of the input.
+/// * `exercise` - The day of the input.
+pub fn sealed_path(year: u32, exercise: u32) -> PathBuf {
+    let mut path = cache_path(year, exercise).into_os_string();
+    path.push(".sealed");
+    PathBuf::from(path)
+}
+
+/// The key encrypting the sealed inputs.
+#[derive(Clone)]
+pub struct Key(Aes256Gcm);
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+impl Key {
+    /// Parses a key from its 64 hexadecimal digits.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if `hex` is not made of 64
+    /// hexadecimal digits.
+    pub fn from_hex(hex: &str) -> Result<Key, String> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err("expected a key of 64 hexadecimal digits".to_string());
+        }
+        let bytes: Vec<u8> = (0..hex.len()).step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap_or_else(|_| unreachable!("digits are checked")))
+            .collect();
+        Ok(Key(Aes256Gcm::new_from_slice(&bytes).unwrap_or_else(|_| unreachable!("the key has 32 bytes"))))
+    }
+
+    /// Reads the key from the `AOC_INPUT_KEY` environment variable.
+    ///
+    /// # Returns
+    /// The key, or `None` if the variable is not set.
+    ///
+    /// # Errors
+    /// Returns an [`io::ErrorKind::InvalidInput`] error if the variable does
+    /// not hold a valid key.
+    pub fn from_env() -> io::Result<Option<Key>> {
+        match env::var(KEY_VAR) {
+            Ok(hex) => Key::from_hex(&hex)
+                .map(Some)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{KEY_VAR}: {err}"))),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Reads the key from the `AOC_INPUT_KEY` environment variable, failing
+    /// if it is not set.
+    ///
+    /// # Errors
+    /// Returns an [`io::ErrorKind::NotFound`] error if the variable is not
+    /// set, or an error as described by [`Key::from_env`].
+    pub fn require() -> io::Result<Key> {
+        Key::from_env()?.ok_or_else(|| io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("sealed inputs need a key, set {KEY_VAR} to 64 hexadecimal digits"),
+        ))
+    }
+
+    /// Encrypts an input with a fresh random nonce.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::seal::Key;
+    /// let key = Key::from_hex(&"2015".repeat(16)).unwrap();
+    /// let sealed = key.seal(b"^v^v^v^v^v");
+    /// assert_eq!(key.unseal(&sealed).unwrap(), b"^v^v^v^v^v");
+    /// assert_ne!(key.seal(b"^v^v^v^v^v"), sealed);
+    ///
+    /// let other = Key::from_hex(&"1225".repeat(16)).unwrap();
+    /// assert!(other.unseal(&sealed).is_err());
+    /// ```
+    pub fn seal(&self, input: &[u8]) -> Vec<u8> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.0.encrypt(&nonce, Payload { msg: input, aad: MAGIC })
+            .unwrap_or_else(|_| panic!("input of {} bytes too long to seal", input.len()));
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypts an input sealed by [`Key::seal`].
+    ///
+    /// # Errors
+    /// Returns an [`io::ErrorKind::InvalidData`] error if `sealed` is not a
+    /// sealed input, was sealed with another key or was tampered with.
+    pub fn unseal(&self, sealed: &[u8]) -> io::Result<Vec<u8>> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let rest = sealed.strip_prefix(MAGIC).ok_or_else(|| invalid("not a sealed input"))?;
+        if rest.len() < NONCE_LEN {
+            return Err(invalid("truncated sealed input"));
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        self.0.decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: MAGIC })
+            .map_err(|_| invalid("cannot unseal input, wrong key or corrupted file"))
+    }
+}
+
+/// Reads and decrypts the sealed input of a day.
+///
+/// # Returns
+/// The input, or `None` if the day has no sealed input.
+///
+/// # Errors
+/// Returns an [`io::Error`] naming the file if the key is not set or
+/// invalid, or the file cannot be read or unsealed.
+pub fn read_sealed(year: u32, exercise: u32) -> io::Result<Option<String>> {
+    let path = sealed_path(year, exercise);
+    let sealed = match fs::read(&path) {
+        Ok(sealed) => sealed,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let named = |err: io::Error| io::Error::new(err.kind(), format!("{}: {err}", path.display()));
+    let input = Key::require().and_then(|key| key.unseal(&sealed)).map_err(named)?;
+    String::from_utf8(input)
+        .map(Some)
+        .map_err(|_| named(io::Error::new(io::ErrorKind::InvalidData, "sealed input is not UTF-8")))
+}
+
+/// Encrypts the plaintext input of a day in `data/`, replacing it by its
+/// sealed copy.
+///
+/// # Returns
+/// Whether the day had a plaintext input to seal.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the files cannot be read, written or removed.
+pub fn seal_file(key: &Key, year: u32, exercise: u32) -> io::Result<bool> {
+    let path = cache_path(year, exercise);
+    let input = match fs::read(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    fs::write(sealed_path(year, exercise), key.seal(&input))?;
+    fs::remove_file(&path)?;
+    Ok(true)
+}
+
+/// Decrypts the sealed input of a day in `data/`, replacing it by its
+/// plaintext copy.
+///
+/// # Returns
+/// Whether the day had a sealed input to unseal.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the files cannot be read, written or removed,
+/// or the input cannot be unsealed with `key`.
+pub fn unseal_file(key: &Key, year: u32, exercise: u32) -> io::Result<bool> {
+    let path = sealed_path(year, exercise);
+    let sealed = match fs::read(&path) {
+        Ok(sealed) => sealed,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    let input = key.unseal(&sealed).map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+    fs::write(cache_path(year, exercise), input)?;
+    fs::remove_file(&path)?;
+    Ok(true)
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use aoc2015::answer::Answer;
+use aoc2015::days;
+use aoc2015::input::read_exercise_input;
+use aoc2015::solution::{Part, Solution};
+use toml::{Table, Value};
+use crate::output::Style;
+use crate::runner::{check_examples, verify};
+
+/// The outcome of one check of the self-test.
+enum Verdict {
+    /// The check passed, with what was checked.
+    Pass(String),
+    /// The check failed, with why.
+    Fail(String),
+    /// The check could not run, with why.
+    Skip(String),
+}
+
+/// Counts the [`Verdict`]s of the self-test as they are printed.
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+impl Tally {
+    /// Prints the verdict of the check named `check` of a day and counts it.
+    fn record(&mut self, day: u32, check: &str, verdict: Verdict) {
+        let name = format!("ex{day:02} {check}");
+        match verdict {
+            Verdict::Pass(what) => {
+                self.passed += 1;
+                println!("{name:<24} {} {what}", Style::Green.paint("pass"));
+            }
+            Verdict::Fail(why) => {
+                self.failed += 1;
+                println!("{name:<24} {} {why}", Style::Red.paint("FAIL"));
+            }
+            Verdict::Skip(why) => {
+                self.skipped += 1;
+                println!("{name:<24} {}", Style::Dim.paint(format!("skip {why}")));
+            }
+        }
+    }
+}
+
+/// Reads the golden answers of a TOML file mapping each day to the answers
+/// of its parts, as `tests/answers.rs` does:
+///
+/// ```toml
+/// [day01]
+/// a = "280"
+/// b = "1797"
+/// ```
+///
+/// # Returns
+/// The answers of each part of each day, none if the file does not exist.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the file cannot be read or holds anything
+/// else than answers.
+fn read_answers(path: &Path) -> io::Result<HashMap<(u32, Part), Answer>> {
+    let table: Table = match fs::read_to_string(path) {
+        Ok(content) => content.parse().map_err(|err: toml::de::Error| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(err),
+    };
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut answers = HashMap::new();
+    for (key, parts) in &table {
+        let day = key.strip_prefix("day").and_then(|day| day.parse().ok()).ok_or_else(|| invalid(format!("unknown key {key}, expected dayNN")))?;
+        let Value::Table(parts) = parts else { return Err(invalid(format!("{key} is not a table"))) };
+        for (part, answer) in parts {
+            let part: Part = part.parse().map_err(|err| invalid(format!("{key}: {err}")))?;
+            let Value::String(answer) = answer else { return Err(invalid(format!("{key}.{part} is not a string"))) };
+            answers.insert((day, part), answer.parse().map_err(|err| invalid(format!("{key}.{part}: {err}")))?);
+        }
+    }
+    Ok(answers)
+}
+
+/// Checks the examples of a [`Solution`], with the solution itself and with
+/// each of its alternate implementations.
+fn examples(solution: &dyn Solution) -> Verdict {
+    let count = match check_examples(solution, &[Part::A, Part::B]) {
+        Ok(0) => return Verdict::Skip(format!("no examples, see the doctests of aoc2015::year{}::ex{:02}", solution.year(), solution.day())),
+        Ok(count) => count,
+        Err(err) => return Verdict::Fail(err.to_string()),
+    };
+    for implementation in solution.implementations() {
+        for example in solution.examples().iter().filter(|example| example.part == implementation.part) {
+            if let Err(message) = example.check_implementation(implementation) {
+                return Verdict::Fail(message);
+            }
+        }
+    }
+    Verdict::Pass(format!("{count} example{}", if count == 1 { "" } else { "s" }))
+}
+
+/// Runs the checks of the self-test on one day: its examples, then, on its
+/// input, whether its alternate implementations agree and whether it finds
+/// the golden answers.
+fn check_day(solution: &dyn Solution, answers: &HashMap<(u32, Part), Answer>, tally: &mut Tally) {
+    let day = solution.day();
+    tally.record(day, "examples", examples(solution));
+    let parts: Vec<Part> = [Part::A, Part::B].into_iter().filter(|&part| solution.implemented().contains(part)).collect();
+    let input = match read_exercise_input(solution.year(), day) {
+        Ok(input) => input,
+        Err(err) => {
+            for part in parts {
+                tally.record(day, &format!("part {part}"), Verdict::Skip(err.to_string()));
+            }
+            return;
+        }
+    };
+    for part in parts {
+        let answer = match solution.solve(part, &input) {
+            Ok(answer) => answer,
+            Err(err) => {
+                tally.record(day, &format!("part {part}"), Verdict::Fail(err.to_string()));
+                continue;
+            }
+        };
+        let implementations = solution.implementations().iter().filter(|implementation| implementation.part == part).count();
+        if implementations > 0 {
+            let verdict = match verify(solution, part, &input, None, &answer) {
+                Ok(()) => Verdict::Pass(format!("{implementations} alternate implementation{} agree", if implementations == 1 { "" } else { "s" })),
+                Err(err) => Verdict::Fail(err.to_string()),
+            };
+            tally.record(day, &format!("part {part} impls"), verdict);
+        }
+        let verdict = match answers.get(&(day, part)) {
+            Some(expected) if *expected == answer => Verdict::Pass(format!("found {answer}")),
+            Some(expected) => Verdict::Fail(format!("found {answer}, expected {expected}")),
+            None => Verdict::Skip(format!("found {answer}, no golden answer")),
+        };
+        tally.record(day, &format!("part {part} golden"), verdict);
+    }
+}
+
+/// Checks every implemented day without `cargo test`, to sanity check a
+/// build on another machine: the examples of the puzzle texts, whether the
+/// alternate implementations of each part agree on the real input, and
+/// whether the answers match the golden answers of `answers`, printing a
+/// verdict per check and a summary.
+///
+/// Days whose input is not cached and cannot be downloaded only have their
+/// examples checked.
+///
+/// # Arguments
+/// * `year` - The year whose days to check.
+/// * `answers` - The TOML file of golden answers, which may not exist.
+///
+/// # Returns
+/// Whether no check failed.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the golden answers exist but cannot be read.
+pub fn selftest(year: u32, answers: &Path) -> io::Result<bool> {
+    let answers = read_answers(answers)?;
+    let mut tally = Tally::default();
+    for solution in days::registry(year) {
+        check_day(solution.as_ref(), &answers, &mut tally);
+    }
+    let Tally { passed, failed, skipped } = tally;
+    let failed_text = format!("{failed} failed");
+    let failed_text = if failed > 0 { Style::Red.paint(failed_text).to_string() } else { failed_text };
+    println!("selftest: {passed} passed, {failed_text}, {skipped} skipped");
+    Ok(failed == 0)
+}
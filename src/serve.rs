@@ -0,0 +1,174 @@
+//! A small HTTP server solving the puzzles of a year for other tools, with
+//! a single route:
+//!
+//! ```text
+//! POST /solve/{day}/{part}
+//! ```
+//!
+//! The body of the request is the raw input, and the response a JSON
+//! object with the `answer` and the `duration_ms` spent finding it, or an
+//! `error` with a 4xx or 5xx status:
+//!
+//! ```text
+//! $ curl --data-binary @data/exercise_01.txt localhost:2015/solve/1/b
+//! {"answer":1797,"duration_ms":0.012}
+//! ```
+//!
+//! HTTP/1.1 is handled by hand, one request per connection, which is all
+//! local tooling needs.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+use aoc2015::days;
+use aoc2015::error::AocError;
+use aoc2015::solution::Part;
+use aoc2015::util::checkpoint::NoCheckpoint;
+use serde_json::{json, Value};
+use crate::runner::{answer_value, run_part, RunOptions};
+
+/// The largest input accepted, far above that of any puzzle.
+const MAX_BODY: usize = 1 << 20;
+
+/// The delay after which a client that stops sending its request is
+/// disconnected.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An HTTP request, reduced to what the route needs.
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// An HTTP response with a JSON body.
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    /// A response carrying an error message.
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response { status, body: json!({ "error": message.into() }) }
+    }
+
+    /// Writes the response and closes the connection.
+    fn write(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            422 => "Unprocessable Content",
+            _ => "Internal Server Error",
+        };
+        let body = self.body.to_string();
+        let allow = if self.status == 405 { "Allow: POST\r\n" } else { "" };
+        write!(stream, "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{allow}Connection: close\r\n\r\n{body}", self.status, body.len())?;
+        stream.flush()
+    }
+}
+
+/// Reads a request, its body being delimited by its `Content-Length`.
+///
+/// # Errors
+/// Returns the [`Response`] rejecting a malformed request, or an
+/// [`io::Error`] if the connection fails.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (words.next(), words.next(), words.next()) else {
+        return Ok(Err(Response::error(400, "malformed request line")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let mut length = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 { return Ok(Err(Response::error(400, "truncated headers"))); }
+        let header = line.trim_end();
+        if header.is_empty() { break; }
+        let Some((name, value)) = header.split_once(':') else { return Ok(Err(Response::error(400, format!("malformed header '{header}'")))) };
+        if name.eq_ignore_ascii_case("content-length") {
+            match value.trim().parse::<usize>() {
+                Ok(value) if value > MAX_BODY => return Ok(Err(Response::error(413, format!("inputs are limited to {MAX_BODY} bytes")))),
+                Ok(value) => length = Some(value),
+                Err(_) => return Ok(Err(Response::error(400, format!("invalid content length '{}'", value.trim())))),
+            }
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Ok(Err(Response::error(411, "chunked bodies are not supported, send a content length")));
+        }
+    }
+    let mut body = vec![0; length.unwrap_or(0)];
+    reader.read_exact(&mut body)?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+/// Solves a part of a day of `year` on the body of a request for
+/// `/solve/{day}/{part}`.
+fn route(year: u32, request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let ["solve", day, part] = segments[..] else { return Response::error(404, format!("no route for {}", request.path)) };
+    if request.method != "POST" { return Response::error(405, format!("{} expects POST, not {}", request.path, request.method)); }
+    let Some(solution) = day.parse().ok().and_then(|day| days::find(year, day)) else {
+        return Response::error(404, format!("day {day} of {year} is not implemented"));
+    };
+    let part: Part = match part.parse() {
+        Ok(part) => part,
+        Err(err) => return Response::error(404, err),
+    };
+    let Ok(input) = std::str::from_utf8(&request.body) else { return Response::error(400, "the input is not valid UTF-8") };
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_part(solution.as_ref(), part, input.trim_end(), None, RunOptions::default(), &NoCheckpoint)));
+    let Ok(outcome) = outcome else { return Response::error(500, format!("day {day} panicked")) };
+    match outcome.answer {
+        Ok(answer) => Response { status: 200, body: json!({ "answer": answer_value(&answer), "duration_ms": outcome.duration.as_secs_f64() * 1000.0 }) },
+        Err(err @ AocError::Parse { .. }) => Response::error(422, err.to_string()),
+        Err(err) => Response::error(500, err.to_string()),
+    }
+}
+
+/// Answers the request of a connection.
+fn handle(year: u32, mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&mut BufReader::new(&stream))? {
+        Ok(request) => {
+            let response = route(year, &request);
+            tracing::info!(method = request.method, path = request.path, status = response.status, "served a request");
+            response
+        }
+        Err(response) => response,
+    };
+    response.write(&mut stream)
+}
+
+/// Serves the solutions of the days of a year over HTTP until interrupted,
+/// answering each connection on its own thread.
+///
+/// # Arguments
+/// * `year` - The year whose days to serve.
+/// * `address` - The address to listen on, such as `127.0.0.1:2015`.
+///
+/// # Errors
+/// Returns an [`io::Error`] if the address cannot be listened on.
+pub fn serve(year: u32, address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    eprintln!("serving the days of {year} on http://{}/solve/{{day}}/{{part}}, press Ctrl-C to stop", listener.local_addr()?);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || {
+                    if let Err(err) = handle(year, stream) {
+                        tracing::warn!("unable to answer a request: {err}");
+                    }
+                });
+            }
+            Err(err) => tracing::warn!("unable to accept a connection: {err}"),
+        }
+    }
+    Ok(())
+}
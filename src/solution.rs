@@ -0,0 +1,375 @@
+use std::io::{BufRead, Write};
+use crate::answer::Answer;
+use crate::config::PuzzleConfig;
+use crate::context::Context;
+use crate::error::AocError;
+use crate::util::grid::Grid;
+
+/// A solution to one day of Advent of Code.
+///
+/// Each day module provides a `Solver` implementing this trait, and the
+/// [`registry`](crate::days::registry) collects them so the runner can
+/// execute any day generically. Solutions are shared between the worker
+/// threads of parallel runs, hence the [`Sync`] bound.
+pub trait Solution: Sync {
+    /// The year of the puzzle this [`Solution`] solves.
+    fn year(&self) -> u32;
+
+    /// The day of December this [`Solution`] solves.
+    fn day(&self) -> u32;
+
+    /// The title of the puzzle, such as `Probably a Fire Hazard`.
+    fn title(&self) -> &str;
+
+    /// The address of the puzzle on adventofcode.com.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::solution::Solution;
+    /// assert_eq!(aoc2015::year2015::ex06::Solver.url(), "https://adventofcode.com/2015/day/6");
+    /// ```
+    fn url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year(), self.day())
+    }
+
+    /// The parts of the puzzle this [`Solution`] solves, both by default.
+    ///
+    /// The runner skips the other parts instead of running them, so that a
+    /// day can be registered before it is complete.
+    fn implemented(&self) -> Parts {
+        Parts::BOTH
+    }
+
+    /// Solves the first part of the puzzle.
+    ///
+    /// # Arguments
+    /// * `input` - The puzzle input, without trailing whitespace.
+    ///
+    /// # Returns
+    /// The answer to the first part, as an [`Answer`].
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the input is malformed or has no answer.
+    fn part_a(&self, input: &str) -> Result<Answer, AocError>;
+
+    /// Solves the second part of the puzzle.
+    ///
+    /// # Arguments
+    /// * `input` - The puzzle input, without trailing whitespace.
+    ///
+    /// # Returns
+    /// The answer to the second part, as an [`Answer`].
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the input is malformed or has no answer.
+    fn part_b(&self, input: &str) -> Result<Answer, AocError>;
+
+    /// The examples given in the puzzle text, used to sanity check this
+    /// [`Solution`] before running it on the real input.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    /// The parameters the [`Solution::examples`] of `part` are solved with:
+    /// those of the puzzle, unless its text changes them for the examples,
+    /// such as the shorter race of day 14 or the steps of day 18, which
+    /// differ between the parts.
+    fn example_config(&self, part: Part) -> PuzzleConfig {
+        let _ = part;
+        PuzzleConfig::PUZZLE
+    }
+
+    /// The alternate [`Implementation`]s of the parts of the puzzle, which
+    /// can be selected by name instead of [`Solution::part_a`] and
+    /// [`Solution::part_b`] and must agree with them.
+    fn implementations(&self) -> &'static [Implementation] {
+        &[]
+    }
+
+    /// Renders a picture of the provided [`Part`] of the puzzle, as gray
+    /// levels from 0 for black to 255 for white.
+    ///
+    /// # Returns
+    /// The picture, or `None` if this [`Solution`] has nothing to render,
+    /// which is the default.
+    fn render(&self, part: Part, input: &str) -> Option<Result<Grid<u8>, AocError>> {
+        let _ = (part, input);
+        None
+    }
+
+    /// Parses `input` as the solvers of both parts would, without solving
+    /// the puzzle, to report a malformed input before a long run.
+    ///
+    /// # Returns
+    /// The result of parsing, or `None` if this [`Solution`] has no parser
+    /// to check, which is the default.
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> {
+        let _ = input;
+        None
+    }
+
+    /// Solves the provided [`Part`] of the puzzle.
+    ///
+    /// # Arguments
+    /// * `part` - The [`Part`] to solve.
+    /// * `input` - The puzzle input, without trailing whitespace.
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, AocError> {
+        match part {
+            Part::A => self.part_a(input),
+            Part::B => self.part_b(input),
+        }
+    }
+
+    /// Solves the provided [`Part`] of the puzzle, reading the input from
+    /// `reader`.
+    ///
+    /// Line-oriented solutions override this to process the input one line
+    /// at a time, so that inputs too large to fit in memory can be solved.
+    /// The default implementation reads the whole input, trims its trailing
+    /// whitespace and calls [`Solution::solve`].
+    ///
+    /// # Arguments
+    /// * `part` - The [`Part`] to solve.
+    /// * `reader` - The reader of the puzzle input.
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the input is malformed, cannot be read or
+    /// has no answer.
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        self.solve(part, input.trim_end())
+    }
+
+    /// Solves the provided [`Part`] of the puzzle, writing human-readable
+    /// facts about how the answer was found to `out`, such as the best
+    /// route of day 9.
+    ///
+    /// Only solutions with something to explain write to `out`: the
+    /// default implementation calls [`Solution::solve_in`] with `context`.
+    ///
+    /// # Arguments
+    /// * `part` - The [`Part`] to solve.
+    /// * `input` - The puzzle input, without trailing whitespace.
+    /// * `context` - The [`Context`] of the run.
+    /// * `out` - Receives the explanation, one fact per line.
+    ///
+    /// # Errors
+    /// Returns an [`AocError`] if the input is malformed or has no answer,
+    /// or the explanation cannot be written.
+    fn solve_explained(&self, part: Part, input: &str, context: &Context, out: &mut dyn Write) -> Result<Answer, AocError> {
+        let _ = out;
+        self.solve_in(part, input, context)
+    }
+
+    /// Solves the provided [`Part`] of the puzzle in a [`Context`], as the
+    /// runner does.
+    ///
+    /// Only some solutions use the context: long-running ones report their
+    /// progress to its sink, those with a long iterative search save
+    /// checkpoints to its store, randomized ones draw from its generator
+    /// and those with parameters read its [`PuzzleConfig`]. The default
+    /// implementation ignores it and calls [`Solution::solve`].
+    ///
+    /// # Arguments
+    /// * `part` - The [`Part`] to solve.
+    /// * `input` - The puzzle input, without trailing whitespace.
+    /// * `context` - The [`Context`] of the run.
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let _ = context;
+        self.solve(part, input)
+    }
+}
+
+/// An example input from the puzzle text, along with its expected answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The part the example illustrates.
+    pub part: Part,
+    /// The example input.
+    pub input: &'static str,
+    /// The expected answer, as it would be displayed.
+    pub answer: &'static str,
+}
+
+impl Example {
+    /// Runs a [`Solution`] on this example, with its
+    /// [`Solution::example_config`].
+    ///
+    /// # Errors
+    /// Returns a description of the mismatch if the solution fails or
+    /// finds another answer.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex01::Solver;
+    /// use aoc2015::solution::{Example, Part};
+    /// let example = Example { part: Part::A, input: "(()", answer: "1" };
+    /// assert!(example.check(&Solver).is_ok());
+    /// let example = Example { part: Part::A, input: "(()", answer: "2" };
+    /// assert!(example.check(&Solver).is_err());
+    /// ```
+    pub fn check(&self, solution: &dyn Solution) -> Result<(), String> {
+        let config = solution.example_config(self.part);
+        let context = Context { config: &config, ..Context::default() };
+        self.compare(solution.solve_in(self.part, self.input, &context))
+    }
+
+    /// Runs a [`Solution`] on this example through
+    /// [`Solution::solve_stream`].
+    ///
+    /// # Errors
+    /// Returns a description of the mismatch if the solution fails or
+    /// finds another answer.
+    pub fn check_stream(&self, solution: &dyn Solution) -> Result<(), String> {
+        self.compare(solution.solve_stream(self.part, &mut self.input.as_bytes()))
+            .map_err(|message| format!("streaming {message}"))
+    }
+
+    /// Runs an alternate [`Implementation`] on this example.
+    ///
+    /// # Errors
+    /// Returns a description of the mismatch if the implementation fails
+    /// or finds another answer.
+    ///
+    /// # Panics
+    /// Panics if the implementation solves another part than the example.
+    pub fn check_implementation(&self, implementation: &Implementation) -> Result<(), String> {
+        assert_eq!(implementation.part, self.part, "{} solves another part", implementation.name);
+        self.compare((implementation.solve)(self.input))
+            .map_err(|message| format!("{}: {message}", implementation.name))
+    }
+
+    /// Compares the result of solving this example to the expected answer.
+    fn compare(&self, result: Result<Answer, AocError>) -> Result<(), String> {
+        let expected: Answer = self.answer.parse().unwrap();
+        match result {
+            Ok(answer) if answer == expected => Ok(()),
+            Ok(answer) => Err(format!("part {} on {:?}: expected {expected}, found {answer}", self.part, self.input)),
+            Err(err) => Err(format!("part {} on {:?}: {err}", self.part, self.input)),
+        }
+    }
+}
+
+/// A named implementation of one part of a puzzle, solving it
+/// differently from the [`Solution`] itself.
+#[derive(Clone, Copy)]
+pub struct Implementation {
+    /// The name selecting the implementation.
+    pub name: &'static str,
+    /// The part it solves.
+    pub part: Part,
+    /// Solves the part on an input.
+    pub solve: fn(&str) -> Result<Answer, AocError>,
+}
+
+impl std::fmt::Debug for Implementation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Implementation").field("name", &self.name).field("part", &self.part).finish()
+    }
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    /// Returns the parts to run for an optional [`Part`] selection.
+    ///
+    /// # Arguments
+    /// * `part` - The selected part, or `None` to select both.
+    pub fn selection(part: Option<Part>) -> Vec<Part> {
+        match part {
+            Some(part) => vec![part],
+            None => vec![Part::A, Part::B],
+        }
+    }
+}
+
+/// A set of [`Part`]s, as a bitmask.
+///
+/// # Examples
+/// ```
+/// use aoc2015::solution::{Part, Parts};
+/// let parts = Parts::NONE | Part::A;
+/// assert!(parts.contains(Part::A) && !parts.contains(Part::B));
+/// assert_eq!(parts.len(), 1);
+/// assert_eq!(parts | Part::B, Parts::BOTH);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Parts(u8);
+
+impl Parts {
+    /// No part.
+    pub const NONE: Parts = Parts(0);
+    /// The first part only.
+    pub const A: Parts = Parts(1);
+    /// The second part only.
+    pub const B: Parts = Parts(2);
+    /// Both parts.
+    pub const BOTH: Parts = Parts(3);
+
+    /// Returns whether this set holds `part`.
+    pub fn contains(self, part: Part) -> bool {
+        self.0 & Parts::from(part).0 != 0
+    }
+
+    /// Returns the number of parts of this set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns whether this set holds no part.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<Part> for Parts {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::A => Parts::A,
+            Part::B => Parts::B,
+        }
+    }
+}
+
+impl std::ops::BitOr for Parts {
+    type Output = Parts;
+
+    fn bitor(self, other: Parts) -> Parts {
+        Parts(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOr<Part> for Parts {
+    type Output = Parts;
+
+    fn bitor(self, part: Part) -> Parts {
+        self | Parts::from(part)
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" => Ok(Part::A),
+            "b" | "B" => Ok(Part::B),
+            _ => Err(format!("invalid part '{s}', expected 'a' or 'b'")),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::input::{require_session_token, year_dir, USER_AGENT};
+use crate::solution::Part;
+
+/// The verdict of adventofcode.com on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer is right.
+    Correct,
+    /// The answer is wrong and too high.
+    TooHigh,
+    /// The answer is wrong and too low.
+    TooLow,
+    /// The answer is wrong.
+    Incorrect,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, along with the time left to wait.
+    RateLimited(String),
+}
+
+impl Verdict {
+    /// Extracts the [`Verdict`] from the HTML page answering a submission.
+    ///
+    /// # Returns
+    /// The [`Verdict`], or `None` if the page is not recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::submit::Verdict;
+    /// let page = "<article><p>That's not the right answer; your answer is too high.</p></article>";
+    /// assert_eq!(Verdict::parse(page), Some(Verdict::TooHigh));
+    /// let page = "<article><p>You gave an answer too recently. You have 41s left to wait.</p></article>";
+    /// assert_eq!(Verdict::parse(page), Some(Verdict::RateLimited("41s".to_string())));
+    /// ```
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("too high") {
+                Verdict::TooHigh
+            } else if page.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            })
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page.split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("some time", |(wait, _)| wait);
+            Some(Verdict::RateLimited(wait.to_string()))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the token identifying this verdict in the submission cache,
+    /// or `None` if it is only temporary and must not be cached.
+    fn token(&self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Incorrect => Some("incorrect"),
+            Verdict::AlreadySolved => Some("already-solved"),
+            Verdict::RateLimited(_) => None,
+        }
+    }
+
+    /// Parses a token of the submission cache.
+    fn from_token(token: &str) -> Option<Verdict> {
+        match token {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "incorrect" => Some(Verdict::Incorrect),
+            "already-solved" => Some(Verdict::AlreadySolved),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, {wait} left to wait"),
+        }
+    }
+}
+
+/// A submission recorded in the local cache.
+struct Submission {
+    day: u32,
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Returns the path of the cache of past submissions of a year.
+fn cache_path(year: u32) -> PathBuf {
+    year_dir("data", year).join("submissions.txt")
+}
+
+/// Reads the past submissions of a year from the cache, one tab-separated
+/// `day part answer verdict` line each, skipping unreadable lines.
+fn read_cache(year: u32) -> io::Result<Vec<Submission>> {
+    let contents = match fs::read_to_string(cache_path(year)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(contents.lines()
+        .filter_map(|line| {
+            let [day, part, answer, verdict] = line.split('\t').collect::<Vec<_>>()[..] else { return None };
+            Some(Submission {
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                answer: answer.to_string(),
+                verdict: Verdict::from_token(verdict)?,
+            })
+        })
+        .collect())
+}
+
+/// Appends a submission to the cache.
+fn record(year: u32, day: u32, part: Part, answer: &str, token: &str) -> io::Result<()> {
+    let path = cache_path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{day}\t{part}\t{answer}\t{token}")
+}
+
+/// Returns the verdict already known for an answer, if it was submitted
+/// before or the part is already solved.
+fn known_verdict(year: u32, day: u32, part: Part, answer: &str) -> io::Result<Option<Verdict>> {
+    let submissions: Vec<_> = read_cache(year)?.into_iter().filter(|s| s.day == day && s.part == part).collect();
+    if let Some(submission) = submissions.iter().find(|s| s.answer == answer) {
+        return Ok(Some(submission.verdict.clone()));
+    }
+    Ok(submissions.iter().any(|s| s.verdict == Verdict::Correct).then_some(Verdict::AlreadySolved))
+}
+
+/// Submits the answer of a part to adventofcode.com.
+///
+/// Every verdict except rate limiting is recorded in `data/submissions.txt`,
+/// or `data/YEAR/submissions.txt` for the years after 2015, and an answer
+/// already submitted, or to a part already solved, is not posted again: its
+/// known verdict is returned instead.
+///
+/// # Arguments
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+/// * `part` - The part the answer solves.
+/// * `answer` - The answer to submit.
+///
+/// # Returns
+/// The [`Verdict`] on the answer, and whether it comes from the cache.
+///
+/// # Errors
+/// Returns an [`AocError::Io`] if no session token is configured, the
+/// request fails, the response is not recognized, or the cache cannot be
+/// accessed.
+pub fn submit(year: u32, day: u32, part: Part, answer: &Answer) -> Result<(Verdict, bool), AocError> {
+    let answer = answer.to_string();
+    if let Some(verdict) = known_verdict(year, day, part, &answer)? {
+        return Ok((verdict, true));
+    }
+    let token = require_session_token()?;
+    let level = match part {
+        Part::A => "1",
+        Part::B => "2",
+    };
+    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+    let page = ureq::post(&url)
+        .set("Cookie", &format!("session={token}"))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", level), ("answer", &answer)])
+        .map_err(io::Error::other)?
+        .into_string()?;
+    let verdict = Verdict::parse(&page)
+        .ok_or_else(|| io::Error::other("unrecognized response from adventofcode.com"))?;
+    if let Some(token) = verdict.token() {
+        record(year, day, part, &answer, token)?;
+    }
+    Ok((verdict, false))
+}
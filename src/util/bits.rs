@@ -0,0 +1,198 @@
+//! Kernels over rows of bit-packed lights, used by the bitfield grid of
+//! day 6 and the animation of day 18.
+//!
+//! Every kernel has a portable scalar version. With the `simd` feature, on
+//! x86-64 processors supporting AVX2, detected at runtime, the kernels run
+//! explicit AVX2 implementations processing 256 bits at a time instead.
+
+/// Returns the number of bits set in `words`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::bits::popcount;
+/// assert_eq!(popcount(&[0b1011, u128::MAX, 0]), 131);
+/// ```
+pub fn popcount(words: &[u128]) -> u32 {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the running processor.
+        return unsafe { avx2::popcount(words) };
+    }
+    popcount_scalar(words)
+}
+
+/// Returns the number of bits set in `words`, one word at a time.
+pub fn popcount_scalar(words: &[u128]) -> u32 {
+    words.iter().map(|word| word.count_ones()).sum()
+}
+
+/// Computes the next state of a row of lights in Conway's Game of Life.
+///
+/// A light stays on if 2 or 3 of its 8 neighbors are on, and turns on if
+/// exactly 3 are on. Lights beyond the ends of the rows count as off, and
+/// the caller must clear any bit past the last light of `next`.
+///
+/// # Arguments
+/// * `above` - The row above, all off for the first row.
+/// * `row` - The row to advance.
+/// * `below` - The row below, all off for the last row.
+/// * `next` - Receives the next state of `row`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::bits::life_row;
+/// let mut next = [0];
+/// life_row(&[0b010], &[0b010], &[0b010], &mut next);
+/// assert_eq!(next, [0b111]);
+/// ```
+pub fn life_row(above: &[u128], row: &[u128], below: &[u128], next: &mut [u128]) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the running processor.
+        unsafe { avx2::life_row(above, row, below, next) };
+        return;
+    }
+    life_row_scalar(above, row, below, next)
+}
+
+/// Computes the next state of a row of lights in Conway's Game of Life,
+/// one word at a time. See [`life_row`].
+///
+/// Neighbors are counted 128 lights at a time: for each word, the 8
+/// neighbor bitfields are obtained by shifting the words of the rows
+/// above, below and of the word itself, and are added together in a
+/// bit-sliced counter where `s0`, `s1` and `s2` hold bits 0, 1 and 2 of
+/// the count of every light.
+/// A count of 8 wraps around to 0, which the rules treat the same way.
+pub fn life_row_scalar(above: &[u128], row: &[u128], below: &[u128], next: &mut [u128]) {
+    for x_index in 0..row.len() {
+        let (mut s0, mut s1, mut s2) = (0u128, 0u128, 0u128);
+        let mut add = |bits: u128| {
+            let carry0 = s0 & bits;
+            s0 ^= bits;
+            let carry1 = s1 & carry0;
+            s1 ^= carry0;
+            s2 ^= carry1;
+        };
+        for neighbors in [above, row, below] {
+            add(west(neighbors, x_index));
+            add(east(neighbors, x_index));
+        }
+        add(above[x_index]);
+        add(below[x_index]);
+        next[x_index] = !s2 & s1 & (s0 | row[x_index]);
+    }
+}
+
+/// Returns the word of `row` at `x_index` shifted so that each light
+/// holds the state of its west neighbor.
+fn west(row: &[u128], x_index: usize) -> u128 {
+    let carry = if x_index > 0 { row[x_index - 1] >> 127 } else { 0 };
+    row[x_index] << 1 | carry
+}
+
+/// Returns the word of `row` at `x_index` shifted so that each light
+/// holds the state of its east neighbor.
+fn east(row: &[u128], x_index: usize) -> u128 {
+    let carry = if x_index + 1 < row.len() { row[x_index + 1] << 127 } else { 0 };
+    row[x_index] >> 1 | carry
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    /// Reinterprets 128-bit words as twice as many 64-bit words, the low
+    /// half of each word first as x86-64 is little-endian.
+    fn halves(words: &[u128]) -> &[u64] {
+        // SAFETY: a u128 is exactly two u64 and is at least as aligned.
+        unsafe { std::slice::from_raw_parts(words.as_ptr().cast(), words.len() * 2) }
+    }
+
+    /// Counts the bits set in `words` with the nibble lookup table method
+    /// of Muła, Kurz and Lemire: `vpshufb` looks up the popcount of every
+    /// nibble and `vpsadbw` sums the counts of each 64-bit lane.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn popcount(words: &[u128]) -> u32 {
+        let lookup = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+        );
+        let low_nibbles = _mm256_set1_epi8(0x0f);
+        let mut total = _mm256_setzero_si256();
+        let chunks = words.chunks_exact(2);
+        let remainder = chunks.remainder();
+        for chunk in chunks {
+            let bits = _mm256_loadu_si256(chunk.as_ptr().cast());
+            let low = _mm256_and_si256(bits, low_nibbles);
+            let high = _mm256_and_si256(_mm256_srli_epi16(bits, 4), low_nibbles);
+            let counts = _mm256_add_epi8(_mm256_shuffle_epi8(lookup, low), _mm256_shuffle_epi8(lookup, high));
+            total = _mm256_add_epi64(total, _mm256_sad_epu8(counts, _mm256_setzero_si256()));
+        }
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), total);
+        lanes.iter().sum::<u64>() as u32 + super::popcount_scalar(remainder)
+    }
+
+    /// Computes the next state of a row of lights 256 at a time, on the
+    /// 64-bit halves of the words. See [`super::life_row_scalar`].
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn life_row(above: &[u128], row: &[u128], below: &[u128], next: &mut [u128]) {
+        let rows = [halves(above), halves(row), halves(below)];
+        let len = rows[1].len();
+        // SAFETY: `next` has the same length as `row`.
+        let next: &mut [u64] = std::slice::from_raw_parts_mut(next.as_mut_ptr().cast(), len);
+
+        // The vector loop needs the words on both sides of every lane.
+        let mut x = 1;
+        while x + 4 < len {
+            let (mut s0, mut s1, mut s2) = (_mm256_setzero_si256(), _mm256_setzero_si256(), _mm256_setzero_si256());
+            let mut add = |bits: __m256i| {
+                let carry0 = _mm256_and_si256(s0, bits);
+                s0 = _mm256_xor_si256(s0, bits);
+                let carry1 = _mm256_and_si256(s1, carry0);
+                s1 = _mm256_xor_si256(s1, carry0);
+                s2 = _mm256_xor_si256(s2, carry1);
+            };
+            let mut center = [_mm256_setzero_si256(); 3];
+            for (index, words) in rows.iter().enumerate() {
+                let here = _mm256_loadu_si256(words[x..].as_ptr().cast());
+                let before = _mm256_loadu_si256(words[x - 1..].as_ptr().cast());
+                let after = _mm256_loadu_si256(words[x + 1..].as_ptr().cast());
+                add(_mm256_or_si256(_mm256_slli_epi64(here, 1), _mm256_srli_epi64(before, 63)));
+                add(_mm256_or_si256(_mm256_srli_epi64(here, 1), _mm256_slli_epi64(after, 63)));
+                center[index] = here;
+            }
+            add(center[0]);
+            add(center[2]);
+            let alive = _mm256_and_si256(s1, _mm256_or_si256(s0, center[1]));
+            let alive = _mm256_andnot_si256(s2, alive);
+            _mm256_storeu_si256(next[x..].as_mut_ptr().cast(), alive);
+            x += 4;
+        }
+        for x in (0..1.min(len)).chain(x..len) {
+            next[x] = life_word(&rows, x);
+        }
+    }
+
+    /// Computes the next state of the 64-bit word `x` of the middle row.
+    fn life_word(rows: &[&[u64]; 3], x: usize) -> u64 {
+        let (mut s0, mut s1, mut s2) = (0u64, 0u64, 0u64);
+        let mut add = |bits: u64| {
+            let carry0 = s0 & bits;
+            s0 ^= bits;
+            let carry1 = s1 & carry0;
+            s1 ^= carry0;
+            s2 ^= carry1;
+        };
+        for words in rows {
+            let before = if x > 0 { words[x - 1] >> 63 } else { 0 };
+            let after = if x + 1 < words.len() { words[x + 1] << 63 } else { 0 };
+            add(words[x] << 1 | before);
+            add(words[x] >> 1 | after);
+        }
+        add(rows[0][x]);
+        add(rows[2][x]);
+        !s2 & s1 & (s0 | rows[1][x])
+    }
+}
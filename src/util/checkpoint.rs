@@ -0,0 +1,152 @@
+//! Checkpoints of long searches, so that an interrupted search resumes
+//! where it stopped instead of starting over.
+//!
+//! A [`Checkpoint`] holds named counters, such as the lowest nonce not yet
+//! searched, and is serialized as text: a `checkpoint v1` header followed
+//! by one `name value` line per counter, in name order.
+//!
+//! ```text
+//! checkpoint v1
+//! nonce 1048576
+//! zeroes 6
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use crate::error::AocError;
+
+/// The first line of a serialized [`Checkpoint`].
+const HEADER: &str = "checkpoint v1";
+
+/// The progress of a search, as named counters.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::checkpoint::Checkpoint;
+/// let mut checkpoint = Checkpoint::new();
+/// checkpoint.set("nonce", 1048576);
+/// checkpoint.set("zeroes", 6);
+/// let text = checkpoint.encode();
+/// assert_eq!(text, "checkpoint v1\nnonce 1048576\nzeroes 6\n");
+/// assert_eq!(Checkpoint::decode(&text).unwrap(), checkpoint);
+/// assert_eq!(checkpoint.get("nonce"), Some(1048576));
+/// assert_eq!(checkpoint.get("house"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    counters: BTreeMap<String, u64>,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint without counters.
+    pub fn new() -> Self {
+        Checkpoint::default()
+    }
+
+    /// Returns the value of the counter `name`, if set.
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.counters.get(name).copied()
+    }
+
+    /// Sets the counter `name`, which must be a single word, to `value`.
+    pub fn set(&mut self, name: &str, value: u64) {
+        self.counters.insert(name.to_string(), value);
+    }
+
+    /// Serializes the checkpoint as text.
+    pub fn encode(&self) -> String {
+        let mut text = format!("{HEADER}\n");
+        for (name, value) in &self.counters {
+            text += &format!("{name} {value}\n");
+        }
+        text
+    }
+
+    /// Deserializes a checkpoint serialized by [`Checkpoint::encode`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::checkpoint::Checkpoint;
+    /// assert!(Checkpoint::decode("checkpoint v1\nnonce 12\n").is_ok());
+    /// assert_eq!(Checkpoint::decode("checkpoint v2\n").unwrap_err().to_string(), "line 1: unsupported checkpoint format 'checkpoint v2'");
+    /// assert_eq!(Checkpoint::decode("checkpoint v1\nnonce -1\n").unwrap_err().to_string(), "line 2: invalid counter 'nonce -1'");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the text is not a checkpoint of
+    /// this format.
+    pub fn decode(text: &str) -> Result<Self, AocError> {
+        let mut lines = text.lines();
+        match lines.next() {
+            Some(HEADER) => {}
+            header => return Err(AocError::parse(1, format!("unsupported checkpoint format '{}'", header.unwrap_or_default()))),
+        }
+        let mut checkpoint = Checkpoint::new();
+        for (index, line) in lines.enumerate() {
+            let counter = line.split_once(' ').and_then(|(name, value)| Some((name, value.parse().ok()?)));
+            let Some((name, value)) = counter else { return Err(AocError::parse(index + 2, format!("invalid counter '{line}'"))) };
+            checkpoint.set(name, value);
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// Where a search saves its [`Checkpoint`]s and finds the one it resumes
+/// from.
+///
+/// A store belongs to a single search: a part of a day on an input.
+/// Searches save from any of their threads, every few seconds of work at
+/// most, so implementations may write each checkpoint as it comes.
+pub trait CheckpointStore: Sync {
+    /// Returns the checkpoint to resume from, if any.
+    fn load(&self) -> Option<Checkpoint>;
+
+    /// Saves the progress of the search, replacing the previous checkpoint.
+    fn save(&self, checkpoint: &Checkpoint);
+}
+
+/// A [`CheckpointStore`] never resuming and discarding every checkpoint,
+/// for searches run from scratch.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCheckpoint;
+
+impl CheckpointStore for NoCheckpoint {
+    fn load(&self) -> Option<Checkpoint> {
+        None
+    }
+
+    fn save(&self, _checkpoint: &Checkpoint) {}
+}
+
+/// A [`CheckpointStore`] keeping the last checkpoint in memory, to resume
+/// a search within the same process.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::checkpoint::{Checkpoint, CheckpointStore, MemoryCheckpoint};
+/// let store = MemoryCheckpoint::default();
+/// assert_eq!(store.load(), None);
+/// let mut checkpoint = Checkpoint::new();
+/// checkpoint.set("house", 16384);
+/// store.save(&checkpoint);
+/// assert_eq!(store.load(), Some(checkpoint));
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryCheckpoint(Mutex<Option<Checkpoint>>);
+
+impl MemoryCheckpoint {
+    /// Creates a store holding `checkpoint` to resume from.
+    pub fn new(checkpoint: Checkpoint) -> Self {
+        MemoryCheckpoint(Mutex::new(Some(checkpoint)))
+    }
+}
+
+impl CheckpointStore for MemoryCheckpoint {
+    fn load(&self) -> Option<Checkpoint> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn save(&self, checkpoint: &Checkpoint) {
+        *self.0.lock().unwrap() = Some(checkpoint.clone());
+    }
+}
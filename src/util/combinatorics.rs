@@ -0,0 +1,227 @@
+//! Lazy iterators over permutations, subsets and integer compositions.
+//!
+//! Each iterator keeps only the current arrangement and computes the next
+//! one on demand, so that even a search over billions of arrangements runs
+//! in constant memory and may stop early.
+
+/// Iterator over the permutations of a slice, in the lexicographic order of
+/// the positions of the items. Created by [`permutations`].
+#[derive(Debug, Clone)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    /// The positions of the items in the next permutation, or `None` once
+    /// every permutation has been returned.
+    positions: Option<Vec<usize>>,
+}
+
+/// Returns an iterator over the `n!` permutations of `items`.
+///
+/// Every permutation is returned as a new vector. Use
+/// [`for_each_permutation`] to visit them faster without allocating.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::combinatorics::permutations;
+/// let all: Vec<_> = permutations(&['a', 'b', 'c']).collect();
+/// assert_eq!(all.len(), 6);
+/// assert_eq!(all[0], ['a', 'b', 'c']);
+/// assert_eq!(all[1], ['a', 'c', 'b']);
+/// assert_eq!(all[5], ['c', 'b', 'a']);
+/// ```
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations { items, positions: Some((0..items.len()).collect()) }
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let positions = self.positions.as_mut()?;
+        let permutation = positions.iter().map(|&position| self.items[position].clone()).collect();
+        if !next_permutation(positions) {
+            self.positions = None;
+        }
+        Some(permutation)
+    }
+}
+
+/// Rearranges `values` into the next permutation in lexicographic order.
+///
+/// # Returns
+/// Whether there was a next permutation. The last permutation, sorted in
+/// decreasing order, is left unchanged.
+fn next_permutation(values: &mut [usize]) -> bool {
+    // The longest decreasing suffix cannot be rearranged into a larger one,
+    // so the value before it is replaced by the smallest larger one in it.
+    let Some(pivot) = values.windows(2).rposition(|pair| pair[0] < pair[1]) else { return false };
+    let successor = values.iter().rposition(|&value| value > values[pivot]).unwrap();
+    values.swap(pivot, successor);
+    values[pivot + 1..].reverse();
+    true
+}
+
+/// Calls `visit` with every permutation of `items`, rearranged in place
+/// with Heap's algorithm.
+///
+/// Each permutation is one swap away from the previous one, which makes
+/// this much faster than [`permutations`], but the order is not
+/// lexicographic. `items` is left in its last permutation.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::combinatorics::for_each_permutation;
+/// let mut count = 0;
+/// let mut best = 0;
+/// for_each_permutation(&mut [3, 1, 4, 1], |route| {
+///     count += 1;
+///     best = best.max(route.windows(2).map(|pair| pair[0] * pair[1]).sum());
+/// });
+/// assert_eq!((count, best), (24, 19));
+/// ```
+pub fn for_each_permutation<T>(items: &mut [T], mut visit: impl FnMut(&[T])) {
+    // counters[depth] counts the swaps done at `depth` since it was reset,
+    // replacing the recursion of the textbook version of the algorithm.
+    let mut counters = vec![0; items.len()];
+    visit(items);
+    let mut depth = 1;
+    while depth < items.len() {
+        if counters[depth] < depth {
+            let other = if depth.is_multiple_of(2) { 0 } else { counters[depth] };
+            items.swap(other, depth);
+            visit(items);
+            counters[depth] += 1;
+            depth = 1;
+        } else {
+            counters[depth] = 0;
+            depth += 1;
+        }
+    }
+}
+
+/// Iterator over the subsets of `k` items of a slice, in lexicographic
+/// order of positions. Created by [`k_subsets`].
+#[derive(Debug, Clone)]
+pub struct KSubsets<'a, T> {
+    items: &'a [T],
+    /// The increasing positions of the items in the next subset, or `None`
+    /// once every subset has been returned.
+    positions: Option<Vec<usize>>,
+}
+
+/// Returns an iterator over the subsets of `k` items of `items`, keeping
+/// the items in their order.
+///
+/// There is a single, empty, subset of 0 items, and none of more items than
+/// there are.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::combinatorics::k_subsets;
+/// let pairs: Vec<_> = k_subsets(&[1, 2, 3, 4], 2).collect();
+/// assert_eq!(pairs, [[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]);
+/// assert_eq!(k_subsets(&[1, 2], 0).count(), 1);
+/// assert_eq!(k_subsets(&[1, 2], 3).count(), 0);
+/// ```
+pub fn k_subsets<T: Clone>(items: &[T], k: usize) -> KSubsets<'_, T> {
+    KSubsets { items, positions: (k <= items.len()).then(|| (0..k).collect()) }
+}
+
+impl<T: Clone> Iterator for KSubsets<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let positions = self.positions.as_mut()?;
+        let subset = positions.iter().map(|&position| self.items[position].clone()).collect();
+        // The last position that can still move right is moved by one, and
+        // the following ones are packed right after it.
+        let (n, k) = (self.items.len(), positions.len());
+        match (0..k).rposition(|index| positions[index] < n - k + index) {
+            Some(index) => {
+                positions[index] += 1;
+                for next in index + 1..k {
+                    positions[next] = positions[next - 1] + 1;
+                }
+            }
+            None => self.positions = None,
+        }
+        Some(subset)
+    }
+}
+
+/// Iterator over the ways of writing a number as an ordered sum of a fixed
+/// number of non-negative terms, in lexicographic order. Created by
+/// [`compositions`].
+#[derive(Debug, Clone)]
+pub struct Compositions {
+    /// The terms of the next composition, or `None` once every composition
+    /// has been returned.
+    terms: Option<Vec<u32>>,
+}
+
+/// Returns an iterator over the compositions of `total` into `parts`
+/// non-negative terms, such as the amounts of each ingredient of a recipe.
+///
+/// There are `C(total + parts - 1, parts - 1)` of them.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::combinatorics::compositions;
+/// let all: Vec<_> = compositions(2, 3).collect();
+/// assert_eq!(all, [[0, 0, 2], [0, 1, 1], [0, 2, 0], [1, 0, 1], [1, 1, 0], [2, 0, 0]]);
+/// assert_eq!(compositions(100, 4).count(), 176_851);
+/// assert_eq!(compositions(0, 0).count(), 1);
+/// assert_eq!(compositions(1, 0).count(), 0);
+/// ```
+pub fn compositions(total: u32, parts: usize) -> Compositions {
+    let terms = match parts {
+        0 => (total == 0).then(Vec::new),
+        _ => {
+            let mut terms = vec![0; parts];
+            terms[parts - 1] = total;
+            Some(terms)
+        }
+    };
+    Compositions { terms }
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Vec<u32>> {
+        let terms = self.terms.as_mut()?;
+        let composition = terms.clone();
+        if !next_composition(terms) {
+            self.terms = None;
+        }
+        Some(composition)
+    }
+}
+
+/// Rearranges `terms` into the next composition of their sum in
+/// lexicographic order.
+///
+/// # Returns
+/// Whether there was a next composition.
+fn next_composition(terms: &mut [u32]) -> bool {
+    let Some(last) = terms.len().checked_sub(1) else { return false };
+    if last == 0 {
+        return false;
+    }
+    if terms[last] > 0 {
+        // One unit of the remainder moves to the last free term.
+        terms[last - 1] += 1;
+        terms[last] -= 1;
+        return true;
+    }
+    // Otherwise the last non-zero free term gives one unit to the term
+    // before it, and the rest of it becomes the remainder.
+    match terms[..last].iter().rposition(|&term| term > 0) {
+        Some(index) if index > 0 => {
+            terms[index - 1] += 1;
+            terms[last] = terms[index] - 1;
+            terms[index] = 0;
+            true
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,168 @@
+//! Points and rectangles of grid coordinates, such as the lights of day 6
+//! and day 18.
+//!
+//! Puzzles name rectangles by their first and last cells, both included,
+//! while slices and ranges exclude their end. A [`Rect`] excludes its far
+//! corner like ranges do, and [`Rect::from_inclusive`] is the one place
+//! converting from the puzzle notation.
+
+use std::ops::Range;
+
+/// The `(x, y)` coordinates of a cell, `x` being the column and `y` the
+/// row, both starting at 0 in the top left corner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    /// The column of the cell.
+    pub x: usize,
+    /// The row of the cell.
+    pub y: usize,
+}
+
+impl Point {
+    /// Creates a [`Point`] from its coordinates.
+    pub const fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point { x, y }
+    }
+}
+
+/// A rectangle of cells, from its corner `min` included to its corner `max`
+/// excluded, so that it is empty when `max` is not past `min` on both axes.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::geom::{Point, Rect};
+/// let rect = Rect::from_inclusive(Point::new(1, 1), Point::new(3, 2));
+/// assert_eq!(rect, Rect::new(Point::new(1, 1), Point::new(4, 3)));
+/// assert_eq!((rect.width(), rect.height(), rect.area()), (3, 2, 6));
+/// assert_eq!(rect.last(), Some(Point::new(3, 2)));
+/// assert!(rect.contains(Point::new(3, 2)) && !rect.contains(Point::new(4, 2)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// The top left corner, included.
+    pub min: Point,
+    /// The bottom right corner, excluded.
+    pub max: Point,
+}
+
+impl Rect {
+    /// Creates a [`Rect`] from its top left corner, included, and its
+    /// bottom right corner, excluded.
+    pub const fn new(min: Point, max: Point) -> Self {
+        Rect { min, max }
+    }
+
+    /// Creates the [`Rect`] of `width` by `height` cells whose top left
+    /// corner is the origin, such as a whole grid.
+    pub const fn sized(width: usize, height: usize) -> Self {
+        Rect { min: Point::new(0, 0), max: Point::new(width, height) }
+    }
+
+    /// Creates a [`Rect`] from its first and last cells, both included, as
+    /// puzzles describe them.
+    ///
+    /// A last cell before the first one on either axis makes the rectangle
+    /// empty. The excluded corner saturates at `usize::MAX`, so a last cell
+    /// at `usize::MAX` is left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::geom::{Point, Rect};
+    /// assert_eq!(Rect::from_inclusive(Point::new(0, 0), Point::new(999, 0)).area(), 1000);
+    /// assert!(Rect::from_inclusive(Point::new(5, 5), Point::new(4, 9)).is_empty());
+    /// ```
+    pub fn from_inclusive(first: Point, last: Point) -> Self {
+        Rect { min: first, max: Point::new(last.x.saturating_add(1), last.y.saturating_add(1)) }
+    }
+
+    /// Returns the number of columns of this rectangle.
+    pub fn width(&self) -> usize {
+        self.max.x.saturating_sub(self.min.x)
+    }
+
+    /// Returns the number of rows of this rectangle.
+    pub fn height(&self) -> usize {
+        self.max.y.saturating_sub(self.min.y)
+    }
+
+    /// Returns the number of cells of this rectangle.
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    /// Returns whether this rectangle has no cell.
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    /// Returns the columns of this rectangle.
+    pub fn xs(&self) -> Range<usize> {
+        self.min.x..self.max.x
+    }
+
+    /// Returns the rows of this rectangle.
+    pub fn ys(&self) -> Range<usize> {
+        self.min.y..self.max.y
+    }
+
+    /// Returns the bottom right cell of this rectangle, the last one
+    /// included, or `None` if it is empty.
+    pub fn last(&self) -> Option<Point> {
+        (!self.is_empty()).then(|| Point::new(self.max.x - 1, self.max.y - 1))
+    }
+
+    /// Returns whether this rectangle holds the cell `point`.
+    pub fn contains(&self, point: Point) -> bool {
+        self.xs().contains(&point.x) && self.ys().contains(&point.y)
+    }
+
+    /// Returns the cells held by both this rectangle and `other`.
+    ///
+    /// # Returns
+    /// The overlap of the rectangles, or `None` if they have no cell in
+    /// common.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::geom::{Point, Rect};
+    /// let rect = Rect::new(Point::new(2, 0), Point::new(12, 5));
+    /// assert_eq!(rect.intersect(&Rect::sized(10, 10)), Some(Rect::new(Point::new(2, 0), Point::new(10, 5))));
+    /// assert_eq!(rect.intersect(&Rect::sized(2, 10)), None);
+    /// ```
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        let overlap = Rect { min, max };
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// Returns the cells of this rectangle, row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::geom::{Point, Rect};
+    /// let points: Vec<_> = Rect::new(Point::new(1, 0), Point::new(3, 2)).iter_points().map(|point| (point.x, point.y)).collect();
+    /// assert_eq!(points, [(1, 0), (2, 0), (1, 1), (2, 1)]);
+    /// ```
+    pub fn iter_points(&self) -> impl Iterator<Item = Point> {
+        let xs = self.xs();
+        self.ys().flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+    }
+
+    /// Returns the four corner cells of this rectangle: top left, top
+    /// right, bottom left and bottom right.
+    ///
+    /// # Returns
+    /// The corners, or `None` if the rectangle is empty. They are not all
+    /// different when it is a single row or column.
+    pub fn corners(&self) -> Option<[Point; 4]> {
+        let last = self.last()?;
+        Some([self.min, Point::new(last.x, self.min.y), Point::new(self.min.x, last.y), last])
+    }
+}
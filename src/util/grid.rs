@@ -0,0 +1,165 @@
+use std::ops::{Index, IndexMut};
+
+/// The offsets of the 4 orthogonal neighbors of a cell.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The offsets of the 8 orthogonal and diagonal neighbors of a cell.
+const SURROUNDING: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// A rectangular grid of cells stored row by row.
+///
+/// Cells are addressed by `(x, y)` coordinates, `x` being the column and
+/// `y` the row, both starting at 0 in the top left corner. Indexing with
+/// `grid[(x, y)]` panics out of bounds, while [`Grid::get`] and
+/// [`Grid::set`] check the bounds.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::grid::Grid;
+/// let mut grid = Grid::new(3, 2, 0);
+/// grid[(2, 1)] = 7;
+/// assert_eq!(grid.get(2, 1), Some(&7));
+/// assert_eq!(grid.get(3, 1), None);
+/// assert_eq!(grid.rows().collect::<Vec<_>>(), [[0, 0, 0], [0, 0, 7]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    /// The cells, row by row.
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a [`Grid`] of `width` by `height` cells, all set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Grows the grid by the provided number of cells on each side, the new
+    /// cells being set to `fill`.
+    ///
+    /// Existing cells keep their value but move by `left` columns and `top`
+    /// rows, so coordinates held by the caller must be shifted accordingly.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::grid::Grid;
+    /// let mut grid = Grid::new(1, 1, 1);
+    /// grid.grow(1, 0, 0, 1, 0);
+    /// assert_eq!((grid.width(), grid.height()), (2, 2));
+    /// assert_eq!(grid.rows().collect::<Vec<_>>(), [[0, 1], [0, 0]]);
+    /// ```
+    pub fn grow(&mut self, left: usize, top: usize, right: usize, bottom: usize, fill: T) {
+        let width = left + self.width + right;
+        let height = top + self.height + bottom;
+        let mut cells = Vec::with_capacity(width * height);
+        cells.resize(top * width, fill.clone());
+        for row in self.cells.chunks_exact(self.width.max(1)).take(self.height) {
+            cells.resize(cells.len() + left, fill.clone());
+            cells.extend_from_slice(row);
+            cells.resize(cells.len() + right, fill.clone());
+        }
+        cells.resize(width * height, fill);
+        *self = Grid { cells, width, height };
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns the number of columns of the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows of the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether `(x, y)` lies within the grid.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.contains(x, y).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Sets the cell at `(x, y)` to `value`.
+    ///
+    /// # Returns
+    /// Whether the cell lies within the grid, the value being discarded
+    /// otherwise.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => { *cell = value; true }
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the coordinates and value of every cell,
+    /// row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    /// Returns an iterator over the rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1)).take(self.height)
+    }
+
+    /// Returns an iterator over the coordinates of the up to 4 orthogonal
+    /// neighbors of `(x, y)` within the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::grid::Grid;
+    /// let grid = Grid::new(3, 3, ());
+    /// assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    /// assert_eq!(grid.neighbors4(1, 1).count(), 4);
+    /// ```
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &ORTHOGONAL)
+    }
+
+    /// Returns an iterator over the coordinates of the up to 8 orthogonal
+    /// and diagonal neighbors of `(x, y)` within the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::grid::Grid;
+    /// let grid = Grid::new(3, 3, ());
+    /// assert_eq!(grid.neighbors8(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1), (1, 1)]);
+    /// assert_eq!(grid.neighbors8(1, 1).count(), 8);
+    /// ```
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &SURROUNDING)
+    }
+
+    fn neighbors<'a>(&'a self, x: usize, y: usize, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter()
+            .filter_map(move |&(dx, dy)| Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?)))
+            .filter(|&(x, y)| self.contains(x, y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
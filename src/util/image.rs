@@ -0,0 +1,82 @@
+//! Export of grayscale images, used to render the state of some days.
+//!
+//! Animated GIFs require the `export` feature.
+
+use std::io::{self, Write};
+#[cfg(feature = "export")]
+use std::time::Duration;
+use crate::util::grid::Grid;
+
+/// Writes a grid of gray levels as a binary PGM (portable graymap) image,
+/// 0 being black and 255 white.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::grid::Grid;
+/// use aoc2015::util::image::write_pgm;
+/// let mut image = Grid::new(2, 1, 0);
+/// image[(1, 0)] = 255;
+/// let mut file = Vec::new();
+/// write_pgm(&image, &mut file).unwrap();
+/// assert_eq!(file, b"P5\n2 1\n255\n\x00\xff");
+/// ```
+///
+/// # Errors
+/// Returns an [`io::Error`] if writing fails.
+pub fn write_pgm(image: &Grid<u8>, mut writer: impl Write) -> io::Result<()> {
+    write!(writer, "P5\n{} {}\n255\n", image.width(), image.height())?;
+    for row in image.rows() {
+        writer.write_all(row)?;
+    }
+    writer.flush()
+}
+
+/// Writes grids of gray levels as the frames of an animated GIF, looping
+/// forever.
+///
+/// # Arguments
+/// * `frames` - The frames, which must all have the size of the first.
+/// * `scale` - The side, in pixels, of the square drawn for each cell.
+/// * `delay` - How long each frame is shown, rounded up to hundredths of
+///   a second.
+/// * `writer` - Receives the GIF file.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use aoc2015::util::grid::Grid;
+/// use aoc2015::util::image::write_gif;
+/// let frames = [Grid::new(2, 2, 0), Grid::new(2, 2, 255)];
+/// let mut file = Vec::new();
+/// write_gif(frames, 3, Duration::from_millis(100), &mut file).unwrap();
+/// assert!(file.starts_with(b"GIF89a\x06\x00\x06\x00"));
+/// ```
+///
+/// # Errors
+/// Returns an [`io::Error`] if the frames differ in size or are too large
+/// for a GIF, or if writing fails.
+#[cfg(feature = "export")]
+pub fn write_gif(frames: impl IntoIterator<Item = Grid<u8>>, scale: usize, delay: Duration, writer: impl Write) -> io::Result<()> {
+    let mut frames = frames.into_iter().peekable();
+    let Some(first) = frames.peek() else { return Ok(()) };
+    let (width, height) = (first.width(), first.height());
+    let size = |cells: usize| u16::try_from(cells * scale)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frames are too large for a GIF"));
+    let (pixel_width, pixel_height) = (size(width)?, size(height)?);
+    let palette: Vec<u8> = (0..=255).flat_map(|level| [level; 3]).collect();
+    let mut encoder = gif::Encoder::new(writer, pixel_width, pixel_height, &palette).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    let delay = u16::try_from(delay.as_millis().div_ceil(10)).unwrap_or(u16::MAX);
+    for frame in frames {
+        if (frame.width(), frame.height()) != (width, height) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "frames differ in size"));
+        }
+        let pixels: Vec<u8> = frame.rows()
+            .flat_map(|row| std::iter::repeat_n(row.iter().flat_map(|&level| std::iter::repeat_n(level, scale)), scale).flatten())
+            .collect();
+        let mut frame = gif::Frame::from_indexed_pixels(pixel_width, pixel_height, pixels, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
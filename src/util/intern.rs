@@ -0,0 +1,72 @@
+//! Interning of names, so that the days working on graphs of named nodes
+//! compare and index small integers instead of hashing strings.
+
+use std::collections::HashMap;
+
+/// Maps names to small consecutive IDs, and IDs back to names.
+///
+/// IDs are handed out in the order names are first seen, starting from 0,
+/// so they can index vectors and matrices directly.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::intern::Interner;
+/// let mut cities = Interner::default();
+/// assert_eq!(cities.intern("London"), Ok(0));
+/// assert_eq!(cities.intern("Dublin"), Ok(1));
+/// assert_eq!(cities.intern("London"), Ok(0));
+/// assert_eq!(cities.get("Dublin"), Some(1));
+/// assert_eq!(cities.get("Belfast"), None);
+/// assert_eq!(cities.name(1), "Dublin");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    /// The ID of each name.
+    ids: HashMap<String, u16>,
+    /// The name of each ID.
+    names: Vec<String>,
+}
+
+impl Interner {
+    /// Returns the ID of `name`, giving it the next ID if it is new.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if every ID is already taken.
+    pub fn intern(&mut self, name: &str) -> Result<u16, String> {
+        if let Some(&id) = self.ids.get(name) {
+            return Ok(id);
+        }
+        let id = u16::try_from(self.names.len()).map_err(|_| format!("more than {} distinct names", u16::MAX as usize + 1))?;
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        Ok(id)
+    }
+
+    /// Returns the ID of `name`, if it was interned.
+    pub fn get(&self, name: &str) -> Option<u16> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of `id`.
+    ///
+    /// # Panics
+    /// Panics if no name has this ID.
+    pub fn name(&self, id: u16) -> &str {
+        &self.names[usize::from(id)]
+    }
+
+    /// Returns every name, indexed by ID.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the number of names interned.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether no name was interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
@@ -0,0 +1,102 @@
+//! Kernels over rows of 16-bit lanes, used by the packed brightness grid
+//! of day 6.
+//!
+//! Every kernel has a portable scalar version. With the `simd` feature, on
+//! x86-64 processors supporting AVX2, detected at runtime, the kernels run
+//! explicit AVX2 implementations processing 16 lanes at a time instead.
+
+/// Adds `amount` to every lane of `lanes`, stopping at `u16::MAX`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::lanes::add_saturating;
+/// let mut lanes = [0, 1, u16::MAX - 1];
+/// add_saturating(&mut lanes, 2);
+/// assert_eq!(lanes, [2, 3, u16::MAX]);
+/// ```
+pub fn add_saturating(lanes: &mut [u16], amount: u16) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the running processor.
+        unsafe { avx2::add_saturating(lanes, amount) };
+        return;
+    }
+    add_saturating_scalar(lanes, amount)
+}
+
+/// Adds `amount` to every lane of `lanes`, stopping at `u16::MAX`, one
+/// lane at a time. See [`add_saturating`].
+pub fn add_saturating_scalar(lanes: &mut [u16], amount: u16) {
+    for lane in lanes {
+        *lane = lane.saturating_add(amount);
+    }
+}
+
+/// Subtracts `amount` from every lane of `lanes`, stopping at 0.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::lanes::sub_saturating;
+/// let mut lanes = [0, 1, 5];
+/// sub_saturating(&mut lanes, 2);
+/// assert_eq!(lanes, [0, 0, 3]);
+/// ```
+pub fn sub_saturating(lanes: &mut [u16], amount: u16) {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the running processor.
+        unsafe { avx2::sub_saturating(lanes, amount) };
+        return;
+    }
+    sub_saturating_scalar(lanes, amount)
+}
+
+/// Subtracts `amount` from every lane of `lanes`, stopping at 0, one lane
+/// at a time. See [`sub_saturating`].
+pub fn sub_saturating_scalar(lanes: &mut [u16], amount: u16) {
+    for lane in lanes {
+        *lane = lane.saturating_sub(amount);
+    }
+}
+
+/// Returns the sum of the lanes of `lanes`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::lanes::sum;
+/// assert_eq!(sum(&[u16::MAX, 1, 2]), 65_538);
+/// ```
+pub fn sum(lanes: &[u16]) -> u64 {
+    lanes.iter().map(|&lane| u64::from(lane)).sum()
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    /// Adds `amount` to every lane, 16 lanes at a time with `vpaddusw`.
+    /// See [`super::add_saturating`].
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_saturating(lanes: &mut [u16], amount: u16) {
+        let amounts = _mm256_set1_epi16(amount as i16);
+        let mut chunks = lanes.chunks_exact_mut(16);
+        for chunk in &mut chunks {
+            let values = _mm256_loadu_si256(chunk.as_ptr().cast());
+            _mm256_storeu_si256(chunk.as_mut_ptr().cast(), _mm256_adds_epu16(values, amounts));
+        }
+        super::add_saturating_scalar(chunks.into_remainder(), amount);
+    }
+
+    /// Subtracts `amount` from every lane, 16 lanes at a time with
+    /// `vpsubusw`. See [`super::sub_saturating`].
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sub_saturating(lanes: &mut [u16], amount: u16) {
+        let amounts = _mm256_set1_epi16(amount as i16);
+        let mut chunks = lanes.chunks_exact_mut(16);
+        for chunk in &mut chunks {
+            let values = _mm256_loadu_si256(chunk.as_ptr().cast());
+            _mm256_storeu_si256(chunk.as_mut_ptr().cast(), _mm256_subs_epu16(values, amounts));
+        }
+        super::sub_saturating_scalar(chunks.into_remainder(), amount);
+    }
+}
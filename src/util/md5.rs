@@ -0,0 +1,161 @@
+//! The MD5 message-digest algorithm of RFC 1321, used by day 4.
+//!
+//! MD5 processes its input in blocks of 64 bytes, so the state of a
+//! [`Md5`] hasher can be cloned after consuming a common prefix and reused
+//! to hash many messages sharing it.
+
+/// The number of bits each step rotates its result by.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// The constant added by each step, the integer part of
+/// `abs(sin(i + 1)) * 2^32`.
+const CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// The initial state of the hash.
+const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+/// The padding appended to a message: a single 1 bit followed by zeroes.
+const PADDING: [u8; 64] = {
+    let mut padding = [0; 64];
+    padding[0] = 0x80;
+    padding
+};
+
+/// An incremental MD5 hasher.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::md5::Md5;
+/// let mut prefix = Md5::new();
+/// prefix.update(b"abcdef");
+/// let mut hasher = prefix.clone();
+/// hasher.update(b"609043");
+/// assert_eq!(hasher.finalize()[..3], [0x00, 0x00, 0x01]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Md5 {
+    state: [u32; 4],
+    /// The bytes of the current block consumed so far.
+    block: [u8; 64],
+    /// The number of bytes of `block` in use.
+    buffered: usize,
+    /// The total number of bytes consumed.
+    length: u64,
+}
+
+impl Md5 {
+    /// Creates a hasher that has not consumed anything yet.
+    pub fn new() -> Self {
+        Md5 { state: INITIAL_STATE, block: [0; 64], buffered: 0, length: 0 }
+    }
+
+    /// Consumes the bytes of `data`, hashing every block they complete.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let taken = data.len().min(64 - self.buffered);
+            self.block[self.buffered..self.buffered + taken].copy_from_slice(&data[..taken]);
+            self.buffered += taken;
+            data = &data[taken..];
+            if self.buffered < 64 {
+                return;
+            }
+            compress(&mut self.state, &self.block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut self.state, block.try_into().unwrap());
+        }
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// Pads the message consumed so far and returns its digest.
+    pub fn finalize(mut self) -> [u8; 16] {
+        let bits = self.length.wrapping_mul(8);
+        // The padding leaves exactly 8 bytes in the last block for the length.
+        let padding = if self.buffered < 56 { 56 - self.buffered } else { 120 - self.buffered };
+        self.update(&PADDING[..padding]);
+        self.block[56..].copy_from_slice(&bits.to_le_bytes());
+        compress(&mut self.state, &self.block);
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Md5::new()
+    }
+}
+
+/// Returns the MD5 digest of `data`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::md5::digest;
+/// let hex = |digest: [u8; 16]| digest.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+/// assert_eq!(hex(digest(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+/// assert_eq!(hex(digest(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+/// assert_eq!(
+///     hex(digest(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890")),
+///     "57edf4a22be3c955ac49da2e2107b67a",
+/// );
+/// ```
+pub fn digest(data: &[u8]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Hashes one block of 64 bytes into `state`.
+///
+/// Each of the 4 rounds of 16 steps has its own loop, so that the mixing
+/// function and the order of the words are known at compile time once the
+/// loops are unrolled.
+fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let words: [u32; 16] = std::array::from_fn(|i| u32::from_le_bytes(block[4 * i..4 * i + 4].try_into().unwrap()));
+    let [mut a, mut b, mut c, mut d] = *state;
+    let step = |a: &mut u32, b: u32, mixed: u32, word: usize, index: usize| {
+        let sum = mixed.wrapping_add(*a).wrapping_add(CONSTANTS[index]).wrapping_add(words[word]);
+        *a = b.wrapping_add(sum.rotate_left(SHIFTS[index]));
+    };
+    for index in 0..16 {
+        step(&mut a, b, (b & c) | (!b & d), index, index);
+        (a, b, c, d) = (d, a, b, c);
+    }
+    for index in 16..32 {
+        step(&mut a, b, (d & b) | (!d & c), (5 * index + 1) % 16, index);
+        (a, b, c, d) = (d, a, b, c);
+    }
+    for index in 32..48 {
+        step(&mut a, b, b ^ c ^ d, (3 * index + 5) % 16, index);
+        (a, b, c, d) = (d, a, b, c);
+    }
+    for index in 48..64 {
+        step(&mut a, b, c ^ (b | !d), (7 * index) % 16, index);
+        (a, b, c, d) = (d, a, b, c);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(value);
+    }
+}
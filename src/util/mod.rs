@@ -0,0 +1,17 @@
+//! Utilities shared by several days.
+
+pub mod bits;
+pub mod checkpoint;
+pub mod combinatorics;
+pub mod geom;
+pub mod grid;
+pub mod image;
+pub mod intern;
+pub mod lanes;
+pub mod md5;
+pub mod natural;
+pub mod parse;
+pub mod rng;
+pub mod search;
+pub mod stats;
+pub mod tsp;
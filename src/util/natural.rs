@@ -0,0 +1,128 @@
+//! Arbitrarily large natural numbers, for the counts that outgrow `u128`,
+//! such as the length of a look-and-say sequence after a thousand steps.
+//!
+//! Only what counting needs is supported: addition, multiplication by a
+//! machine integer, and printing in decimal.
+
+use std::fmt;
+use std::ops::{AddAssign, Mul};
+
+/// A natural number of any size.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::natural::Natural;
+/// let mut n = Natural::from(u64::MAX);
+/// n += &Natural::from(1);
+/// assert_eq!(n.to_string(), "18446744073709551616");
+/// assert_eq!((&n * 1000).to_string(), "18446744073709551616000");
+/// assert_eq!(n.to_u64(), None);
+/// assert_eq!(Natural::from(42).to_u64(), Some(42));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Natural {
+    /// The 64-bit digits of the number, least significant first, without
+    /// trailing zeroes, so that zero has none.
+    limbs: Vec<u64>,
+}
+
+impl Natural {
+    /// Returns whether this number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns this number as a `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [limb] => Some(limb),
+            _ => None,
+        }
+    }
+
+    /// Divides this number by `divisor` in place.
+    ///
+    /// # Returns
+    /// The remainder of the division.
+    fn div_rem_small(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 64) | u128::from(*limb);
+            *limb = (value / u128::from(divisor)) as u64;
+            remainder = value % u128::from(divisor);
+        }
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        remainder as u64
+    }
+}
+
+impl From<u64> for Natural {
+    fn from(value: u64) -> Self {
+        Natural { limbs: if value == 0 { Vec::new() } else { vec![value] } }
+    }
+}
+
+impl AddAssign<&Natural> for Natural {
+    fn add_assign(&mut self, other: &Natural) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = false;
+        for (index, limb) in self.limbs.iter_mut().enumerate() {
+            if index >= other.limbs.len() && !carry { break; }
+            let addend = other.limbs.get(index).copied().unwrap_or(0);
+            let (sum, overflow) = limb.overflowing_add(addend);
+            let (sum, carry_overflow) = sum.overflowing_add(u64::from(carry));
+            *limb = sum;
+            carry = overflow || carry_overflow;
+        }
+        if carry {
+            self.limbs.push(1);
+        }
+    }
+}
+
+impl Mul<u64> for &Natural {
+    type Output = Natural;
+
+    fn mul(self, factor: u64) -> Natural {
+        if factor == 0 {
+            return Natural::default();
+        }
+        let mut carry = 0u128;
+        let mut limbs: Vec<u64> = self.limbs.iter()
+            .map(|&limb| {
+                let product = u128::from(limb) * u128::from(factor) + carry;
+                carry = product >> 64;
+                product as u64
+            })
+            .collect();
+        if carry > 0 {
+            limbs.push(carry as u64);
+        }
+        Natural { limbs }
+    }
+}
+
+impl fmt::Display for Natural {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The largest power of ten fitting in a limb.
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        // The number is cut into chunks of 19 decimal digits, least
+        // significant first.
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(CHUNK));
+        }
+        let Some((first, others)) = chunks.split_last() else { return f.pad("0") };
+        let mut digits = first.to_string();
+        for chunk in others.iter().rev() {
+            digits.push_str(&format!("{chunk:019}"));
+        }
+        f.pad(&digits)
+    }
+}
@@ -0,0 +1,192 @@
+//! Helpers for parsing the lines of puzzle inputs, returning a description
+//! of the problem on failure, as expected by
+//! [`parse_lines`](crate::error::parse_lines).
+
+use std::fmt;
+use std::str::FromStr;
+use regex::{Captures, Regex};
+
+/// Parses a number, or any other value, from `text`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::parse::number;
+/// assert_eq!(number::<u32>("464"), Ok(464));
+/// assert_eq!(number::<u32>("-1"), Err("invalid number '-1'".to_string()));
+/// ```
+///
+/// # Errors
+/// Returns a description of the problem if `text` does not parse.
+pub fn number<T: FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("invalid number '{text}'"))
+}
+
+/// Splits `line` into exactly `N` fields around `separator`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::parse::split;
+/// assert_eq!(split("London to Dublin", " to "), Ok(["London", "Dublin"]));
+/// assert!(split::<2>("London", " to ").is_err());
+/// assert!(split::<2>("a to b to c", " to ").is_err());
+/// ```
+///
+/// # Errors
+/// Returns a description of the problem if there are more or fewer fields.
+pub fn split<'a, const N: usize>(line: &'a str, separator: &str) -> Result<[&'a str; N], String> {
+    let fields: Vec<&str> = line.split(separator).collect();
+    fields.try_into().map_err(|_| format!("expected {N} fields separated by '{separator}' in '{line}'"))
+}
+
+/// Splits `line` into exactly `N` fields around `separator`, and parses
+/// each of them.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::parse::fields;
+/// assert_eq!(fields::<u32, 3>("2x3x4", "x"), Ok([2, 3, 4]));
+/// assert_eq!(fields::<u32, 3>("2x3", "x"), Err("expected 3 fields separated by 'x' in '2x3'".to_string()));
+/// assert_eq!(fields::<u32, 3>("2x3xy", "x"), Err("invalid number 'y'".to_string()));
+/// ```
+///
+/// # Errors
+/// Returns a description of the problem if there are more or fewer fields,
+/// or a field does not parse.
+pub fn fields<T: FromStr, const N: usize>(line: &str, separator: &str) -> Result<[T; N], String> {
+    let values = split::<N>(line, separator)?.iter().map(|field| number(field)).collect::<Result<Vec<T>, _>>()?;
+    Ok(values.try_into().unwrap_or_else(|_| unreachable!("there are {N} fields")))
+}
+
+/// Why a line does not match a [`LinePattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError {
+    /// The line does not have the shape of the pattern.
+    Mismatch(String),
+    /// A field of the line does not parse.
+    Field {
+        /// The name of the field.
+        name: &'static str,
+        /// The text of the field.
+        text: String,
+    },
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineError::Mismatch(line) => write!(f, "invalid line '{line}'"),
+            LineError::Field { name, text } => write!(f, "invalid {name} '{text}'"),
+        }
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// The shape of a line, such as `{name} can fly {speed} km/s`, where each
+/// `{field}` stands for some text and the rest must match literally.
+///
+/// Built by [`parse_line!`](crate::parse_line), which parses each field as
+/// the type it is assigned to.
+#[derive(Debug)]
+pub struct LinePattern {
+    regex: Regex,
+}
+
+impl LinePattern {
+    /// Compiles a pattern into a regex matching a whole line, each field
+    /// becoming a named group matching as little text as possible.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::parse::LinePattern;
+    /// let pattern = LinePattern::new("{x},{y} (a {kind})");
+    /// let captures = pattern.captures("3,-4 (a corner)").unwrap();
+    /// assert_eq!(pattern.field::<i32>(&captures, "y"), Ok(-4));
+    /// assert_eq!(pattern.field::<String>(&captures, "kind").unwrap(), "corner");
+    /// assert!(pattern.captures("3,-4 a corner").is_err());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if a brace is not closed, or a field name is empty, not a
+    /// valid identifier or repeated.
+    pub fn new(pattern: &str) -> Self {
+        let mut regex = String::from("^");
+        let mut rest = pattern;
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..].find('}').map(|close| open + close)
+                .unwrap_or_else(|| panic!("unclosed field in line pattern '{pattern}'"));
+            let name = &rest[open + 1..close];
+            assert!(
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "invalid field name '{name}' in line pattern '{pattern}'",
+            );
+            regex.push_str(&regex::escape(&rest[..open]));
+            regex.push_str(&format!("(?P<{name}>.+?)"));
+            rest = &rest[close + 1..];
+        }
+        regex.push_str(&regex::escape(rest));
+        regex.push('$');
+        let regex = Regex::new(&regex).unwrap_or_else(|err| panic!("invalid line pattern '{pattern}': {err}"));
+        LinePattern { regex }
+    }
+
+    /// Matches a whole line against this pattern.
+    ///
+    /// # Errors
+    /// Returns a [`LineError::Mismatch`] if the line does not match.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Captures<'a>, LineError> {
+        self.regex.captures(line).ok_or_else(|| LineError::Mismatch(line.to_string()))
+    }
+
+    /// Parses a field of a line matched by [`LinePattern::captures`].
+    ///
+    /// # Errors
+    /// Returns a [`LineError::Field`] if the field does not parse, or is
+    /// not in the pattern.
+    pub fn field<T: FromStr>(&self, captures: &Captures, name: &'static str) -> Result<T, LineError> {
+        let text = captures.name(name).map_or("", |field| field.as_str());
+        text.parse().map_err(|_| LineError::Field { name, text: text.to_string() })
+    }
+}
+
+/// Parses a line of the shape of a [`LinePattern`], such as
+/// `"{name} can fly {speed} km/s"`, into a struct whose fields are named
+/// after those of the pattern, or a tuple of typed fields.
+///
+/// The pattern is compiled once, on first use, and each field is parsed
+/// with [`FromStr`] as the type of the struct field or of the tuple.
+///
+/// # Returns
+/// A `Result` holding the struct or the tuple, or the [`LineError`] of the
+/// line.
+///
+/// # Examples
+/// ```
+/// use aoc2015::parse_line;
+/// use aoc2015::util::parse::LineError;
+/// struct Route { from: String, to: String, distance: u32 }
+/// let route = parse_line!("{from} to {to} = {distance}", "London to Dublin = 464" => Route { from, to, distance }).unwrap();
+/// assert_eq!((route.from.as_str(), route.to.as_str(), route.distance), ("London", "Dublin", 464));
+///
+/// let corners = parse_line!("{x1},{y1} through {x2},{y2}", "0,0 through 999,1" => (x1: u32, y1: u32, x2: u32, y2: u32));
+/// assert_eq!(corners, Ok((0, 0, 999, 1)));
+/// let corners = parse_line!("{x},{y}", "0,y" => (x: u32, y: u32));
+/// assert_eq!(corners, Err(LineError::Field { name: "y", text: "y".to_string() }));
+/// ```
+#[macro_export]
+macro_rules! parse_line {
+    ($pattern:literal, $line:expr => $name:ident { $($field:ident),* $(,)? }) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::util::parse::LinePattern> = ::std::sync::OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| $crate::util::parse::LinePattern::new($pattern));
+        pattern.captures($line).and_then(|captures| Ok($name {
+            $($field: pattern.field(&captures, stringify!($field))?),*
+        }))
+    }};
+    ($pattern:literal, $line:expr => ($($field:ident: $ty:ty),* $(,)?)) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::util::parse::LinePattern> = ::std::sync::OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| $crate::util::parse::LinePattern::new($pattern));
+        pattern.captures($line).and_then(|captures| Ok((
+            $(pattern.field::<$ty>(&captures, stringify!($field))?,)*
+        )))
+    }};
+}
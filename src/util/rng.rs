@@ -0,0 +1,59 @@
+//! A small seedable pseudo-random generator, used where a solution or a
+//! tool needs reproducible randomness.
+
+use std::ops::RangeInclusive;
+
+/// The xorshift64 generator of Marsaglia: fast, tiny and deterministic,
+/// but not suitable for anything needing statistical quality.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::rng::XorShift;
+/// let mut rng = XorShift::new(42);
+/// let roll = rng.range(1..=6);
+/// assert!((1..=6).contains(&roll));
+/// assert_eq!(XorShift::new(42).range(1..=6), roll);
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    /// Creates a generator from a seed. A null seed, which xorshift cannot
+    /// leave, is replaced by a fixed one.
+    pub fn new(seed: u64) -> Self {
+        XorShift(if seed == 0 { 0x2015_1225 } else { seed })
+    }
+
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number below `bound`, which must not be 0.
+    ///
+    /// The modulo bias is negligible for the small bounds used here.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Returns a number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        start + self.below(end - start + 1)
+    }
+
+    /// Returns a random element of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Shuffles `slice` in place with the Fisher–Yates algorithm.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for index in (1..slice.len()).rev() {
+            slice.swap(index, self.below(index as u64 + 1) as usize);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Shortest path searches over implicit graphs, whose states and edges are
+//! generated on the fly by the caller.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A state waiting in the queue of [`astar`], ordered so that the
+/// [`BinaryHeap`] pops the lowest estimated total cost first.
+struct Queued<S, C> {
+    /// The cost so far plus the estimated remaining cost.
+    estimate: C,
+    /// The cost of reaching the state.
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Among equal estimates, the states furthest from the start are
+        // likely closest to a goal.
+        other.estimate.cmp(&self.estimate).then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+/// Returns the cheapest goal reachable from `start` with Dijkstra's
+/// algorithm, expanding states in order of the cost of reaching them.
+///
+/// # Arguments
+/// * `start` - The state to start from.
+/// * `successors` - Returns the states reachable in one step from a state,
+///   along with the cost of the step, which must not be negative.
+/// * `is_goal` - Returns whether a state is a goal.
+///
+/// # Returns
+/// The first goal reached and the cost of reaching it, or `None` if no goal
+/// is reachable.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::search::dijkstra;
+/// // Reaching 10 from 1 by doubling for 3 or adding 1 for 1.
+/// let successors = |&n: &u32| [(n * 2, 3), (n + 1, 1)].into_iter().filter(|&(n, _)| n <= 10);
+/// assert_eq!(dijkstra(1, successors, |&n| n == 10), Some((10, 7)));
+/// ```
+pub fn dijkstra<S, C, I>(start: S, successors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> Option<(S, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Returns the cheapest goal reachable from `start` with the A* algorithm,
+/// expanding states in order of the cost of reaching them plus an estimate
+/// of the remaining cost.
+///
+/// The better the estimate, the fewer states are expanded. With an estimate
+/// of zero, this is [`dijkstra`].
+///
+/// # Arguments
+/// * `start` - The state to start from.
+/// * `successors` - Returns the states reachable in one step from a state,
+///   along with the cost of the step, which must not be negative.
+/// * `heuristic` - Estimates the cost of reaching a goal from a state. It
+///   must never overestimate it, or the goal found may not be the cheapest.
+/// * `is_goal` - Returns whether a state is a goal.
+///
+/// # Returns
+/// The first goal reached and the cost of reaching it, or `None` if no goal
+/// is reachable.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::search::astar;
+/// // Walking on a grid from (0, 0) to (3, 4), estimating the Manhattan distance.
+/// let successors = |&(x, y): &(i32, i32)| [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)].map(|next| (next, 1));
+/// let heuristic = |&(x, y): &(i32, i32)| (3 - x).abs() + (4 - y).abs();
+/// assert_eq!(astar((0, 0), successors, heuristic, |&state| state == (3, 4)), Some(((3, 4), 7)));
+/// ```
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(S, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_path(start, successors, heuristic, is_goal).map(|(mut path, cost)| (path.pop().expect("paths are never empty"), cost))
+}
+
+/// Returns the cheapest path from `start` to a goal with the A* algorithm,
+/// as [`astar`] finds it, remembering how each state was reached.
+///
+/// # Arguments
+/// The same as [`astar`].
+///
+/// # Returns
+/// The states along the path, from `start` to the goal reached, and the
+/// cost of the path, or `None` if no goal is reachable.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::search::astar_path;
+/// // Reaching 10 from 1 by doubling for 3 or adding 1 for 1.
+/// let successors = |&n: &u32| [(n * 2, 3), (n + 1, 1)].into_iter().filter(|&(n, _)| n <= 10);
+/// assert_eq!(astar_path(1, successors, |_| 0, |&n| n == 10), Some((vec![1, 2, 3, 4, 5, 10], 7)));
+/// ```
+pub fn astar_path<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // The cheapest known cost of reaching each state seen so far, and the
+    // state it is reached from that way.
+    let mut costs = HashMap::from([(start.clone(), (C::default(), None::<S>))]);
+    let mut queue = BinaryHeap::from([Queued { estimate: heuristic(&start), cost: C::default(), state: start }]);
+    let mut expanded = 0usize;
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        // A state is queued again whenever a cheaper way to it is found,
+        // which leaves the former entries behind to be skipped.
+        if costs.get(&state).is_some_and(|&(best, _)| best < cost) { continue; }
+        if is_goal(&state) {
+            tracing::debug!(expanded, queued = queue.len(), "reached a goal");
+            let mut path = vec![state];
+            while let Some((_, Some(previous))) = costs.get(path.last().expect("paths are never empty")) {
+                path.push(previous.clone());
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+        expanded += 1;
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            match costs.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= cost => continue,
+                Entry::Occupied(mut entry) => { entry.insert((cost, Some(state.clone()))); }
+                Entry::Vacant(entry) => { entry.insert((cost, Some(state.clone()))); }
+            }
+            queue.push(Queued { estimate: cost + heuristic(&next), cost, state: next });
+        }
+    }
+    tracing::debug!(expanded, "no goal is reachable");
+    None
+}
@@ -0,0 +1,68 @@
+//! Summary statistics of repeated measurements, such as the runtimes of a
+//! part run several times, to tell a stable timing from noise without a
+//! full benchmark harness.
+
+use std::fmt;
+use std::time::Duration;
+
+/// The minimum, median, mean and standard deviation of a set of durations.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use aoc2015::util::stats::Stats;
+/// let samples = [4, 1, 3, 2].map(Duration::from_millis);
+/// let stats = Stats::of(&samples).unwrap();
+/// assert_eq!(stats.count, 4);
+/// assert_eq!(stats.min, Duration::from_millis(1));
+/// assert_eq!(stats.median, Duration::from_micros(2500));
+/// assert_eq!(stats.mean, Duration::from_micros(2500));
+/// assert_eq!(stats.stddev.as_micros(), 1290);
+/// assert_eq!(stats.to_string(), "min 1.000ms, median 2.500ms, mean 2.500ms ± 1.291ms over 4 runs");
+/// assert_eq!(Stats::of(&[]), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of durations.
+    pub count: usize,
+    /// The shortest duration.
+    pub min: Duration,
+    /// The middle duration, or the mean of the two middle ones for an even
+    /// count.
+    pub median: Duration,
+    /// The mean duration.
+    pub mean: Duration,
+    /// The sample standard deviation of the durations, zero for a single
+    /// one.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarizes a set of durations, in any order.
+    ///
+    /// # Returns
+    /// The [`Stats`], or `None` if there are no durations.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len();
+        let min = *sorted.first()?;
+        let median = match count % 2 {
+            1 => sorted[count / 2],
+            _ => (sorted[count / 2 - 1] + sorted[count / 2]) / 2,
+        };
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        let variance = match count {
+            1 => 0.0,
+            _ => sorted.iter().map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2)).sum::<f64>() / (count - 1) as f64,
+        };
+        Some(Stats { count, min, median, mean, stddev: Duration::from_secs_f64(variance.sqrt()) })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let runs = if self.count == 1 { "run" } else { "runs" };
+        write!(f, "min {:.3?}, median {:.3?}, mean {:.3?} ± {:.3?} over {} {runs}", self.min, self.median, self.mean, self.stddev, self.count)
+    }
+}
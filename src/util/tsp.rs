@@ -0,0 +1,439 @@
+//! Searches for the best route through every node of a small weighted
+//! graph, as in the travelling salesman problem.
+//!
+//! Two [`Algorithm`]s are provided: trying every permutation is faster up
+//! to about 6 nodes, while Held–Karp wins from 7 on and is hundreds of
+//! times faster at 12, as `cargo bench --bench tsp` shows.
+//!
+//! Each algorithm rejects graphs larger than its [`Algorithm::max_nodes`]
+//! rather than searching them: brute force would take hours past 12
+//! nodes, and Held–Karp needs memory for every subset of the nodes.
+
+use std::ops::Add;
+use crate::error::AocError;
+use crate::util::combinatorics::for_each_permutation;
+
+/// The most nodes a route is searched through, whose Held–Karp table holds
+/// 20 million weights.
+pub const MAX_NODES: usize = 20;
+
+/// An algorithm searching for the best route through every node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    /// Tries every permutation of the nodes, in `O(n! * n)` time and `O(n)`
+    /// memory.
+    BruteForce,
+    /// The Held–Karp dynamic programming algorithm, in `O(2^n * n^2)` time
+    /// and `O(2^n * n)` memory.
+    #[default]
+    HeldKarp,
+}
+
+/// The state of the Held–Karp algorithm: the best weight of a route for
+/// every set of visited nodes and every last node of the route.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::tsp::Routes;
+/// let weights = vec![
+///     vec![None, Some(1), Some(5)],
+///     vec![Some(1), None, Some(2)],
+///     vec![Some(5), Some(2), None],
+/// ];
+/// let routes = Routes::compute(&weights, &[0], u32::min).unwrap();
+/// assert_eq!(routes.get(0b011, 1), Some(1));
+/// assert_eq!(routes.get(0b111, 2), Some(3));
+/// assert_eq!(routes.get(0b110, 2), None);
+///
+/// let too_many = vec![vec![Some(1); 21]; 21];
+/// assert!(Routes::compute(&too_many, &[0], u32::min).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Routes<T> {
+    /// The number of nodes.
+    n: usize,
+    /// The best weights, indexed by `set * n + last`.
+    weights: Vec<Option<T>>,
+}
+
+impl<T> Routes<T>
+where
+    T: Copy + Add<Output = T> + Default,
+{
+    /// Computes the best route over every subset of nodes.
+    ///
+    /// For every subset of nodes and every node in it, the best route
+    /// starting at one of `starts`, visiting exactly that subset and ending
+    /// at that node is computed from the best routes over the subset minus
+    /// that node.
+    ///
+    /// # Arguments
+    /// * `weights` - The weight of the edge between each pair of nodes, if any.
+    /// * `starts` - The nodes a route may start at.
+    /// * `better` - Picks the better of two route weights, such as `min` or `max`.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are more than
+    /// [`MAX_NODES`] nodes.
+    pub fn compute(weights: &[Vec<Option<T>>], starts: &[usize], better: fn(T, T) -> T) -> Result<Self, AocError> {
+        let n = weights.len();
+        check_nodes(n, MAX_NODES)?;
+        let mut routes = Routes { n, weights: vec![None; n << n] };
+        for &start in starts {
+            routes.weights[(1 << start) * n + start] = Some(T::default());
+        }
+        for set in 1..1usize << n {
+            for (last, edges) in weights.iter().enumerate() {
+                let Some(weight) = routes.get(set, last) else { continue };
+                for next in (0..n).filter(|next| set & (1 << next) == 0) {
+                    let Some(edge) = edges[next] else { continue };
+                    let candidate = weight + edge;
+                    let best = &mut routes.weights[(set | (1 << next)) * n + next];
+                    *best = Some(best.map_or(candidate, |best| better(best, candidate)));
+                }
+            }
+        }
+        Ok(routes)
+    }
+
+    /// Returns the best weight of a route visiting exactly the nodes of
+    /// `set`, as a bitmask, and ending at `last`, or `None` if there is none.
+    pub fn get(&self, set: usize, last: usize) -> Option<T> {
+        self.weights[set * self.n + last]
+    }
+
+    /// Returns the best weight of a route visiting every node, indexed by
+    /// its last node.
+    pub fn complete(&self) -> &[Option<T>] {
+        &self.weights[self.weights.len() - self.n..]
+    }
+}
+
+impl<T> Routes<T>
+where
+    T: Copy + Add<Output = T> + Default + PartialEq,
+{
+    /// Returns the nodes of a best route visiting exactly the nodes of
+    /// `set`, as a bitmask, and ending at `last`, in order.
+    ///
+    /// The route is found backwards: each node is preceded by a node whose
+    /// best route over the rest of the set, plus the edge between them, has
+    /// the best weight of the route.
+    ///
+    /// # Arguments
+    /// * `weights` - The weights the routes were computed from.
+    /// * `set` - The nodes of the route, as a bitmask.
+    /// * `last` - The last node of the route.
+    ///
+    /// # Returns
+    /// The nodes of the route, or `None` if there is none.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::tsp::Routes;
+    /// let weights = vec![
+    ///     vec![None, Some(1), Some(5)],
+    ///     vec![Some(1), None, Some(2)],
+    ///     vec![Some(5), Some(2), None],
+    /// ];
+    /// let routes = Routes::compute(&weights, &[0], u32::min).unwrap();
+    /// assert_eq!(routes.route(&weights, 0b111, 2), Some(vec![0, 1, 2]));
+    /// assert_eq!(routes.route(&weights, 0b110, 2), None);
+    /// ```
+    pub fn route(&self, weights: &[Vec<Option<T>>], set: usize, last: usize) -> Option<Vec<usize>> {
+        let mut weight = self.get(set, last)?;
+        let (mut set, mut last) = (set, last);
+        let mut route = vec![last];
+        while set != 1 << last {
+            set &= !(1 << last);
+            last = (0..self.n)
+                .filter(|previous| set & (1 << previous) != 0)
+                .find(|&previous| self.get(set, previous).zip(weights[previous][last]).is_some_and(|(route, edge)| route + edge == weight))?;
+            weight = self.get(set, last)?;
+            route.push(last);
+        }
+        route.reverse();
+        Some(route)
+    }
+}
+
+/// Checks that a route can be searched through `n` nodes, at most `max`.
+fn check_nodes(n: usize, max: usize) -> Result<(), AocError> {
+    if n > max {
+        return Err(AocError::unsolvable(format!("{n} nodes are too many to search, at most {max} are supported")));
+    }
+    Ok(())
+}
+
+/// Returns the best of the candidate weights, along with what they belong
+/// to, keeping the first of equally good candidates.
+fn best_of<T: Copy + PartialEq, K>(candidates: impl Iterator<Item = (K, T)>, better: fn(T, T) -> T) -> Option<(K, T)> {
+    candidates.reduce(|best, candidate| if better(best.1, candidate.1) == best.1 { best } else { candidate })
+}
+
+impl Algorithm {
+    /// Returns the most nodes this algorithm searches a route through: 12
+    /// for brute force, which then takes about a second, and [`MAX_NODES`]
+    /// for Held–Karp.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::tsp::Algorithm;
+    /// let weights = vec![vec![Some(1); 13]; 13];
+    /// assert_eq!(Algorithm::BruteForce.max_nodes(), 12);
+    /// assert!(Algorithm::BruteForce.best_path(&weights, u32::min).is_err());
+    /// assert_eq!(Algorithm::HeldKarp.best_path(&weights, u32::min).unwrap(), Some(12));
+    /// ```
+    pub fn max_nodes(self) -> usize {
+        match self {
+            Algorithm::BruteForce => 12,
+            Algorithm::HeldKarp => MAX_NODES,
+        }
+    }
+
+    /// Returns the weight of the best path visiting every node exactly
+    /// once, starting and ending anywhere.
+    ///
+    /// # Arguments
+    /// * `weights` - The weight of the edge between each pair of nodes, if any.
+    /// * `better` - Picks the better of two path weights, such as `min` or `max`.
+    ///
+    /// # Returns
+    /// The weight of the best path, or `None` if no path visits every node.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are more than
+    /// [`Algorithm::max_nodes`] nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::tsp::Algorithm;
+    /// let weights = vec![
+    ///     vec![None, Some(1), Some(5)],
+    ///     vec![Some(1), None, Some(2)],
+    ///     vec![Some(5), Some(2), None],
+    /// ];
+    /// for algorithm in [Algorithm::BruteForce, Algorithm::HeldKarp] {
+    ///     assert_eq!(algorithm.best_path(&weights, u32::max).unwrap(), Some(7));
+    /// }
+    /// ```
+    pub fn best_path<T>(self, weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<T>, AocError>
+    where
+        T: Copy + Add<Output = T> + Default,
+    {
+        check_nodes(weights.len(), self.max_nodes())?;
+        if weights.is_empty() { return Ok(None); }
+        let mut nodes: Vec<usize> = (0..weights.len()).collect();
+        Ok(match self {
+            Algorithm::BruteForce => best_permutation(&mut nodes, weights, better, |_| Some(T::default())),
+            Algorithm::HeldKarp => Routes::compute(weights, &nodes, better)?.complete().iter().flatten().copied().reduce(better),
+        })
+    }
+
+    /// Returns the weight of the best cycle visiting every node exactly
+    /// once.
+    ///
+    /// As every cycle goes through the first node, only cycles starting
+    /// there are searched, which is `n` times fewer than for
+    /// [`Algorithm::best_path`].
+    ///
+    /// # Arguments
+    /// * `weights` - The weight of the edge between each pair of nodes, if any.
+    /// * `better` - Picks the better of two cycle weights, such as `min` or `max`.
+    ///
+    /// # Returns
+    /// The weight of the best cycle, or `None` if no cycle visits every node.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are more than
+    /// [`Algorithm::max_nodes`] nodes.
+    pub fn best_cycle<T>(self, weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<T>, AocError>
+    where
+        T: Copy + Add<Output = T> + Default,
+    {
+        check_nodes(weights.len(), self.max_nodes())?;
+        Ok(match (weights.len(), self) {
+            (0, _) => None,
+            (1, _) => Some(T::default()),
+            (n, Algorithm::BruteForce) => {
+                // The first node is left out of the permutations, and both
+                // of its edges are added to the path through the others.
+                let mut nodes: Vec<usize> = (1..n).collect();
+                best_permutation(&mut nodes, weights, better, |path| {
+                    Some(weights[0][path[0]]? + weights[path[n - 2]][0]?)
+                })
+            }
+            (_, Algorithm::HeldKarp) => Routes::compute(weights, &[0], better)?.complete().iter().enumerate()
+                .filter_map(|(last, weight)| Some((*weight)? + weights[last][0]?))
+                .reduce(better),
+        })
+    }
+}
+
+/// Returns the best weight of a path through `nodes` in any order, plus
+/// what `extra` adds to each path.
+///
+/// # Returns
+/// The best weight, or `None` if no path has every edge it needs.
+fn best_permutation<T>(
+    nodes: &mut [usize],
+    weights: &[Vec<Option<T>>],
+    better: fn(T, T) -> T,
+    extra: impl Fn(&[usize]) -> Option<T>,
+) -> Option<T>
+where
+    T: Copy + Add<Output = T> + Default,
+{
+    let mut best = None;
+    for_each_permutation(nodes, |path| {
+        let weight = path.windows(2).try_fold(T::default(), |weight, edge| Some(weight + weights[edge[0]][edge[1]]?));
+        if let Some(weight) = weight.zip(extra(path)).map(|(weight, extra)| weight + extra) {
+            best = Some(best.map_or(weight, |best| better(best, weight)));
+        }
+    });
+    best
+}
+
+/// Returns the weight of the best path visiting every node exactly once,
+/// starting and ending anywhere, found with [`Algorithm::HeldKarp`].
+///
+/// # Arguments
+/// * `weights` - The weight of the edge between each pair of nodes, if any.
+/// * `better` - Picks the better of two path weights, such as `min` or `max`.
+///
+/// # Returns
+/// The weight of the best path, or `None` if no path visits every node.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if there are more than
+/// [`MAX_NODES`] nodes.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::tsp::best_path;
+/// let weights = vec![
+///     vec![None, Some(1), Some(5)],
+///     vec![Some(1), None, Some(2)],
+///     vec![Some(5), Some(2), None],
+/// ];
+/// assert_eq!(best_path(&weights, u32::min).unwrap(), Some(3));
+/// assert_eq!(best_path(&weights, u32::max).unwrap(), Some(7));
+/// ```
+pub fn best_path<T>(weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<T>, AocError>
+where
+    T: Copy + Add<Output = T> + Default,
+{
+    Algorithm::HeldKarp.best_path(weights, better)
+}
+
+/// Returns the best path visiting every node exactly once, starting and
+/// ending anywhere, found with [`Algorithm::HeldKarp`].
+///
+/// # Arguments
+/// * `weights` - The weight of the edge between each pair of nodes, if any.
+/// * `better` - Picks the better of two path weights, such as `min` or `max`.
+///
+/// # Returns
+/// The weight of the best path and its nodes in order, or `None` if no
+/// path visits every node.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if there are more than
+/// [`MAX_NODES`] nodes.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::tsp::best_path_route;
+/// let weights = vec![
+///     vec![None, Some(1), Some(5)],
+///     vec![Some(1), None, Some(2)],
+///     vec![Some(5), Some(2), None],
+/// ];
+/// assert_eq!(best_path_route(&weights, u32::min).unwrap(), Some((3, vec![2, 1, 0])));
+/// assert_eq!(best_path_route(&weights, u32::max).unwrap(), Some((7, vec![1, 2, 0])));
+/// ```
+pub fn best_path_route<T>(weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<(T, Vec<usize>)>, AocError>
+where
+    T: Copy + Add<Output = T> + Default + PartialEq,
+{
+    if weights.is_empty() { return Ok(None); }
+    let nodes: Vec<usize> = (0..weights.len()).collect();
+    let routes = Routes::compute(weights, &nodes, better)?;
+    let candidates = routes.complete().iter().enumerate().filter_map(|(last, weight)| Some((last, (*weight)?)));
+    Ok(best_of(candidates, better).and_then(|(last, weight)| Some((weight, routes.route(weights, (1 << weights.len()) - 1, last)?))))
+}
+
+/// Returns the best cycle visiting every node exactly once, found with
+/// [`Algorithm::HeldKarp`].
+///
+/// # Arguments
+/// * `weights` - The weight of the edge between each pair of nodes, if any.
+/// * `better` - Picks the better of two cycle weights, such as `min` or `max`.
+///
+/// # Returns
+/// The weight of the best cycle and its nodes in order, starting with the
+/// first node and without coming back to it, or `None` if no cycle visits
+/// every node.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if there are more than
+/// [`MAX_NODES`] nodes.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::tsp::best_cycle_route;
+/// let weights = vec![
+///     vec![None, Some(1), Some(5), Some(1)],
+///     vec![Some(1), None, Some(1), Some(5)],
+///     vec![Some(5), Some(1), None, Some(1)],
+///     vec![Some(1), Some(5), Some(1), None],
+/// ];
+/// assert_eq!(best_cycle_route(&weights, u32::min).unwrap(), Some((4, vec![0, 3, 2, 1])));
+/// ```
+pub fn best_cycle_route<T>(weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<(T, Vec<usize>)>, AocError>
+where
+    T: Copy + Add<Output = T> + Default + PartialEq,
+{
+    match weights.len() {
+        0 => Ok(None),
+        1 => Ok(Some((T::default(), vec![0]))),
+        n => {
+            let routes = Routes::compute(weights, &[0], better)?;
+            let candidates = routes.complete().iter().enumerate()
+                .filter_map(|(last, weight)| Some((last, (*weight)? + weights[last][0]?)));
+            Ok(best_of(candidates, better).and_then(|(last, weight)| Some((weight, routes.route(weights, (1 << n) - 1, last)?))))
+        }
+    }
+}
+
+/// Returns the weight of the best cycle visiting every node exactly once.
+///
+/// The cycle is found with [`Algorithm::HeldKarp`].
+///
+/// # Arguments
+/// * `weights` - The weight of the edge between each pair of nodes, if any.
+/// * `better` - Picks the better of two cycle weights, such as `min` or `max`.
+///
+/// # Returns
+/// The weight of the best cycle, or `None` if no cycle visits every node.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if there are more than
+/// [`MAX_NODES`] nodes.
+///
+/// # Examples
+/// ```
+/// use aoc2015::util::tsp::best_cycle;
+/// let weights = vec![
+///     vec![None, Some(1), Some(5)],
+///     vec![Some(1), None, Some(2)],
+///     vec![Some(5), Some(2), None],
+/// ];
+/// assert_eq!(best_cycle(&weights, u32::min).unwrap(), Some(8));
+/// ```
+pub fn best_cycle<T>(weights: &[Vec<Option<T>>], better: fn(T, T) -> T) -> Result<Option<T>, AocError>
+where
+    T: Copy + Add<Output = T> + Default,
+{
+    Algorithm::HeldKarp.best_cycle(weights, better)
+}
@@ -0,0 +1,135 @@
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+use aoc2015::error::AocError;
+use aoc2015::year2015::ex06::{self, Grid};
+use aoc2015::year2015::ex18;
+use aoc2015::solution::Part;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+/// The days whose grid can be visualized.
+pub const DAYS: [u32; 2] = [6, 18];
+
+/// The characters drawn for a block of lights, from all off to all on.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// The delay between two frames when the animation starts playing.
+const INITIAL_DELAY: Duration = Duration::from_millis(100);
+
+/// Puts the terminal in raw mode on an alternate screen, restoring it when
+/// dropped, even if the visualization fails.
+struct Screen {
+    stdout: Stdout,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen { stdout })
+    }
+
+    /// Draws a grid scaled down to the terminal, with a status line below.
+    fn draw(&mut self, grid: &Grid, status: &str) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let (columns, rows) = (usize::from(columns).max(1), usize::from(rows).max(2) - 1);
+        // Each character covers a block of block_width by block_height lights.
+        let block_width = grid.width().div_ceil(columns);
+        let block_height = grid.height().div_ceil(rows);
+        queue!(self.stdout, terminal::Clear(terminal::ClearType::All))?;
+        for (row, top) in (0..grid.height()).step_by(block_height).enumerate() {
+            let line: String = (0..grid.width()).step_by(block_width)
+                .map(|left| {
+                    let (right, bottom) = ((left + block_width).min(grid.width()), (top + block_height).min(grid.height()));
+                    let lit = (top..bottom).flat_map(|y| (left..right).map(move |x| (x, y)))
+                        .filter(|&(x, y)| grid.get(x, y))
+                        .count();
+                    let total = (right - left) * (bottom - top);
+                    SHADES[(lit * (SHADES.len() - 1)).div_ceil(total)]
+                })
+                .collect();
+            queue!(self.stdout, cursor::MoveTo(0, row as u16), style::Print(line))?;
+        }
+        queue!(self.stdout, cursor::MoveTo(0, rows as u16), style::Print(status))?;
+        self.stdout.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Returns the successive grids of one of the [`DAYS`] on an input, as
+/// computed by its solver.
+///
+/// # Errors
+/// Returns an [`AocError`] if the input cannot be parsed.
+fn frames(day: u32, part: Part, input: &str) -> Result<Box<dyn Iterator<Item = Grid>>, AocError> {
+    match day {
+        6 => Ok(Box::new(ex06::states(input)?)),
+        18 => Ok(Box::new(ex18::states(input, part == Part::B)?.take(ex18::STEPS + 1))),
+        _ => unreachable!("day {day} has no grid to visualize"),
+    }
+}
+
+/// Animates the grid of one of the [`DAYS`] on an input in the terminal,
+/// frame by frame.
+///
+/// The animation starts paused. Space plays or pauses it, the right arrow
+/// or `n` advances one frame, `+` and `-` change the speed, and `q` or
+/// Escape quits.
+///
+/// # Errors
+/// Returns an [`AocError`] if the input cannot be parsed, or the terminal
+/// fails.
+pub fn visualize(day: u32, part: Part, input: &str) -> Result<(), AocError> {
+    let mut frames = frames(day, part, input)?;
+    let Some(mut grid) = frames.next() else { return Ok(()) };
+    let mut screen = Screen::enter()?;
+    let (mut frame, mut playing, mut delay, mut done) = (0, false, INITIAL_DELAY, false);
+    loop {
+        let state = match (done, playing) {
+            (true, _) => "done",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+        let status = format!(
+            "ex{day:02}{part} frame {frame}, {} lights on, {state} - space: play/pause, n: step, +/-: speed, q: quit",
+            grid.count(),
+        );
+        screen.draw(&grid, &status)?;
+
+        let mut advance = false;
+        // While paused, the status is redrawn once a minute at most.
+        if event::poll(if playing { delay } else { Duration::from_secs(60) })? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                    KeyCode::Char(' ') => playing = !playing && !done,
+                    KeyCode::Char('n') | KeyCode::Right => advance = true,
+                    KeyCode::Char('+') => delay = (delay / 2).max(Duration::from_millis(1)),
+                    KeyCode::Char('-') => delay = (delay * 2).min(Duration::from_secs(2)),
+                    _ => {}
+                },
+                _ => {}
+            }
+        } else {
+            advance = playing;
+        }
+        if advance && !done {
+            match frames.next() {
+                Some(next) => {
+                    grid = next;
+                    frame += 1;
+                }
+                None => (done, playing) = (true, false),
+            }
+        }
+    }
+    Ok(())
+}
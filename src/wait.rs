@@ -0,0 +1,69 @@
+use std::io::{self, IsTerminal};
+use std::thread;
+use std::time::Duration;
+use chrono::Utc;
+use aoc2015::error::AocError;
+use aoc2015::input::{read_exercise_input, time_until_unlock, InputSource};
+use crate::scaffold;
+
+/// The delay between two updates of the countdown.
+const TICK: Duration = Duration::from_secs(1);
+
+/// The number of times the input is requested once the puzzle unlocks, as
+/// it may take a few seconds to be served.
+const ATTEMPTS: u32 = 10;
+
+/// Formats a duration as hours, minutes and seconds, such as `05:59:59`.
+fn countdown(left: Duration) -> String {
+    let seconds = left.as_secs();
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Sleeps until the puzzle of a day unlocks, then downloads its input and
+/// optionally creates its module.
+///
+/// The time left is shown on standard error, updated every second when it
+/// is a terminal. The input is requested again a few times if it is not
+/// served right after the unlock.
+///
+/// # Arguments
+/// * `year` - The year of the puzzle.
+/// * `day` - The day of the puzzle.
+/// * `new` - Whether to create the module of the day once its input is
+///   downloaded, as `aoc15 new` does.
+///
+/// # Errors
+/// Returns an [`AocError`] if the input cannot be downloaded or the module
+/// cannot be created.
+pub fn wait(year: u32, day: u32, new: bool) -> Result<(), AocError> {
+    let terminal = io::stderr().is_terminal();
+    let left = |now| time_until_unlock(year as i32, day, now).unwrap_or_else(|| unreachable!("day {day} is checked by the CLI"));
+    let remaining = left(Utc::now());
+    if !remaining.is_zero() && !terminal {
+        eprintln!("day {day} unlocks in {}", countdown(remaining));
+    }
+    loop {
+        let remaining = left(Utc::now());
+        if remaining.is_zero() { break; }
+        if terminal { eprint!("\rday {day} unlocks in {}", countdown(remaining)); }
+        thread::sleep(remaining.min(TICK));
+    }
+    if terminal { eprintln!("\rday {day} is unlocked        "); }
+    let mut attempt = 1;
+    let input = loop {
+        match read_exercise_input(year, day) {
+            Err(AocError::MissingInput { reason, .. }) if attempt < ATTEMPTS => {
+                tracing::debug!("input of day {day} not served yet: {reason}");
+                attempt += 1;
+                thread::sleep(TICK);
+            }
+            result => break result?,
+        }
+    };
+    let path = InputSource::Data.path(year, day).unwrap_or_else(|| unreachable!("data inputs are files"));
+    eprintln!("input of day {day} saved to {} ({} bytes)", path.display(), input.len());
+    if new {
+        scaffold::new_day(year, day)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! JavaScript bindings, to embed the solvers in a web page.
+//!
+//! The solvers only see the input passed to them and never touch the
+//! filesystem or the network, which are unavailable in library mode
+//! without the `net` feature.
+
+use std::panic::{self, AssertUnwindSafe};
+use wasm_bindgen::prelude::*;
+use crate::days;
+use crate::solution::Part;
+use crate::year2015;
+
+/// Solves a part of the puzzle of a day of 2015.
+///
+/// # Arguments
+/// * `day` - The day of the puzzle.
+/// * `part` - The part to solve, `'a'` or `'b'`.
+/// * `input` - The puzzle input.
+///
+/// # Returns
+/// The answer, formatted as the puzzle expects it.
+///
+/// # Errors
+/// Throws a JavaScript `Error` if the day or the part is unknown, if the
+/// input is malformed or has no answer, or if the solver panics.
+#[wasm_bindgen]
+pub fn solve(day: u32, part: char, input: &str) -> Result<String, JsError> {
+    #[cfg(target_arch = "wasm32")]
+    install_panic_hook();
+    run(day, part, input).map_err(|message| JsError::new(&message))
+}
+
+/// Makes panics throw a JavaScript `Error` carrying the panic message.
+///
+/// WebAssembly panics abort by default, which surfaces in JavaScript as an
+/// opaque `RuntimeError: unreachable`. The hook throws before that happens,
+/// so the caller gets the message instead.
+#[cfg(target_arch = "wasm32")]
+fn install_panic_hook() {
+    static HOOK: std::sync::Once = std::sync::Once::new();
+    HOOK.call_once(|| panic::set_hook(Box::new(|info| wasm_bindgen::throw_str(&info.to_string()))));
+}
+
+/// Solves a part of the puzzle of a day, catching panics where they unwind.
+///
+/// # Errors
+/// Returns the message of the error or of the panic.
+fn run(day: u32, part: char, input: &str) -> Result<String, String> {
+    let solution = days::find(year2015::YEAR, day).ok_or_else(|| format!("day {day} is not implemented"))?;
+    let part: Part = part.to_string().parse()?;
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(part, input.trim_end()))) {
+        Ok(result) => result.map(|answer| answer.to_string()).map_err(|err| err.to_string()),
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            Err(format!("day {day} panicked: {message}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves() {
+        assert_eq!(run(1, 'b', "()())\n"), Ok("5".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_day_and_part() {
+        assert!(run(15, 'a', "").is_err());
+        assert!(run(1, 'c', "").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use aoc2015::input::InputSource;
+use aoc2015::solution::{Part, Solution};
+use crate::output::{Emitter, Text};
+use crate::runner::{run_day_on, RunOptions};
+
+/// The delay between two checks of the input for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Returns when the file at `path` was last modified, or `None` if it does
+/// not exist or cannot be inspected.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Runs a day, then runs it again whenever its input changes, until
+/// interrupted.
+///
+/// The input is polled rather than watched through the file system
+/// notifications, which some editors defeat by replacing the file on
+/// saving. Errors, such as an input that does not parse yet, are printed
+/// and the day runs again on the next change. Changes to the sources are
+/// not noticed, as they need a new build: run this under `cargo watch -x
+/// 'run -- 7 --watch'` to rebuild on them too.
+///
+/// # Arguments
+/// * `solution` - The [`Solution`] to run.
+/// * `parts` - The parts to run.
+/// * `source` - Where to read the input from.
+/// * `path` - The file of the input, watched for changes.
+/// * `options` - How to run the day.
+pub fn watch(solution: &dyn Solution, parts: &[Part], source: &InputSource, path: &Path, options: RunOptions) -> ! {
+    loop {
+        match source.read(solution.year(), solution.day()).and_then(|input| run_day_on(solution, parts, &input, options)) {
+            Ok(outcomes) => outcomes.iter().for_each(|outcome| Text.outcome(outcome)),
+            Err(err) => Text.failure(solution.day(), None, &err),
+        }
+        // Taken after the run, which downloads the input if it is missing.
+        let last = modified(path);
+        eprintln!("watching {} for changes, press Ctrl-C to stop", path.display());
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if modified(path) != last { break; }
+        }
+    }
+}
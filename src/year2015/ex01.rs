@@ -0,0 +1,321 @@
+//! [Day 1: Not Quite Lisp](https://adventofcode.com/2015/day/1).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex01::{a_purist, b};
+//! assert_eq!(a_purist("(())").unwrap(), 0);
+//! assert_eq!(a_purist("(()(()(").unwrap(), 3);
+//! assert_eq!(a_purist(")())())").unwrap(), -3);
+//! assert_eq!(b(")").unwrap(), 1);
+//! assert_eq!(b("()())").unwrap(), 5);
+//! ```
+
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::AocError;
+use crate::solution::{Example, Implementation, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 1 }
+    fn title(&self) -> &str { "Not Quite Lisp" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a_purist(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(input.chars().enumerate().try_for_each(|(index, code)| decoder(index, code).map(drop))) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        match part {
+            Part::A => self.part_a(input),
+            Part::B => first_position(input, context.config.basement_floor).map(Answer::from),
+        }
+    }
+}
+
+/// The floor whose first position the second part finds.
+pub const BASEMENT: i32 = -1;
+
+/// The alternate implementations of the first part.
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "purist", part: Part::A, solve: |input| a_purist(input).map(Answer::from) },
+    Implementation { name: "readable", part: Part::A, solve: |input| a_readable(input).map(Answer::from) },
+    Implementation { name: "branchless", part: Part::A, solve: |input| a_branchless(input).map(Answer::from) },
+    Implementation { name: "chunked", part: Part::A, solve: |input| a_chunked(input).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "(())", answer: "0" },
+    Example { part: Part::A, input: "()()", answer: "0" },
+    Example { part: Part::A, input: "(((", answer: "3" },
+    Example { part: Part::A, input: "(()(()(", answer: "3" },
+    Example { part: Part::A, input: "))(((((", answer: "3" },
+    Example { part: Part::A, input: "())", answer: "-1" },
+    Example { part: Part::A, input: "))(", answer: "-1" },
+    Example { part: Part::A, input: ")))", answer: "-3" },
+    Example { part: Part::A, input: ")())())", answer: "-3" },
+    Example { part: Part::B, input: ")", answer: "1" },
+    Example { part: Part::B, input: "()())", answer: "5" },
+];
+
+pub fn a_purist(input: &str) -> Result<i32, AocError> {
+    input.chars().enumerate().try_fold(0, |acc, (index, code)| Ok(acc + decoder(index, code)?))
+}
+
+pub fn a_readable(input: &str) -> Result<i32, AocError> {
+    let mut floor = 0;
+    for (index, code) in input.chars().enumerate() {
+        floor += decoder(index, code)?;
+    }
+    Ok(floor)
+}
+
+fn decoder(index: usize, code: char) -> Result<i32, AocError> {
+    match code {
+        '(' => Ok(1),
+        ')' => Ok(-1),
+        _ => Err(AocError::parse_at(1, index + 1, format!("invalid instruction '{code}'"))),
+    }
+}
+
+/// Returns the direction of an instruction without branching: `(` is
+/// `0x28` and `)` is `0x29`, so the low bit of the byte selects `1` or
+/// `-1`.
+fn direction(byte: u8) -> i32 {
+    1 - 2 * i32::from(byte & 1)
+}
+
+/// Returns whether a byte is neither `(` nor `)`, without branching.
+fn is_invalid(byte: u8) -> bool {
+    byte | 1 != b')'
+}
+
+// Same as the first part, over the bytes of the input without branching on
+// the instructions: the floor is accumulated with [`direction`], and the
+// invalid instructions are only looked for once it is known.
+pub fn a_branchless(input: &str) -> Result<i32, AocError> {
+    let bytes = input.as_bytes();
+    let floor = bytes.iter().fold(0i32, |floor, &byte| floor.wrapping_add(direction(byte)));
+    if bytes.iter().fold(false, |invalid, &byte| invalid | is_invalid(byte)) {
+        // Reports the first invalid instruction.
+        return a_readable(input);
+    }
+    Ok(floor)
+}
+
+// Same as the first part, counting the `(` and `)` of the input many bytes
+// at a time with [`count_byte`]: the floor is their difference.
+pub fn a_chunked(input: &str) -> Result<i32, AocError> {
+    let bytes = input.as_bytes();
+    let (up, down) = (count_byte(bytes, b'('), count_byte(bytes, b')'));
+    if up + down != bytes.len() {
+        return a_readable(input);
+    }
+    let floor = i64::try_from(up).ok().zip(i64::try_from(down).ok()).and_then(|(up, down)| i32::try_from(up - down).ok());
+    floor.ok_or_else(|| AocError::unsolvable("the floor does not fit in an i32"))
+}
+
+/// Returns the number of occurrences of `needle` in `bytes`.
+///
+/// With the `simd` feature, on x86-64 processors supporting AVX2, detected
+/// at runtime, the bytes are compared 32 at a time. They are otherwise
+/// compared 8 at a time within a `u64`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex01::count_byte;
+/// assert_eq!(count_byte(b"(()(()(", b'('), 5);
+/// ```
+pub fn count_byte(bytes: &[u8], needle: u8) -> usize {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the running processor.
+        return unsafe { avx2::count_byte(bytes, needle) };
+    }
+    count_byte_swar(bytes, needle)
+}
+
+/// Returns the number of occurrences of `needle` in `bytes`, 8 bytes at a
+/// time.
+///
+/// The bytes equal to `needle` are those that XOR to zero with it. Adding
+/// `0x7f` to the low 7 bits of a byte sets its high bit unless they are
+/// all zero, without carrying into the next byte, so the high bit ends up
+/// clear exactly in the zero bytes.
+fn count_byte_swar(bytes: &[u8], needle: u8) -> usize {
+    const LOW_BITS: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+    let pattern = u64::from_ne_bytes([needle; 8]);
+    let chunks = bytes.chunks_exact(8);
+    let remainder = chunks.remainder();
+    let mut count = 0;
+    for chunk in chunks {
+        let word = u64::from_ne_bytes(chunk.try_into().unwrap_or_else(|_| unreachable!("chunks have 8 bytes"))) ^ pattern;
+        let nonzero = ((word & LOW_BITS) + LOW_BITS) | word;
+        count += (!nonzero & !LOW_BITS).count_ones() as usize;
+    }
+    count + remainder.iter().filter(|&&byte| byte == needle).count()
+}
+
+/// Calls `visit` with the 0-based position and the direction of each
+/// instruction read from `input`, one buffer at a time, until it returns
+/// `false`. Trailing whitespace is ignored.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] locating the first invalid byte, or an
+/// [`AocError::Io`] if the input cannot be read.
+fn for_each_direction(mut input: impl BufRead, mut visit: impl FnMut(usize, i32) -> bool) -> Result<(), AocError> {
+    let mut position = 0;
+    let mut whitespace = None;
+    loop {
+        let buffer = input.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        for &byte in buffer {
+            if byte.is_ascii_whitespace() {
+                whitespace.get_or_insert((position, byte));
+            } else if let Some((start, space)) = whitespace {
+                return decoder(start, char::from(space)).map(drop);
+            } else if is_invalid(byte) {
+                return decoder(position, char::from(byte)).map(drop);
+            } else if !visit(position, direction(byte)) {
+                return Ok(());
+            }
+            position += 1;
+        }
+        let len = buffer.len();
+        input.consume(len);
+    }
+}
+
+// Same as the first part, reading the instructions one buffer at a time,
+// in constant memory.
+pub fn a_stream(input: impl BufRead) -> Result<i32, AocError> {
+    let mut floor = 0i32;
+    for_each_direction(input, |_, direction| {
+        floor += direction;
+        true
+    })?;
+    Ok(floor)
+}
+
+pub fn b(input: &str) -> Result<usize, AocError> {
+    first_position(input, BASEMENT)
+}
+
+/// Returns the position of the first instruction taking Santa to `target`.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if an instruction is invalid, or an
+/// [`AocError::Unsolvable`] if Santa never reaches `target`.
+fn first_position(input: &str, target: i32) -> Result<usize, AocError> {
+    let mut floor = 0;
+    for (index, code) in input.chars().enumerate() {
+        floor += decoder(index, code)?;
+        if floor == target {
+            return Ok(index + 1);
+        }
+    }
+    match target {
+        BASEMENT => Err(AocError::unsolvable("Santa never enters the basement")),
+        _ => Err(AocError::unsolvable(format!("Santa never reaches floor {target}"))),
+    }
+}
+
+// Same as the second part, reading the instructions one buffer at a time,
+// in constant memory.
+pub fn b_stream(input: impl BufRead) -> Result<usize, AocError> {
+    let mut floor = 0;
+    let mut basement = None;
+    for_each_direction(input, |position, direction| {
+        floor += direction;
+        if floor == BASEMENT {
+            basement = Some(position + 1);
+        }
+        basement.is_none()
+    })?;
+    basement.ok_or_else(|| AocError::unsolvable("Santa never enters the basement"))
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod avx2 {
+    use std::arch::x86_64::*;
+
+    /// Counts the occurrences of `needle` in `bytes` 32 at a time:
+    /// `vpcmpeqb` sets the matching bytes, whose high bits `vpmovmskb`
+    /// gathers into a 32-bit mask.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn count_byte(bytes: &[u8], needle: u8) -> usize {
+        let pattern = _mm256_set1_epi8(needle as i8);
+        let chunks = bytes.chunks_exact(32);
+        let remainder = chunks.remainder();
+        let mut count = 0;
+        for chunk in chunks {
+            let matches = _mm256_cmpeq_epi8(_mm256_loadu_si256(chunk.as_ptr().cast()), pattern);
+            count += (_mm256_movemask_epi8(matches) as u32).count_ones() as usize;
+        }
+        count + super::count_byte_swar(remainder, needle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn streams() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+        assert_eq!(a_stream("(()\n".as_bytes()).unwrap(), 1);
+        assert!(matches!(a_stream("(( )".as_bytes()), Err(AocError::Parse { .. })));
+    }
+
+    #[test]
+    fn count_byte_matches_a_plain_count() {
+        let bytes: Vec<u8> = (0..1000u32).map(|index| [b'(', b')', 0x28 ^ 0x80, b'x'][(index * 7 % 11 % 4) as usize]).collect();
+        for len in [0, 7, 8, 31, 32, 33, 1000] {
+            for needle in [b'(', b')', 0xa8] {
+                let expected = bytes[..len].iter().filter(|&&byte| byte == needle).count();
+                assert_eq!(count_byte(&bytes[..len], needle), expected);
+                assert_eq!(count_byte_swar(&bytes[..len], needle), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_instructions_are_located_by_every_implementation() {
+        for implementation in IMPLEMENTATIONS {
+            let err = (implementation.solve)("(()x)").unwrap_err();
+            assert_eq!(err.to_string(), "line 1, column 4: invalid instruction 'x'", "{}", implementation.name);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! [Day 2: I Was Told There Would Be No Math](https://adventofcode.com/2015/day/2).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex02::{a, b};
+//! assert_eq!(a("2x3x4").unwrap(), 58);
+//! assert_eq!(a("1x1x10").unwrap(), 43);
+//! assert_eq!(b("2x3x4").unwrap(), 34);
+//! assert_eq!(b("1x1x10").unwrap(), 14);
+//! ```
+
+use std::io::{BufRead, Write};
+use memchr::{memchr, memchr_iter};
+use rayon::prelude::*;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Example, Implementation, Part, Solution};
+use crate::util::parse::fields;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 2 }
+    fn title(&self) -> &str { "I Was Told There Would Be No Math" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse_lines(input, parse_line).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+
+    fn solve_explained(&self, part: Part, input: &str, _context: &Context, out: &mut dyn Write) -> Result<Answer, AocError> {
+        explain(part, input, out).map(Answer::from)
+    }
+}
+
+/// The alternate implementations of both parts.
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "lines", part: Part::A, solve: |input| a(input).map(Answer::from) },
+    Implementation { name: "batch", part: Part::A, solve: |input| a_batch(input).map(Answer::from) },
+    Implementation { name: "lines", part: Part::B, solve: |input| b(input).map(Answer::from) },
+    Implementation { name: "batch", part: Part::B, solve: |input| b_batch(input).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "2x3x4", answer: "58" },
+    Example { part: Part::A, input: "1x1x10", answer: "43" },
+    Example { part: Part::B, input: "2x3x4", answer: "34" },
+    Example { part: Part::B, input: "1x1x10", answer: "14" },
+];
+
+/// Parses the dimensions of a present, such as `2x3x4`.
+fn parse_line(line: &str) -> Result<[u32; 3], String> {
+    fields(line, "x")
+}
+
+/// Parses a decimal number from bytes, optionally preceded by `+` as
+/// accepted by `u32::from_str`.
+///
+/// # Returns
+/// The number, or `None` if there are no digits, anything but digits, or
+/// the number overflows.
+fn parse_number(digits: &[u8]) -> Option<u32> {
+    let digits = digits.strip_prefix(b"+").unwrap_or(digits);
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u32, |number, &digit| {
+        let digit = digit.wrapping_sub(b'0');
+        if digit > 9 { return None; }
+        number.checked_mul(10)?.checked_add(u32::from(digit))
+    })
+}
+
+/// Parses the dimensions of a present from the bytes of its line, such as
+/// `2x3x4`, finding the separators with `memchr`.
+///
+/// # Returns
+/// The dimensions, or `None` if the line is malformed.
+fn parse_dimensions(line: &[u8]) -> Option<[u32; 3]> {
+    let first = memchr(b'x', line)?;
+    let second = first + 1 + memchr(b'x', &line[first + 1..])?;
+    Some([
+        parse_number(&line[..first])?,
+        parse_number(&line[first + 1..second])?,
+        parse_number(&line[second + 1..])?,
+    ])
+}
+
+/// Parses the dimensions of every present in a single pass over the bytes
+/// of the input, without allocating for each line.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] for the first malformed line, described
+/// as by the line by line parser.
+fn parse_batch(input: &str) -> Result<Vec<[u32; 3]>, AocError> {
+    let bytes = input.as_bytes();
+    let mut presents = Vec::with_capacity(bytes.len() / 6 + 1);
+    let mut start = 0;
+    for end in memchr_iter(b'\n', bytes).chain([bytes.len()]) {
+        let line = &bytes[start..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        match parse_dimensions(line) {
+            Some(present) => presents.push(present),
+            // Reports the first malformed line as the other parsers do.
+            None => return parse_lines(input, parse_line).map(|_| unreachable!("line {} is malformed", presents.len() + 1)),
+        }
+        start = end + 1;
+    }
+    Ok(presents)
+}
+
+/// Adds up the amount needed by every present, in parallel and as a
+/// `u64`, so that huge inputs do not overflow.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if the amount of a present does not
+/// fit in a `u32`, or the total in a `u64`.
+fn total_parallel(presents: &[[u32; 3]], amount: fn([u32; 3]) -> Option<u32>) -> Result<u64, AocError> {
+    presents.par_iter()
+        .map(|&present| amount(present).map(u64::from))
+        .try_reduce(|| 0, |total, amount| total.checked_add(amount))
+        .ok_or_else(|| AocError::unsolvable("the total is too large"))
+}
+
+/// Returns the paper needed to wrap a present, or `None` on overflow.
+fn wrapping_paper([w, h, l]: [u32; 3]) -> Option<u32> {
+    let mut areas = [w.checked_mul(h)?, w.checked_mul(l)?, h.checked_mul(l)?];
+    areas.sort();
+    let sides = areas[0].checked_add(areas[1])?.checked_add(areas[2])?;
+    sides.checked_mul(2)?.checked_add(areas[0])
+}
+
+/// Returns the ribbon needed to tie a present, or `None` on overflow.
+fn ribbon([w, h, l]: [u32; 3]) -> Option<u32> {
+    let mut half_perimeters = [w.checked_add(h)?, w.checked_add(l)?, h.checked_add(l)?];
+    half_perimeters.sort();
+    half_perimeters[0].checked_mul(2)?.checked_add(w.checked_mul(h)?.checked_mul(l)?)
+}
+
+/// Adds up the amount needed by every present.
+///
+/// # Errors
+/// Returns the first error of `presents`, or an [`AocError::Unsolvable`] if
+/// the total does not fit in a `u32`.
+fn total(presents: impl IntoIterator<Item = Result<[u32; 3], AocError>>, amount: fn([u32; 3]) -> Option<u32>) -> Result<u32, AocError> {
+    presents.into_iter().try_fold(0u32, |total, present| {
+        amount(present?).and_then(|amount| total.checked_add(amount))
+            .ok_or_else(|| AocError::unsolvable("the total is too large"))
+    })
+}
+
+/// Solves a part, writing what every present needs to `out`: the paper of
+/// its sides and the slack, or the ribbon around it and the bow.
+fn explain(part: Part, input: &str, out: &mut dyn Write) -> Result<u32, AocError> {
+    let presents = parse_lines(input, parse_line)?;
+    for &[w, h, l] in &presents {
+        // Computed in 64 bits, as the amounts may overflow before the total
+        // reports it.
+        let [w, h, l] = [w, h, l].map(u64::from);
+        match part {
+            Part::A => {
+                let (sides, slack) = (2 * (w * h + w * l + h * l), (w * h).min(w * l).min(h * l));
+                writeln!(out, "{w}x{h}x{l}: {sides} square feet of sides + {slack} of slack = {}", sides + slack)?;
+            }
+            Part::B => {
+                let (around, bow) = (2 * (w + h).min(w + l).min(h + l), w * h * l);
+                writeln!(out, "{w}x{h}x{l}: {around} feet around + {bow} for the bow = {}", around + bow)?;
+            }
+        }
+    }
+    let amount = match part {
+        Part::A => wrapping_paper,
+        Part::B => ribbon,
+    };
+    total(presents.into_iter().map(Ok), amount)
+}
+
+pub fn a(input: &str) -> Result<u32, AocError> {
+    total(parse_lines(input, parse_line)?.into_iter().map(Ok), wrapping_paper)
+}
+
+// Same as the first part, reading the presents one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u32, AocError> {
+    total(parse_stream(input, parse_line), wrapping_paper)
+}
+
+// Same as the first part, parsing every present in one pass over the input
+// and adding up their paper in parallel, for huge inputs.
+pub fn a_batch(input: &str) -> Result<u64, AocError> {
+    total_parallel(&parse_batch(input)?, wrapping_paper)
+}
+
+pub fn b(input: &str) -> Result<u32, AocError> {
+    total(parse_lines(input, parse_line)?.into_iter().map(Ok), ribbon)
+}
+
+// Same as the second part, reading the presents one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u32, AocError> {
+    total(parse_stream(input, parse_line), ribbon)
+}
+
+// Same as the second part, parsing every present in one pass over the
+// input and adding up their ribbon in parallel, for huge inputs.
+pub fn b_batch(input: &str) -> Result<u64, AocError> {
+    total_parallel(&parse_batch(input)?, ribbon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn stream() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn batch_parser_agrees_with_the_line_parser() {
+        let input = "2x3x4\r\n1x1x10\n30x+20x10";
+        assert_eq!(parse_batch(input).unwrap(), parse_lines(input, parse_line).unwrap());
+        for malformed in ["2x3x4\n1x1", "2x3x4\n\n1x1x1", "2x3x-4", "2x3x+", "2xx3", "99999999999x1x1", "1x1x1x1"] {
+            let expected = parse_lines(malformed, parse_line).unwrap_err().to_string();
+            assert_eq!(parse_batch(malformed).unwrap_err().to_string(), expected, "{malformed}");
+        }
+    }
+
+    #[test]
+    fn batch_totals_do_not_overflow() {
+        let input = vec!["1000x1000x1000"; 1000].join("\n");
+        assert!(a(&input).is_err());
+        assert_eq!(a_batch(&input).unwrap(), 7_000_000_000);
+    }
+}
@@ -0,0 +1,309 @@
+//! [Day 3: Perfectly Spherical Houses in a Vacuum](https://adventofcode.com/2015/day/3).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex03::{a, b};
+//! assert_eq!(a(">").unwrap(), 2);
+//! assert_eq!(a("^>v<").unwrap(), 4);
+//! assert_eq!(b("^v").unwrap(), 3);
+//! assert_eq!(b("^v^v^v^v^v").unwrap(), 11);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Part, Solution};
+use crate::util::grid::Grid;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 3 }
+    fn title(&self) -> &str { "Perfectly Spherical Houses in a Vacuum" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(input.chars().enumerate().try_for_each(|(index, code)| step(&mut (0, 0), index, code))) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn render(&self, part: Part, input: &str) -> Option<Result<Grid<u8>, AocError>> {
+        Some(render(input, part))
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: ">", answer: "2" },
+    Example { part: Part::A, input: "^>v<", answer: "4" },
+    Example { part: Part::A, input: "^v^v^v^v^v", answer: "2" },
+    Example { part: Part::B, input: "^v", answer: "3" },
+    Example { part: Part::B, input: "^>v<", answer: "3" },
+    Example { part: Part::B, input: "^v^v^v^v^v", answer: "11" },
+];
+
+/// Returns how many times each house is visited by one of `santas` taking
+/// turns following the directions, by `(x, y)` coordinates relative to the
+/// starting house.
+///
+/// # Arguments
+/// * `input` - The directions of every Santa.
+/// * `santa` - The 0-based index of the Santa to follow.
+/// * `santas` - How many Santas take turns.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex03::visit_counts;
+/// let counts = visit_counts("^v^v", 0, 1).unwrap();
+/// assert_eq!(counts[&(0, 0)], 3);
+/// assert_eq!(counts[&(0, -1)], 2);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions contain anything but
+/// `^`, `v`, `<` and `>`, or an [`AocError::Unsolvable`] if `santa` is not
+/// one of the `santas`.
+pub fn visit_counts(input: &str, santa: usize, santas: usize) -> Result<HashMap<(i64, i64), u32>, AocError> {
+    if santa >= santas {
+        return Err(AocError::unsolvable(format!("there is no Santa {santa} out of {santas}")));
+    }
+    let mut position = (0, 0);
+    let mut houses = HashMap::from([(position, 1)]);
+    for (index, code) in input.chars().enumerate().skip(santa).step_by(santas) {
+        step(&mut position, index, code)?;
+        *houses.entry(position).or_insert(0) += 1;
+    }
+    Ok(houses)
+}
+
+/// Moves a Santa from `position` following the direction `code`, the
+/// `index`-th of the input.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if `code` is not `^`, `v`, `<` or `>`.
+fn step(position: &mut (i64, i64), index: usize, code: char) -> Result<(), AocError> {
+    match code {
+        '^' => position.1 -= 1,
+        'v' => position.1 += 1,
+        '<' => position.0 -= 1,
+        '>' => position.0 += 1,
+        _ => return Err(AocError::parse_at(1, index + 1, format!("invalid direction '{code}'"))),
+    }
+    Ok(())
+}
+
+/// The houses visited by a group of Santas, as `(x, y)` coordinates
+/// relative to the starting house, which they all visit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// The houses visited by each Santa, by index.
+    pub by_santa: Vec<HashSet<(i64, i64)>>,
+    /// The houses visited by at least one Santa.
+    pub houses: HashSet<(i64, i64)>,
+}
+
+impl Delivery {
+    /// Returns the number of houses visited by at least one Santa.
+    pub fn count(&self) -> usize {
+        self.houses.len()
+    }
+
+    /// Returns the number of houses visited by more than one Santa,
+    /// including the starting house if there are several Santas.
+    pub fn shared(&self) -> usize {
+        self.houses.iter()
+            .filter(|house| self.by_santa.iter().filter(|visited| visited.contains(house)).count() > 1)
+            .count()
+    }
+}
+
+/// Returns the houses visited by `santas` taking turns following the
+/// directions, in order: the first Santa follows the first direction, the
+/// second Santa the second one, and so on.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex03::solve;
+/// let delivery = solve("^>v<", 2).unwrap();
+/// assert_eq!(delivery.count(), 3);
+/// assert_eq!(delivery.by_santa[1].len(), 2);
+/// assert_eq!(delivery.shared(), 1);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions contain anything but
+/// `^`, `v`, `<` and `>`.
+///
+/// # Panics
+/// Panics if there are no Santas.
+pub fn solve(input: &str, santas: usize) -> Result<Delivery, AocError> {
+    assert!(santas > 0, "there must be at least one Santa");
+    solve_scheduled(input, santas, |index| index % santas)
+}
+
+/// Returns the houses visited by `santas` following the directions, each
+/// direction being followed by the Santa chosen by `schedule` from its
+/// 0-based index in the input.
+///
+/// # Examples
+/// Santa follows two directions, then Robo-Santa one:
+/// ```
+/// use aoc2015::year2015::ex03::solve_scheduled;
+/// let delivery = solve_scheduled("^^v^^v", 2, |index| usize::from(index % 3 == 2)).unwrap();
+/// assert_eq!(delivery.by_santa[0].len(), 5);
+/// assert_eq!(delivery.by_santa[1].len(), 3);
+/// assert_eq!(delivery.count(), 7);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions contain anything but
+/// `^`, `v`, `<` and `>`.
+///
+/// # Panics
+/// Panics if `schedule` chooses a Santa out of `0..santas`.
+pub fn solve_scheduled(input: &str, santas: usize, mut schedule: impl FnMut(usize) -> usize) -> Result<Delivery, AocError> {
+    let mut positions = vec![(0, 0); santas];
+    let mut by_santa = vec![HashSet::from([(0, 0)]); santas];
+    for (index, code) in input.chars().enumerate() {
+        let santa = schedule(index);
+        assert!(santa < santas, "direction {index} scheduled for Santa {santa} out of {santas}");
+        step(&mut positions[santa], index, code)?;
+        by_santa[santa].insert(positions[santa]);
+    }
+    let houses = by_santa.iter().flatten().copied().collect();
+    Ok(Delivery { by_santa, houses })
+}
+
+/// Returns the houses visited by one of `santas` taking turns following the
+/// directions, as `(x, y)` coordinates relative to the starting house.
+///
+/// # Arguments
+/// * `input` - The directions of every Santa.
+/// * `santa` - The 0-based index of the Santa to follow.
+/// * `santas` - How many Santas take turns.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex03::visited;
+/// assert_eq!(visited("^>v<", 0, 1).unwrap().len(), 4);
+/// assert_eq!(visited("^v^v", 1, 2).unwrap().len(), 3);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions contain anything but
+/// `^`, `v`, `<` and `>`, or an [`AocError::Unsolvable`] if `santa` is not
+/// one of the `santas`.
+pub fn visited(input: &str, santa: usize, santas: usize) -> Result<HashSet<(i64, i64)>, AocError> {
+    Ok(visit_counts(input, santa, santas)?.into_keys().collect())
+}
+
+/// Returns how many times each house is visited by all of `santas` taking
+/// turns following the directions.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions are invalid.
+pub fn heatmap(input: &str, santas: usize) -> Result<HashMap<(i64, i64), u32>, AocError> {
+    let mut houses = HashMap::new();
+    for santa in 0..santas {
+        for (house, count) in visit_counts(input, santa, santas)? {
+            *houses.entry(house).or_insert(0) += count;
+        }
+    }
+    Ok(houses)
+}
+
+/// Renders the visits of a part as an image, one pixel per house.
+///
+/// Part a follows Santa alone and part b Santa and Robo-Santa. Both images
+/// cover the houses visited in either part, so they can be compared side
+/// by side. Unvisited houses are black, and visited ones range from dark
+/// gray to white on a logarithmic scale of their visits.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex03::render;
+/// use aoc2015::solution::Part;
+/// let image = render("^>v<", Part::A).unwrap();
+/// assert_eq!((image.width(), image.height()), (2, 2));
+/// assert_eq!(image[(0, 1)], 255);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the directions are invalid.
+pub fn render(input: &str, part: Part) -> Result<Grid<u8>, AocError> {
+    let alone = heatmap(input, 1)?;
+    let together = heatmap(input, 2)?;
+    let houses = alone.keys().chain(together.keys());
+    let (min_x, max_x) = houses.clone().map(|&(x, _)| x).fold((0, 0), |(min, max), x| (min.min(x), max.max(x)));
+    let (min_y, max_y) = houses.map(|&(_, y)| y).fold((0, 0), |(min, max), y| (min.min(y), max.max(y)));
+    let counts = match part {
+        Part::A => alone,
+        Part::B => together,
+    };
+    let most = counts.values().copied().max().unwrap_or(1).max(2) as f64;
+    let mut image = Grid::new((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize, 0);
+    for ((x, y), count) in counts {
+        let level = 64.0 + 191.0 * (count as f64).ln() / most.ln();
+        image[((x - min_x) as usize, (y - min_y) as usize)] = level.round().min(255.0) as u8;
+    }
+    Ok(image)
+}
+
+// 3rd day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/3
+//
+// This is a solution to the first part of the puzzle.
+// The houses visited by Santa are collected in a set.
+pub fn a(input: &str) -> Result<usize, AocError> {
+    Ok(solve(input, 1)?.count())
+}
+
+// This is a solution to the second part of the puzzle.
+// The houses visited by Santa and Robo-Santa are the union of their sets.
+pub fn b(input: &str) -> Result<usize, AocError> {
+    Ok(solve(input, 2)?.count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn round_robin_follows_every_santa() {
+        let input = "^>v<<^^>vv>>^<";
+        let delivery = solve(input, 3).unwrap();
+        for santa in 0..3 {
+            assert_eq!(delivery.by_santa[santa], visited(input, santa, 3).unwrap());
+        }
+        assert_eq!(solve_scheduled(input, 3, |index| index % 3).unwrap(), delivery);
+    }
+
+    #[test]
+    fn santas_going_opposite_ways_only_share_the_start() {
+        let delivery = solve("^v^v^v^v^v", 2).unwrap();
+        assert_eq!(delivery.count(), 11);
+        assert_eq!(delivery.shared(), 1);
+    }
+
+    #[test]
+    fn santas_following_the_same_path_share_every_house() {
+        let delivery = solve("^^>>vv<<", 2).unwrap();
+        assert_eq!(delivery.by_santa[0], delivery.by_santa[1]);
+        assert_eq!(delivery.shared(), delivery.count());
+    }
+
+    #[test]
+    fn missing_santas_are_rejected() {
+        assert!(matches!(visit_counts("^v", 0, 0), Err(AocError::Unsolvable(_))));
+        assert!(matches!(visited("^v", 2, 2), Err(AocError::Unsolvable(_))));
+        assert!(heatmap("^v", 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! [Day 4: The Ideal Stocking Stuffer](https://adventofcode.com/2015/day/4).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex04::a;
+//! assert_eq!(a("abcdef").unwrap(), 609043);
+//! ```
+
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::AocError;
+use crate::progress::{NoProgress, ProgressSink};
+use crate::solution::{Example, Part, Solution};
+use crate::util::checkpoint::{Checkpoint, CheckpointStore, NoCheckpoint};
+use crate::util::md5::Md5;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 4 }
+    fn title(&self) -> &str { "The Ideal Stocking Stuffer" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let zeroes = match part {
+            Part::A => 5,
+            Part::B => 6,
+        };
+        search(input, zeroes, context.progress, context.checkpoint).map(Answer::from)
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "abcdef", answer: "609043" },
+    Example { part: Part::A, input: "pqrstuv", answer: "1048970" },
+];
+
+/// The number of consecutive nonces a worker checks each time it claims work.
+const CHUNK_SIZE: u32 = 4096;
+
+/// The number of nonces between two checkpoints of the search, a multiple
+/// of [`CHUNK_SIZE`].
+const CHECKPOINT_INTERVAL: u32 = 1 << 22;
+
+/// Returns whether the hex representation of `digest` starts with `zeroes` zeroes.
+fn has_leading_zeroes(digest: &[u8; 16], zeroes: usize) -> bool {
+    digest.iter().take(zeroes / 2).all(|&byte| byte == 0) &&
+        (zeroes.is_multiple_of(2) || digest[zeroes / 2] < 0x10)
+}
+
+/// Writes the decimal digits of `nonce` at the end of `buffer`.
+///
+/// # Returns
+/// The digits, without allocating a string for each nonce.
+fn digits(nonce: u32, buffer: &mut [u8; 10]) -> &[u8] {
+    let mut start = buffer.len();
+    let mut rest = nonce;
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 { break; }
+    }
+    &buffer[start..]
+}
+
+/// The chunks of the nonce space claimed by the workers of a search.
+struct Claims {
+    /// The start of the next chunk to claim.
+    next: u32,
+    /// The starts of the chunks being searched.
+    searching: BTreeSet<u32>,
+}
+
+impl Claims {
+    /// Returns the lowest nonce not searched yet: every nonce below it has
+    /// been searched, whatever the order the chunks completed in.
+    fn searched(&self) -> u32 {
+        self.searching.first().copied().unwrap_or(self.next)
+    }
+}
+
+/// Finds the lowest nonce whose MD5 hash, appended to `key`, starts with
+/// `zeroes` hex zeroes.
+///
+/// The key is hashed once, and the state of the hash after it is cloned
+/// for every nonce so that only the digits of the nonce are hashed.
+///
+/// The nonce space is split into chunks of [`CHUNK_SIZE`] nonces that
+/// worker threads claim in increasing order. A worker stops claiming chunks
+/// once a nonce lower than the next chunk has been found, so every chunk
+/// below the lowest match is always fully searched and the result does not
+/// depend on thread scheduling.
+///
+/// Every [`CHECKPOINT_INTERVAL`] nonces, the lowest nonce not searched yet
+/// is saved to `checkpoint`, and a search resumes from the nonce saved by
+/// an interrupted search for as many zeroes.
+///
+/// # Arguments
+/// * `key` - The secret key prefixing every nonce.
+/// * `zeroes` - The number of leading hex zeroes required.
+/// * `progress` - Receives the number of nonces claimed so far.
+/// * `checkpoint` - Saves the progress of the search, and holds the one
+///   to resume from.
+///
+/// # Errors
+/// Returns an [`AocError::Unsolvable`] if no nonce fits in a `u32`.
+fn search(key: &str, zeroes: usize, progress: &dyn ProgressSink, checkpoint: &dyn CheckpointStore) -> Result<u32, AocError> {
+    let mut prefix = Md5::new();
+    prefix.update(key.as_bytes());
+    let resumed = checkpoint.load()
+        .filter(|saved| saved.get("zeroes") == Some(zeroes as u64))
+        .and_then(|saved| u32::try_from(saved.get("nonce")?).ok())
+        .unwrap_or(0);
+    if resumed > 0 { tracing::info!(nonce = resumed, "resumed the search"); }
+    let claims = Mutex::new(Claims { next: resumed, searching: BTreeSet::new() });
+    let found = AtomicU32::new(u32::MAX);
+    let worker = || loop {
+        let start = {
+            let mut claims = claims.lock().unwrap();
+            let start = claims.next;
+            if start >= found.load(Ordering::Relaxed) || start > u32::MAX - CHUNK_SIZE { break; }
+            claims.next += CHUNK_SIZE;
+            claims.searching.insert(start);
+            start
+        };
+        progress.report(u64::from(start), None);
+        let mut buffer = [0; 10];
+        for nonce in start..start + CHUNK_SIZE {
+            let mut hasher = prefix.clone();
+            hasher.update(digits(nonce, &mut buffer));
+            if has_leading_zeroes(&hasher.finalize(), zeroes) {
+                found.fetch_min(nonce, Ordering::Relaxed);
+                break;
+            }
+        }
+        let searched = {
+            let mut claims = claims.lock().unwrap();
+            claims.searching.remove(&start);
+            claims.searched()
+        };
+        if (start + CHUNK_SIZE).is_multiple_of(CHECKPOINT_INTERVAL) && found.load(Ordering::Relaxed) == u32::MAX {
+            let mut saved = Checkpoint::new();
+            saved.set("nonce", u64::from(searched));
+            saved.set("zeroes", zeroes as u64);
+            checkpoint.save(&saved);
+        }
+    };
+    // Targets without threads, such as WebAssembly, search on the caller's
+    // thread.
+    match thread::available_parallelism().map_or(1, NonZeroUsize::get) {
+        1 => worker(),
+        workers => thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(worker);
+            }
+        }),
+    }
+    tracing::debug!(claimed = claims.into_inner().unwrap().next, "searched nonces");
+    match found.into_inner() {
+        u32::MAX => Err(AocError::unsolvable("no nonce below 2^32 produces the hash")),
+        found => Ok(found),
+    }
+}
+
+// 4th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/4
+//
+// This is a solution to the first part of the puzzle.
+// The lowest nonce producing a hash with 5 leading zeroes is searched
+// in parallel.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    search(input, 5, &NoProgress, &NoCheckpoint)
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, but with 6 leading zeroes.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    search(input, 6, &NoProgress, &NoCheckpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::checkpoint::MemoryCheckpoint;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn resumes_from_a_checkpoint() {
+        let mut saved = Checkpoint::new();
+        saved.set("nonce", 600000);
+        saved.set("zeroes", 5);
+        assert_eq!(search("abcdef", 5, &NoProgress, &MemoryCheckpoint::new(saved.clone())).unwrap(), 609043);
+        // Nonces below the checkpoint are not searched again.
+        saved.set("nonce", 700000);
+        assert!(search("abcdef", 5, &NoProgress, &MemoryCheckpoint::new(saved.clone())).unwrap() > 700000);
+        // Nor are checkpoints of a search for another number of zeroes used.
+        saved.set("zeroes", 6);
+        assert_eq!(search("abcdef", 5, &NoProgress, &MemoryCheckpoint::new(saved)).unwrap(), 609043);
+    }
+}
@@ -0,0 +1,225 @@
+//! [Day 5: Doesn't He Have Intern-Elves For This?](https://adventofcode.com/2015/day/5).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex05::{a, b};
+//! assert_eq!(a("ugknbfddgicrmopn\njchzalrnumimnmhp\nhaegwjzuvuyypxyu").unwrap(), 1);
+//! assert_eq!(b("qjhvhtzxzqqjkmpb\nxxyxx\nuurcxstgeygkmmuz").unwrap(), 2);
+//! ```
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 5 }
+    fn title(&self) -> &str { "Doesn't He Have Intern-Elves For This?" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse_lines(input, ascii).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "ugknbfddgicrmopn", answer: "1" },
+    Example { part: Part::A, input: "aaa", answer: "1" },
+    Example { part: Part::A, input: "jchzalrnumimnmhp", answer: "0" },
+    Example { part: Part::A, input: "haegwjzuvuyypxyu", answer: "0" },
+    Example { part: Part::A, input: "dvszwmarrgswjxmb", answer: "0" },
+    Example { part: Part::B, input: "qjhvhtzxzqqjkmpb", answer: "1" },
+    Example { part: Part::B, input: "xxyxx", answer: "1" },
+    Example { part: Part::B, input: "uurcxstgeygkmmuz", answer: "0" },
+    Example { part: Part::B, input: "ieodomkazucvgmuy", answer: "0" },
+];
+
+/// A criterion that nice strings meet.
+///
+/// Strings are checked as bytes, which are their letters as long as they
+/// are ASCII: see [`ascii`].
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    /// The name of the rule, reported for the strings failing it.
+    pub name: &'static str,
+    /// Whether a string meets the rule.
+    pub check: fn(&[u8]) -> bool,
+}
+
+/// The rules of the first part.
+pub const RULES_A: [Rule; 3] = [
+    Rule { name: "at least three vowels", check: has_three_vowels },
+    Rule { name: "a letter twice in a row", check: has_double_letter },
+    Rule { name: "no forbidden pair", check: has_no_forbidden_pair },
+];
+
+/// The rules of the second part.
+pub const RULES_B: [Rule; 2] = [
+    Rule { name: "a pair appearing twice without overlapping", check: has_repeated_pair },
+    Rule { name: "a letter repeating with one between", check: has_split_repeat },
+];
+
+/// The pairs of letters nice strings of the first part do not contain.
+const FORBIDDEN_PAIRS: [&[u8]; 4] = [b"ab", b"cd", b"pq", b"xy"];
+
+fn has_three_vowels(string: &[u8]) -> bool {
+    string.iter().filter(|letter| b"aeiou".contains(letter)).count() >= 3
+}
+
+fn has_double_letter(string: &[u8]) -> bool {
+    string.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+fn has_no_forbidden_pair(string: &[u8]) -> bool {
+    !string.windows(2).any(|pair| FORBIDDEN_PAIRS.contains(&pair))
+}
+
+/// Checks for a pair of letters appearing twice without overlapping, in
+/// a single pass remembering where each pair first appears.
+fn has_repeated_pair(string: &[u8]) -> bool {
+    let mut first = HashMap::new();
+    string.windows(2).enumerate()
+        .any(|(index, pair)| index >= *first.entry(pair).or_insert(index) + 2)
+}
+
+fn has_split_repeat(string: &[u8]) -> bool {
+    string.windows(3).any(|triple| triple[0] == triple[2])
+}
+
+/// Returns the letters of a string, as bytes.
+///
+/// # Errors
+/// Returns a description of the problem if the string is not ASCII, as
+/// its bytes would then not be its letters.
+pub fn ascii(string: &str) -> Result<&[u8], String> {
+    match string.find(|letter: char| !letter.is_ascii()) {
+        Some(index) => Err(format!("non-ASCII character at column {}", string[..index].chars().count() + 1)),
+        None => Ok(string.as_bytes()),
+    }
+}
+
+/// Returns the first of `rules` that `string` fails.
+///
+/// # Returns
+/// The failed [`Rule`], or `None` if the string is nice.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex05::{failed_rule, RULES_A, RULES_B};
+/// assert!(failed_rule(&RULES_A, b"ugknbfddgicrmopn").is_none());
+/// assert_eq!(failed_rule(&RULES_A, b"haegwjzuvuyypxyu").unwrap().name, "no forbidden pair");
+/// assert_eq!(failed_rule(&RULES_B, b"aaa").unwrap().name, "a pair appearing twice without overlapping");
+/// ```
+pub fn failed_rule(rules: &[Rule], string: &[u8]) -> Option<Rule> {
+    rules.iter().copied().find(|rule| !(rule.check)(string))
+}
+
+/// Returns whether `string` meets every one of `rules`.
+pub fn is_nice(rules: &[Rule], string: &[u8]) -> bool {
+    match failed_rule(rules, string) {
+        Some(rule) => {
+            tracing::trace!(string = %String::from_utf8_lossy(string), rule = rule.name, "naughty string");
+            false
+        }
+        None => true,
+    }
+}
+
+/// Counts the lines read from `input` meeting every one of `rules`, one at
+/// a time.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if a line is not ASCII, or an
+/// [`AocError::Io`] if it cannot be read.
+fn count_stream(input: impl BufRead, rules: &[Rule]) -> Result<u32, AocError> {
+    let mut count = 0;
+    for line in parse_stream(input, |line| ascii(line).map(|string| is_nice(rules, string))) {
+        count += u32::from(line?);
+    }
+    Ok(count)
+}
+
+/// Counts the lines of `input` meeting every one of `rules`.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if a line is not ASCII.
+fn count(input: &str, rules: &[Rule]) -> Result<u32, AocError> {
+    let strings = parse_lines(input, ascii)?;
+    Ok(strings.into_iter().filter(|string| is_nice(rules, string)).count() as u32)
+}
+
+pub fn a(input: &str) -> Result<u32, AocError> {
+    count(input, &RULES_A)
+}
+
+// Same as the first part, reading the strings one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u32, AocError> {
+    count_stream(input, &RULES_A)
+}
+
+pub fn b(input: &str) -> Result<u32, AocError> {
+    count(input, &RULES_B)
+}
+
+// Same as the second part, reading the strings one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u32, AocError> {
+    count_stream(input, &RULES_B)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn stream() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn failed_rules() {
+        let failed = |rules: &[Rule], string: &str| failed_rule(rules, string.as_bytes()).map(|rule| rule.name);
+        assert_eq!(failed(&RULES_A, "jchzalrnumimnmhp"), Some("a letter twice in a row"));
+        assert_eq!(failed(&RULES_A, "dvszwmarrgswjxmb"), Some("at least three vowels"));
+        assert_eq!(failed(&RULES_B, "aabcdeaa"), Some("a letter repeating with one between"));
+        assert_eq!(failed(&RULES_B, "ieodomkazucvgmuy"), Some("a pair appearing twice without overlapping"));
+        assert_eq!(failed(&RULES_B, "aaaa"), None);
+    }
+
+    #[test]
+    fn short_and_overlapping_strings() {
+        for string in ["", "a", "aa", "ab", "aaa"] {
+            assert!(!is_nice(&RULES_B, string.as_bytes()), "{string:?}");
+        }
+        assert!(is_nice(&RULES_B, b"aaaa"));
+        assert!(is_nice(&RULES_A, b"aaa"));
+        assert!(!is_nice(&RULES_A, b"aa"));
+    }
+
+    #[test]
+    fn rejects_non_ascii() {
+        assert!(matches!(a("aaa\nnaïve"), Err(AocError::Parse { line: 2, .. })));
+        assert!(matches!(b_stream("aaa\néa".as_bytes()), Err(AocError::Parse { line: 2, .. })));
+        assert_eq!(ascii("naïve"), Err("non-ASCII character at column 3".to_string()));
+    }
+}
@@ -0,0 +1,1030 @@
+//! [Day 6: Probably a Fire Hazard](https://adventofcode.com/2015/day/6).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex06::{a, b};
+//! assert_eq!(a("turn on 0,0 through 999,999\nturn off 499,499 through 500,500").unwrap(), 999_996);
+//! assert_eq!(b("toggle 0,0 through 999,999").unwrap(), 2_000_000);
+//! ```
+
+use std::fmt;
+use std::io::BufRead;
+use std::ops::Range;
+use std::str::FromStr;
+use rayon::prelude::*;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::parse_line;
+use crate::solution::{Example, Implementation, Part, Solution};
+use crate::util;
+use crate::util::bits::{life_row, popcount};
+use crate::util::geom::{Point, Rect};
+use crate::util::lanes;
+use crate::util::parse::LineError;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 6 }
+    fn title(&self) -> &str { "Probably a Fire Hazard" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse_instructions(input, SIZE, SIZE).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let size = context.config.light_grid_size;
+        solve_with_size(part, input, size, size).map(Answer::from)
+    }
+}
+
+/// The alternate implementations of both parts.
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "serial", part: Part::A, solve: |input| a(input).map(Answer::from) },
+    Implementation { name: "parallel", part: Part::A, solve: |input| a_parallel(input).map(Answer::from) },
+    Implementation { name: "sweep", part: Part::A, solve: |input| a_sweep(input).map(Answer::from) },
+    Implementation { name: "serial", part: Part::B, solve: |input| b(input).map(Answer::from) },
+    Implementation { name: "packed", part: Part::B, solve: |input| b_packed(input).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "turn on 0,0 through 999,999", answer: "1000000" },
+    Example { part: Part::A, input: "toggle 0,0 through 999,0", answer: "1000" },
+    Example { part: Part::A, input: "turn on 0,0 through 999,999\nturn off 499,499 through 500,500", answer: "999996" },
+    Example { part: Part::B, input: "turn on 0,0 through 0,0", answer: "1" },
+    Example { part: Part::B, input: "toggle 0,0 through 999,999", answer: "2000000" },
+];
+
+/// An instruction of Santa's, such as `toggle 0,0 through 999,0`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::{Instruction, Op};
+/// use aoc2015::util::geom::Point;
+/// let instruction: Instruction = "turn on 0,0 through 999,999".parse().unwrap();
+/// assert_eq!(instruction, Instruction { op: Op::On, from: Point::new(0, 0), to: Point::new(999, 999) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    /// The operation to perform.
+    pub op: Op,
+    /// The coordinates of the first light to update.
+    pub from: Point,
+    /// The coordinates of the last light to update, inclusive.
+    pub to: Point,
+}
+
+impl Instruction {
+    /// Checks that this instruction only updates lights of a grid of
+    /// `width` by `height` lights, and that its first corner is not past the
+    /// last one.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Instruction;
+    /// let instruction: Instruction = "toggle 0,0 through 999,0".parse().unwrap();
+    /// assert!(instruction.check_bounds(1000, 1).is_ok());
+    /// assert!(instruction.check_bounds(10, 10).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the instruction is out of bounds.
+    pub fn check_bounds(&self, width: usize, height: usize) -> Result<(), String> {
+        let (from, to) = (self.from, self.to);
+        if from.x > to.x || from.y > to.y {
+            return Err(format!("corner {},{} is past corner {},{}", from.x, from.y, to.x, to.y));
+        }
+        if to.x >= width || to.y >= height {
+            return Err(format!("light {},{} is outside the {width}x{height} grid", to.x, to.y));
+        }
+        Ok(())
+    }
+
+    /// Returns the rectangle of lights to update.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Instruction;
+    /// let instruction: Instruction = "toggle 0,0 through 999,0".parse().unwrap();
+    /// assert_eq!(instruction.rect().area(), 1000);
+    /// ```
+    pub fn rect(&self) -> Rect {
+        Rect::from_inclusive(self.from, self.to)
+    }
+}
+
+/// An error found while parsing an [`Instruction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line is not an instruction.
+    Malformed(String),
+    /// A coordinate does not fit in a `usize`.
+    BadCoordinate(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Malformed(line) => write!(f, "invalid instruction '{line}'"),
+            ParseError::BadCoordinate(coordinate) => write!(f, "coordinate {coordinate} is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    /// Parses an instruction such as `turn off 499,499 through 500,500`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::{Instruction, ParseError};
+    /// assert_eq!("flip 0,0 through 1,1".parse::<Instruction>(), Err(ParseError::Malformed("flip 0,0 through 1,1".to_string())));
+    /// let line = "toggle 0,0 through 99999999999999999999,1";
+    /// assert_eq!(line.parse::<Instruction>(), Err(ParseError::BadCoordinate("99999999999999999999".to_string())));
+    /// ```
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let malformed = || ParseError::Malformed(line.to_string());
+        let (op, corners) = [("turn on ", Op::On), ("turn off ", Op::Off), ("toggle ", Op::Toggle)].into_iter()
+            .find_map(|(prefix, op)| Some((op, line.strip_prefix(prefix)?)))
+            .ok_or_else(malformed)?;
+        let (x1, y1, x2, y2) = parse_line!("{x1},{y1} through {x2},{y2}", corners => (x1: usize, y1: usize, x2: usize, y2: usize))
+            .map_err(|err| match err {
+                LineError::Mismatch(_) => malformed(),
+                LineError::Field { text, .. } => ParseError::BadCoordinate(text),
+            })?;
+        Ok(Instruction { op, from: Point::new(x1, y1), to: Point::new(x2, y2) })
+    }
+}
+
+/// A grid of lights.
+///
+/// The grid is stored as a flat vector of bitfields, where each bit represents a light.
+/// The bitfields are stored in row-major order, with each bitfield representing a row.
+/// The bitfields are stored in little-endian order, so the least significant bit represents
+/// the leftmost light.
+/// For example, the grid
+/// ```text
+/// 0 1 0 1
+/// 1 0 0 1
+/// 0 0 1 0
+/// 1 1 1 1
+/// ```
+/// would be stored as
+/// ```text
+/// [0b1010, 0b1001, 0b0010, 0b1111]
+/// ```
+#[derive(Clone)]
+pub struct Grid {
+    /// The bitfields of this grid as a flat vector.
+    grid: Vec<u128>,
+    /// The height of this grid.
+    height: usize,
+    /// The width of this grid in 128-bit words.
+    words: usize,
+    /// The width of this grid in lights.
+    columns: usize,
+}
+
+/// An operation to perform on a [`Grid`] or a [`BrightnessGrid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    On,
+    Off,
+    Toggle,
+}
+
+/// A grid of lights.
+impl Grid {
+    /// Creates a new [`Grid`] of the provided size.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the grid.
+    /// * `height` - The height of the grid.
+    ///
+    /// # Returns
+    /// A new [`Grid`] of the provided size.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Grid;
+    /// let grid = Grid::new(1000, 1000);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `width` or `height` is 0.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "Invalid dimensions");
+        let columns = width;
+        let words = (width - 1) / 128 + 1;
+        Grid { grid: vec![0; words * height], words, height, columns }
+    }
+
+    /// Returns the width of this grid in lights.
+    pub fn width(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the height of this grid in lights.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Updates this grid according to the provided [`Op`] and rectangle.
+    ///
+    /// # Arguments
+    /// * `op` - The [`Op`] to perform.
+    /// * `rect` - The lights to update. Lights outside of the grid are
+    ///   ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::{Grid, Op};
+    /// use aoc2015::util::geom::Rect;
+    /// let mut grid = Grid::new(1000, 1000);
+    /// grid.update(Op::On, Rect::sized(2, 2));
+    /// ```
+    pub fn update(&mut self, op: Op, rect: Rect) {
+        update_band(&mut self.grid, self.words, self.columns, 0, op, rect);
+    }
+
+    /// Applies an [`Instruction`] to this grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Grid;
+    /// let mut grid = Grid::new(1000, 1000);
+    /// grid.apply(&"toggle 0,0 through 999,0".parse().unwrap());
+    /// assert_eq!(grid.count(), 1000);
+    /// ```
+    pub fn apply(&mut self, instruction: &Instruction) {
+        self.update(instruction.op, instruction.rect());
+    }
+
+    /// Applies every instruction to this grid, in parallel over horizontal
+    /// bands of rows.
+    ///
+    /// Each instruction only affects its own rectangle, so the grid is split
+    /// into one band of rows per rayon thread and every band applies the
+    /// whole instruction stream, clipped to its rows, independently of the
+    /// others.
+    ///
+    /// # Arguments
+    /// * `instructions` - The [`Instruction`]s to apply, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::{Grid, Instruction, Op};
+    /// use aoc2015::util::geom::Point;
+    /// let mut grid = Grid::new(1000, 1000);
+    /// grid.update_parallel(&[
+    ///     Instruction { op: Op::On, from: Point::new(0, 0), to: Point::new(999, 999) },
+    ///     Instruction { op: Op::Toggle, from: Point::new(0, 0), to: Point::new(999, 0) },
+    /// ]);
+    /// assert_eq!(grid.count(), 999000);
+    /// ```
+    pub fn update_parallel(&mut self, instructions: &[Instruction]) {
+        let band_rows = self.height.div_ceil(rayon::current_num_threads());
+        let (width, columns) = (self.words, self.columns);
+        self.grid.par_chunks_mut(band_rows * width)
+            .enumerate()
+            .for_each(|(band, bitfields)| {
+                for instruction in instructions {
+                    update_band(bitfields, width, columns, band * band_rows, instruction.op, instruction.rect());
+                }
+            });
+    }
+
+    /// Returns a picture of this grid, lights on being white and lights
+    /// off black.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Grid;
+    /// let mut grid = Grid::new(2, 1);
+    /// grid.set(1, 0, true);
+    /// assert_eq!(grid.image().rows().collect::<Vec<_>>(), [[0, 255]]);
+    /// ```
+    pub fn image(&self) -> util::grid::Grid<u8> {
+        let mut image = util::grid::Grid::new(self.columns, self.height, 0);
+        for y in 0..self.height {
+            for x in (0..self.columns).filter(|&x| self.get(x, y)) {
+                image[(x, y)] = 255;
+            }
+        }
+        image
+    }
+
+    /// Returns the number of lights turned on in this [`Grid`].
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::{Grid, Op};
+    /// use aoc2015::util::geom::Rect;
+    /// let mut grid = Grid::new(1000, 1000);
+    /// grid.update(Op::On, Rect::sized(2, 2));
+    /// assert_eq!(grid.count(), 4);
+    /// ```
+    pub fn count(&self) -> u32 {
+        popcount(&self.grid)
+    }
+
+    /// Returns whether the light at the provided coordinates is on.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.columns && y < self.height, "Out of bounds");
+        self.grid[y * self.words + x / 128] >> (x % 128) & 1 == 1
+    }
+
+    /// Turns the light at the provided coordinates on or off.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        assert!(x < self.columns && y < self.height, "Out of bounds");
+        let bitfield = &mut self.grid[y * self.words + x / 128];
+        let bit = 1u128 << (x % 128);
+        if on { *bitfield |= bit } else { *bitfield &= !bit }
+    }
+
+    /// Advances this grid one step of Conway's Game of Life.
+    ///
+    /// A light stays on if 2 or 3 of its 8 neighbors are on, and turns on if
+    /// exactly 3 are on. Lights beyond the edges count as off.
+    ///
+    /// Every row is advanced with [`life_row`], which counts the neighbors
+    /// of many lights at once in a bit-sliced counter.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::Grid;
+    /// let mut grid = Grid::new(3, 3);
+    /// grid.set(0, 1, true);
+    /// grid.set(1, 1, true);
+    /// grid.set(2, 1, true);
+    /// grid.step();
+    /// assert!(grid.get(1, 0) && grid.get(1, 1) && grid.get(1, 2));
+    /// assert_eq!(grid.count(), 3);
+    /// ```
+    pub fn step(&mut self) {
+        let empty = vec![0; self.words];
+        let mut next = vec![0; self.grid.len()];
+        let last_mask = match self.columns % 128 {
+            0 => u128::MAX,
+            bits => (1u128 << bits) - 1,
+        };
+        for (y, next) in next.chunks_exact_mut(self.words).enumerate() {
+            let above = if y > 0 { self.row(y - 1) } else { &empty };
+            let below = if y + 1 < self.height { self.row(y + 1) } else { &empty };
+            life_row(above, self.row(y), below, next);
+            next[self.words - 1] &= last_mask;
+        }
+        self.grid = next;
+    }
+
+    /// Returns the bitfields of the provided row.
+    fn row(&self, y: usize) -> &[u128] {
+        &self.grid[y * self.words..(y + 1) * self.words]
+    }
+}
+
+/// Applies an [`Op`] to the lights of a band of rows of a [`Grid`].
+///
+/// # Arguments
+/// * `band` - The bitfields of the rows of the band.
+/// * `width` - The width of the grid in 128-bit words.
+/// * `columns` - The width of the grid in lights.
+/// * `first_row` - The y-coordinate of the first row of the band.
+/// * `op` - The [`Op`] to perform.
+/// * `rect` - The lights to update, as in [`Grid::update`].
+fn update_band(band: &mut [u128], width: usize, columns: usize, first_row: usize, op: Op, rect: Rect) {
+    // Lights beyond the edges are ignored, so that no bit past the last
+    // column of a row is ever set.
+    let rows = Rect::new(Point::new(0, first_row), Point::new(columns, first_row + band.len() / width));
+    let Some(rect) = rect.intersect(&rows) else { return };
+    let (x1, y1, x2, y2) = (rect.min.x, rect.min.y, rect.max.x, rect.max.y);
+    for x_index in x1 / 128..=(x2 - 1) / 128 {
+        let start_bit = x1.saturating_sub(x_index * 128);
+        let end_bit = x2 - x_index * 128;
+        let start_bit = 1u128 << start_bit;
+        let end_bit = if end_bit < 128 { 1u128 << end_bit } else { 0 };
+        let mask = u128::wrapping_sub(end_bit, start_bit);
+        for y in y1 - first_row..y2 - first_row {
+            let bitfield = &mut band[y * width + x_index];
+            match op {
+                Op::On => *bitfield |= mask,
+                Op::Off => *bitfield &= !mask,
+                Op::Toggle => *bitfield ^= mask,
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.chunks(self.words) {
+            for bitfield in row {
+                let bitfield = format!("{:032X}", bitfield.reverse_bits()).replace("0", " ");
+                write!(f, "{bitfield}|")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A grid of lights with individually adjustable brightness.
+///
+/// The brightness of each light is stored as a `u32` in a flat vector in
+/// row-major order.
+pub struct BrightnessGrid {
+    /// The brightness of each light as a flat vector.
+    grid: Vec<u32>,
+    /// The width of this grid.
+    width: usize,
+}
+
+impl BrightnessGrid {
+    /// Creates a new [`BrightnessGrid`] of the provided size, with every light off.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the grid.
+    /// * `height` - The height of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `width` or `height` is 0.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "Invalid dimensions");
+        BrightnessGrid { grid: vec![0; width * height], width }
+    }
+
+    /// Applies an [`Instruction`] to this grid.
+    pub fn apply(&mut self, instruction: &Instruction) {
+        self.update(instruction.op, instruction.rect());
+    }
+
+    /// Updates this grid according to the provided [`Op`] and rectangle.
+    ///
+    /// [`Op::On`] increases brightness by 1, [`Op::Off`] decreases it by 1
+    /// (stopping at 0) and [`Op::Toggle`] increases it by 2.
+    ///
+    /// # Arguments
+    /// * `op` - The [`Op`] to perform.
+    /// * `rect` - The lights to update. Lights outside of the grid are
+    ///   ignored.
+    pub fn update(&mut self, op: Op, rect: Rect) {
+        let Some(rect) = rect.intersect(&Rect::sized(self.width, self.grid.len() / self.width)) else { return };
+        for row in self.grid[rect.min.y * self.width..rect.max.y * self.width].chunks_mut(self.width) {
+            for light in &mut row[rect.xs()] {
+                match op {
+                    Op::On => *light += 1,
+                    Op::Off => *light = light.saturating_sub(1),
+                    Op::Toggle => *light += 2,
+                }
+            }
+        }
+    }
+
+    /// Returns the total brightness of this [`BrightnessGrid`].
+    pub fn brightness(&self) -> u32 {
+        self.grid.iter().sum()
+    }
+}
+
+/// A grid of lights with individually adjustable brightness, packed in
+/// 16-bit lanes.
+///
+/// Each row of lights an instruction updates is a contiguous slice of
+/// lanes, updated with the saturating kernels of [`lanes`], many lights at
+/// a time. Brightness stops at `u16::MAX`, which takes more than 32767
+/// instructions to reach.
+pub struct PackedBrightnessGrid {
+    /// The brightness of each light as a flat vector, in row-major order.
+    grid: Vec<u16>,
+    /// The width of this grid.
+    width: usize,
+}
+
+impl PackedBrightnessGrid {
+    /// Creates a new [`PackedBrightnessGrid`] of the provided size, with
+    /// every light off.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the grid.
+    /// * `height` - The height of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `width` or `height` is 0.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 0 && height > 0, "Invalid dimensions");
+        PackedBrightnessGrid { grid: vec![0; width * height], width }
+    }
+
+    /// Applies an [`Instruction`] to this grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex06::PackedBrightnessGrid;
+    /// let mut grid = PackedBrightnessGrid::new(1000, 1000);
+    /// grid.apply(&"toggle 0,0 through 999,999".parse().unwrap());
+    /// grid.apply(&"turn off 0,0 through 999,0".parse().unwrap());
+    /// assert_eq!(grid.brightness(), 1_999_000);
+    /// ```
+    pub fn apply(&mut self, instruction: &Instruction) {
+        self.update(instruction.op, instruction.rect());
+    }
+
+    /// Updates this grid according to the provided [`Op`] and rectangle,
+    /// as [`BrightnessGrid::update`] does.
+    ///
+    /// # Arguments
+    /// * `op` - The [`Op`] to perform.
+    /// * `rect` - The lights to update. Lights outside of the grid are
+    ///   ignored.
+    pub fn update(&mut self, op: Op, rect: Rect) {
+        let Some(rect) = rect.intersect(&Rect::sized(self.width, self.grid.len() / self.width)) else { return };
+        for row in self.grid[rect.min.y * self.width..rect.max.y * self.width].chunks_mut(self.width) {
+            let lights = &mut row[rect.xs()];
+            match op {
+                Op::On => lanes::add_saturating(lights, 1),
+                Op::Off => lanes::sub_saturating(lights, 1),
+                Op::Toggle => lanes::add_saturating(lights, 2),
+            }
+        }
+    }
+
+    /// Returns the total brightness of this [`PackedBrightnessGrid`].
+    pub fn brightness(&self) -> u64 {
+        lanes::sum(&self.grid)
+    }
+}
+
+/// The width and height of the grid of lights of the puzzle.
+pub const SIZE: usize = 1000;
+
+/// The most lights a grid sized by [`solve_auto_size`] may have.
+pub const MAX_LIGHTS: usize = 1 << 28;
+
+/// Parses an instruction, checking that it fits a grid of `width` by
+/// `height` lights.
+fn parse_checked(line: &str, width: usize, height: usize) -> Result<Instruction, String> {
+    let instruction: Instruction = line.parse().map_err(|err: ParseError| err.to_string())?;
+    instruction.check_bounds(width, height)?;
+    Ok(instruction)
+}
+
+/// Parses the instructions of the input, checking that they fit a grid of
+/// `width` by `height` lights.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if an instruction is malformed or out of
+/// bounds.
+fn parse_instructions(input: &str, width: usize, height: usize) -> Result<Vec<Instruction>, AocError> {
+    parse_lines(input, |line| parse_checked(line, width, height))
+}
+
+/// Returns the size of the smallest grid holding every light updated by
+/// `instructions`, as `(width, height)`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::{detect_size, Instruction};
+/// let instructions: Vec<Instruction> = ["turn on 0,0 through 9,2", "toggle 4,4 through 5,5"]
+///     .iter().map(|line| line.parse().unwrap()).collect();
+/// assert_eq!(detect_size(&instructions), (10, 6));
+/// assert_eq!(detect_size(&[]), (1, 1));
+/// ```
+pub fn detect_size(instructions: &[Instruction]) -> (usize, usize) {
+    instructions.iter().fold((1, 1), |(width, height), instruction| {
+        let rect = instruction.rect();
+        (width.max(rect.max.x), height.max(rect.max.y))
+    })
+}
+
+/// Returns the coordinates where some instruction starts or stops updating
+/// lights along an axis, in order and with the edges `0` and `size`.
+fn boundaries(instructions: &[Instruction], size: usize, axis: impl Fn(&Rect) -> Range<usize>) -> Vec<usize> {
+    let mut boundaries: Vec<usize> = instructions.iter()
+        .flat_map(|instruction| {
+            let range = axis(&instruction.rect());
+            [range.start, range.end]
+        })
+        .chain([0, size])
+        .filter(|&boundary| boundary <= size)
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+    boundaries
+}
+
+/// Returns the number of lights on once `instructions` are applied to a
+/// grid of `width` by `height` lights, all off at first, without updating
+/// any grid.
+///
+/// The corners of the instructions cut the grid into rectangular regions
+/// whose lights are all updated by the same instructions, so they end up
+/// in the same state. The grid is swept one column of regions at a time,
+/// keeping only the instructions covering that column, and the final
+/// state of each region is found once by going back through them from the
+/// last: toggles flip the state until an instruction turning lights on or
+/// off sets it.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::{count_sweep, Instruction};
+/// let instructions: Vec<Instruction> = ["turn on 0,0 through 999,999", "toggle 0,0 through 999,0", "turn off 499,499 through 500,500"]
+///     .iter().map(|line| line.parse().unwrap()).collect();
+/// assert_eq!(count_sweep(&instructions, 1000, 1000), 998_996);
+/// ```
+pub fn count_sweep(instructions: &[Instruction], width: usize, height: usize) -> u32 {
+    let xs = boundaries(instructions, width, Rect::xs);
+    let ys = boundaries(instructions, height, Rect::ys);
+    let mut count = 0;
+    for columns in xs.windows(2) {
+        let covering: Vec<(Op, Rect)> = instructions.iter()
+            .map(|instruction| (instruction.op, instruction.rect()))
+            .filter(|(_, rect)| rect.xs().contains(&columns[0]))
+            .collect();
+        for rows in ys.windows(2) {
+            let mut toggled = false;
+            let mut on = false;
+            for (op, _) in covering.iter().rev().filter(|(_, rect)| rect.ys().contains(&rows[0])) {
+                match op {
+                    Op::Toggle => toggled = !toggled,
+                    op => {
+                        on = *op == Op::On;
+                        break;
+                    }
+                }
+            }
+            if on != toggled {
+                count += (columns[1] - columns[0]) * (rows[1] - rows[0]);
+            }
+        }
+    }
+    count as u32
+}
+
+/// Applies `instructions` to a grid of `width` by `height` lights, all off
+/// at first, and returns the answer to `part`: the number of lights on, or
+/// their total brightness.
+fn lights(part: Part, instructions: &[Instruction], width: usize, height: usize) -> u32 {
+    match part {
+        Part::A => {
+            let mut grid = Grid::new(width, height);
+            for instruction in instructions {
+                grid.apply(instruction);
+            }
+            grid.count()
+        }
+        Part::B => {
+            let mut grid = BrightnessGrid::new(width, height);
+            for instruction in instructions {
+                grid.apply(instruction);
+            }
+            grid.brightness()
+        }
+    }
+}
+
+/// Solves a part of the puzzle on a grid of `width` by `height` lights
+/// instead of the [`SIZE`] by [`SIZE`] lights of the puzzle.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::solve_with_size;
+/// use aoc2015::solution::Part;
+/// assert_eq!(solve_with_size(Part::A, "turn on 0,0 through 3,1\ntoggle 2,0 through 4,0", 5, 2).unwrap(), 7);
+/// assert!(solve_with_size(Part::A, "turn on 0,0 through 5,1", 5, 2).is_err());
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if an instruction is malformed or updates
+/// lights outside of the grid, or an [`AocError::Unsolvable`] if the grid
+/// has no light.
+pub fn solve_with_size(part: Part, input: &str, width: usize, height: usize) -> Result<u32, AocError> {
+    if width == 0 || height == 0 {
+        return Err(AocError::unsolvable(format!("a {width}x{height} grid has no light")));
+    }
+    Ok(lights(part, &parse_instructions(input, width, height)?, width, height))
+}
+
+/// Solves a part of the puzzle on the smallest grid holding every light
+/// the instructions update, as found by [`detect_size`].
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::solve_auto_size;
+/// use aoc2015::solution::Part;
+/// assert_eq!(solve_auto_size(Part::B, "toggle 0,0 through 1999,0").unwrap(), 4000);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if an instruction is malformed, or an
+/// [`AocError::Unsolvable`] if the grid would have more than [`MAX_LIGHTS`]
+/// lights.
+pub fn solve_auto_size(part: Part, input: &str) -> Result<u32, AocError> {
+    let instructions = parse_instructions(input, usize::MAX, usize::MAX)?;
+    let (width, height) = detect_size(&instructions);
+    if width.checked_mul(height).is_none_or(|lights| lights > MAX_LIGHTS) {
+        return Err(AocError::unsolvable(format!("a {width}x{height} grid is too large")));
+    }
+    Ok(lights(part, &instructions, width, height))
+}
+
+/// Returns the successive states of the lights of the first part: all off
+/// at first, then after each instruction in turn.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex06::states;
+/// let counts: Vec<_> = states("turn on 0,0 through 9,9\ntoggle 0,0 through 9,0").unwrap()
+///     .map(|grid| grid.count())
+///     .collect();
+/// assert_eq!(counts, [0, 100, 90]);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if an instruction is malformed or out of
+/// bounds.
+pub fn states(input: &str) -> Result<impl Iterator<Item = Grid>, AocError> {
+    let instructions = parse_instructions(input, SIZE, SIZE)?;
+    let mut grid = Grid::new(SIZE, SIZE);
+    let initial = grid.clone();
+    Ok(std::iter::once(initial).chain(instructions.into_iter().map(move |instruction| {
+        grid.apply(&instruction);
+        grid.clone()
+    })))
+}
+
+// 6th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/6
+//
+// This is a solution to the first part of the puzzle.
+// The solution is found by parsing the input into a grid of lights,
+// then updating the grid according to the instructions.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    solve_with_size(Part::A, input, SIZE, SIZE)
+}
+
+// Same as the first part, reading the instructions one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u32, AocError> {
+    let mut grid = Grid::new(SIZE, SIZE);
+    for instruction in parse_stream(input, |line| parse_checked(line, SIZE, SIZE)) {
+        grid.apply(&instruction?);
+    }
+    Ok(grid.count())
+}
+
+// Same as the first part, but the instructions are applied in parallel to
+// horizontal bands of the grid.
+pub fn a_parallel(input: &str) -> Result<u32, AocError> {
+    let mut grid = Grid::new(SIZE, SIZE);
+    grid.update_parallel(&parse_instructions(input, SIZE, SIZE)?);
+    Ok(grid.count())
+}
+
+// Same as the first part, but the final state of every region of lights
+// updated alike is found once with a sweep over the compressed coordinates
+// of the instructions, instead of updating a grid.
+pub fn a_sweep(input: &str) -> Result<u32, AocError> {
+    Ok(count_sweep(&parse_instructions(input, SIZE, SIZE)?, SIZE, SIZE))
+}
+
+// This is a solution to the second part of the puzzle.
+// The instructions are the same as in the first part, but each light
+// now has a brightness, so they are applied to a [`BrightnessGrid`].
+pub fn b(input: &str) -> Result<u32, AocError> {
+    solve_with_size(Part::B, input, SIZE, SIZE)
+}
+
+// Same as the second part, but the brightness of the lights is packed in
+// 16-bit lanes updated a row slice at a time. Inputs long enough to reach
+// the largest brightness a lane holds fall back to the second part, and a
+// total brightness beyond 32 bits is an error rather than truncated.
+pub fn b_packed(input: &str) -> Result<u32, AocError> {
+    let instructions = parse_instructions(input, SIZE, SIZE)?;
+    if instructions.len() > usize::from(u16::MAX / 2) {
+        return Ok(lights(Part::B, &instructions, SIZE, SIZE));
+    }
+    let mut grid = PackedBrightnessGrid::new(SIZE, SIZE);
+    for instruction in &instructions {
+        grid.apply(instruction);
+    }
+    let brightness = grid.brightness();
+    u32::try_from(brightness).map_err(|_| AocError::unsolvable(format!("the total brightness {brightness} does not fit in 32 bits")))
+}
+
+// Same as the second part, reading the instructions one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u32, AocError> {
+    let mut grid = BrightnessGrid::new(SIZE, SIZE);
+    for instruction in parse_stream(input, |line| parse_checked(line, SIZE, SIZE)) {
+        grid.apply(&instruction?);
+    }
+    Ok(grid.brightness())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn stream() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn out_of_bounds_instructions_are_rejected() {
+        let err = a("turn on 0,0 through 9,9\ntoggle 10,10 through 1000,10").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: light 1000,10 is outside the 1000x1000 grid");
+        let err = b("turn off 5,5 through 4,9").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: corner 5,5 is past corner 4,9");
+        assert!(solve_auto_size(Part::A, "turn on 0,0 through 99999,99999").is_err());
+    }
+
+    #[test]
+    fn auto_size_agrees_on_examples() {
+        for example in EXAMPLES {
+            let expected: u32 = example.answer.parse().unwrap();
+            assert_eq!(solve_auto_size(example.part, example.input).unwrap(), expected, "{}", example.input);
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    /// An operation on a [`Grid`], applied alike to the reference model.
+    #[derive(Debug, Clone)]
+    enum Action {
+        Update(Op, usize, usize, usize, usize),
+        Set(usize, usize, bool),
+        Step,
+    }
+
+    /// A naive grid of lights, one `bool` per light.
+    struct Model {
+        lights: Vec<Vec<bool>>,
+    }
+
+    impl Model {
+        fn apply(&mut self, action: &Action) {
+            let (width, height) = (self.lights[0].len(), self.lights.len());
+            match *action {
+                Action::Update(op, x1, y1, x2, y2) => {
+                    for row in self.lights.iter_mut().take(y2).skip(y1) {
+                        for light in row.iter_mut().take(x2).skip(x1) {
+                            *light = match op {
+                                Op::On => true,
+                                Op::Off => false,
+                                Op::Toggle => !*light,
+                            };
+                        }
+                    }
+                }
+                Action::Set(x, y, on) => self.lights[y % height][x % width] = on,
+                Action::Step => {
+                    let on = |x: usize, y: usize| self.lights[y][x];
+                    let lights = (0..height)
+                        .map(|y| (0..width)
+                            .map(|x| {
+                                let neighbors = (y.saturating_sub(1)..=(y + 1).min(height - 1))
+                                    .flat_map(|ny| (x.saturating_sub(1)..=(x + 1).min(width - 1)).map(move |nx| (nx, ny)))
+                                    .filter(|&(nx, ny)| (nx, ny) != (x, y) && on(nx, ny))
+                                    .count();
+                                neighbors == 3 || (neighbors == 2 && on(x, y))
+                            })
+                            .collect())
+                        .collect();
+                    self.lights = lights;
+                }
+            }
+        }
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![Just(Op::On), Just(Op::Off), Just(Op::Toggle)]
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        (op(), 0..1100usize, 0..1100usize, 0..1100usize, 0..1100usize)
+            .prop_map(|(op, x1, y1, x2, y2)| Instruction { op, from: Point::new(x1, y1), to: Point::new(x2, y2) })
+    }
+
+    fn action() -> impl Strategy<Value = Action> {
+        // Coordinates go past the edges to exercise the clipping.
+        prop_oneof![
+            4 => (op(), 0..400usize, 0..12usize, 0..400usize, 0..12usize)
+                .prop_map(|(op, x1, y1, x2, y2)| Action::Update(op, x1, y1, x2, y2)),
+            2 => (0..400usize, 0..12usize, any::<bool>()).prop_map(|(x, y, on)| Action::Set(x, y, on)),
+            1 => Just(Action::Step),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn parallel_updates_match_serial_updates(
+            instructions in proptest::collection::vec(instruction(), 0..50),
+        ) {
+            let mut serial = Grid::new(1000, 1000);
+            for instruction in &instructions {
+                serial.apply(instruction);
+            }
+            let mut parallel = Grid::new(1000, 1000);
+            parallel.update_parallel(&instructions);
+            prop_assert_eq!(parallel.grid, serial.grid);
+        }
+
+        #[test]
+        fn sweep_counts_match_grid_counts(
+            instructions in proptest::collection::vec(instruction(), 0..50),
+        ) {
+            // The sweep expects instructions within the grid, as parsed.
+            let instructions: Vec<Instruction> = instructions.into_iter()
+                .map(|Instruction { op, from, to }| Instruction {
+                    op,
+                    from: Point::new(from.x.min(to.x).min(999), from.y.min(to.y).min(999)),
+                    to: Point::new(from.x.max(to.x).min(999), from.y.max(to.y).min(999)),
+                })
+                .collect();
+            let mut grid = Grid::new(1000, 1000);
+            for instruction in &instructions {
+                grid.apply(instruction);
+            }
+            prop_assert_eq!(count_sweep(&instructions, 1000, 1000), grid.count());
+        }
+
+        #[test]
+        fn packed_brightness_matches_brightness(
+            instructions in proptest::collection::vec(instruction(), 0..50),
+        ) {
+            let mut grid = BrightnessGrid::new(1000, 1000);
+            let mut packed = PackedBrightnessGrid::new(1000, 1000);
+            for instruction in &instructions {
+                grid.apply(instruction);
+                packed.apply(instruction);
+            }
+            prop_assert_eq!(packed.brightness(), u64::from(grid.brightness()));
+        }
+
+        #[test]
+        fn grid_matches_naive_model(
+            width in prop_oneof![1..300usize, Just(128usize), Just(256usize)],
+            height in 1..10usize,
+            actions in proptest::collection::vec(action(), 0..30),
+        ) {
+            let mut grid = Grid::new(width, height);
+            let mut model = Model { lights: vec![vec![false; width]; height] };
+            for action in &actions {
+                match *action {
+                    Action::Update(op, x1, y1, x2, y2) => grid.update(op, Rect::new(Point::new(x1, y1), Point::new(x2, y2))),
+                    Action::Set(x, y, on) => grid.set(x % width, y % height, on),
+                    Action::Step => grid.step(),
+                }
+                model.apply(action);
+                for (y, row) in model.lights.iter().enumerate() {
+                    for (x, &on) in row.iter().enumerate() {
+                        prop_assert_eq!(grid.get(x, y), on, "light ({}, {}) after {:?}", x, y, action);
+                    }
+                }
+                let count = model.lights.iter().flatten().filter(|&&on| on).count() as u32;
+                prop_assert_eq!(grid.count(), count, "count after {:?}", action);
+            }
+        }
+    }
+}
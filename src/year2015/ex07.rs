@@ -0,0 +1,270 @@
+//! [Day 7: Some Assembly Required](https://adventofcode.com/2015/day/7).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex07::a;
+//! let circuit = "123 -> x\n456 -> y\nx AND y -> d\nx OR y -> e\nx LSHIFT 2 -> f\ny RSHIFT 2 -> g\nNOT x -> h\nNOT y -> i";
+//! assert_eq!(a(&format!("{circuit}\nd -> a")).unwrap(), 72);
+//! assert_eq!(a(&format!("{circuit}\nh -> a")).unwrap(), 65412);
+//! ```
+
+use crate::answer::Answer;
+use crate::error::{parse_lines, AocError};
+use crate::solution::{Example, Part, Solution};
+use crate::util::intern::Interner;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 7 }
+    fn title(&self) -> &str { "Some Assembly Required" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(Circuit::parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "123 -> x\n456 -> y\nx AND y -> d\nx OR y -> e\nx LSHIFT 2 -> f\ny RSHIFT 2 -> g\nNOT x -> h\nNOT y -> i\nd -> a", answer: "72" },
+];
+
+/// An input of a [`Gate`]: either a constant signal or the signal of another wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wire {
+    /// A constant signal.
+    Signal(u16),
+    /// The signal carried by the wire with this ID in its [`Circuit`].
+    Named(u16),
+}
+
+/// A gate driving the signal of a wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate {
+    /// Forwards the signal of a [`Wire`].
+    Assign(Wire),
+    /// Bitwise AND of two [`Wire`]s.
+    And(Wire, Wire),
+    /// Bitwise OR of two [`Wire`]s.
+    Or(Wire, Wire),
+    /// Left shift of a [`Wire`] by a constant amount.
+    LShift(Wire, u16),
+    /// Right shift of a [`Wire`] by a constant amount.
+    RShift(Wire, u16),
+    /// Bitwise complement of a [`Wire`].
+    Not(Wire),
+}
+
+/// Parses a line of the input.
+///
+/// # Arguments
+/// * `line` - The line to parse, such as `x AND y -> d`.
+/// * `wires` - The IDs of the wires named so far, where new ones are added.
+///
+/// # Returns
+/// A tuple of the ID of the target wire and the [`Gate`] driving it.
+///
+/// # Errors
+/// Returns a description of the problem if the line is malformed.
+fn parse_line(line: &str, wires: &mut Interner) -> Result<(u16, Gate), String> {
+    let (expression, target) = line.split_once(" -> ").ok_or("missing target wire")?;
+    let mut wire = |s: &str| match s.parse() {
+        Ok(signal) => Ok(Wire::Signal(signal)),
+        Err(_) => wires.intern(s).map(Wire::Named),
+    };
+    let shift = |s: &str| match s.parse::<u16>() {
+        Ok(n) if n < 16 => Ok(n),
+        _ => Err(format!("invalid shift amount '{s}'")),
+    };
+    let gate = match expression.split(' ').collect::<Vec<_>>()[..] {
+        [x] => Gate::Assign(wire(x)?),
+        ["NOT", x] => Gate::Not(wire(x)?),
+        [x, "AND", y] => Gate::And(wire(x)?, wire(y)?),
+        [x, "OR", y] => Gate::Or(wire(x)?, wire(y)?),
+        [x, "LSHIFT", n] => Gate::LShift(wire(x)?, shift(n)?),
+        [x, "RSHIFT", n] => Gate::RShift(wire(x)?, shift(n)?),
+        _ => return Err(format!("invalid expression '{expression}'")),
+    };
+    Ok((wires.intern(target)?, gate))
+}
+
+/// A circuit of wires connected by [`Gate`]s.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex07::Circuit;
+/// let circuit = Circuit::parse("123 -> x\n456 -> y\nx AND y -> d\nNOT x -> h").unwrap();
+/// assert_eq!(circuit.evaluate("d").unwrap(), 72);
+/// assert_eq!(circuit.evaluate("h").unwrap(), 65412);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+    /// The ID of each wire, which indexes the gates.
+    wires: Interner,
+    /// The [`Gate`] driving each wire, if any.
+    gates: Vec<Option<Gate>>,
+}
+
+impl Circuit {
+    /// Parses a [`Circuit`] from the puzzle input.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut wires = Interner::default();
+        let connections = parse_lines(input, |line| parse_line(line, &mut wires))?;
+        let mut circuit = Circuit { gates: vec![None; wires.len()], wires };
+        for (wire, gate) in connections {
+            circuit.set(wire, gate);
+        }
+        Ok(circuit)
+    }
+
+    /// Returns the ID of the wire `name`, if the circuit has such a wire.
+    pub fn wire(&self, name: &str) -> Option<u16> {
+        self.wires.get(name)
+    }
+
+    /// Returns the name of the wire with this ID.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex07::Circuit;
+    /// let circuit = Circuit::parse("123 -> x\nNOT x -> h").unwrap();
+    /// assert_eq!(circuit.name(circuit.wire("h").unwrap()), "h");
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the circuit has no wire with this ID.
+    pub fn name(&self, wire: u16) -> &str {
+        self.wires.name(wire)
+    }
+
+    /// Connects `gate` to the wire with ID `wire`, replacing whatever drove
+    /// it before.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex07::{Circuit, Gate, Wire};
+    /// let mut circuit = Circuit::parse("123 -> x\nNOT x -> h").unwrap();
+    /// circuit.set(circuit.wire("x").unwrap(), Gate::Assign(Wire::Signal(0)));
+    /// assert_eq!(circuit.evaluate("h").unwrap(), 65535);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the circuit has no wire with this ID.
+    pub fn set(&mut self, wire: u16, gate: Gate) {
+        self.gates[usize::from(wire)] = Some(gate);
+    }
+
+    /// Returns the signal carried by the wire `name`.
+    ///
+    /// Every wire is evaluated at most once, its signal being memoized for
+    /// the other gates it feeds.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex07::Circuit;
+    /// let circuit = Circuit::parse("123 -> x\n456 -> y\nx OR y -> e\nx LSHIFT 2 -> f\ny RSHIFT 2 -> g").unwrap();
+    /// assert_eq!(circuit.evaluate("e").unwrap(), 507);
+    /// assert_eq!(circuit.evaluate("f").unwrap(), 492);
+    /// assert_eq!(circuit.evaluate("g").unwrap(), 114);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if a wire involved has no [`Gate`]
+    /// driving it, or if the wires involved form a loop, which is named.
+    pub fn evaluate(&self, name: &str) -> Result<u16, AocError> {
+        let wire = self.wire(name).ok_or_else(|| AocError::unsolvable(format!("wire {name} has no signal")))?;
+        self.evaluate_memoized(wire, &mut vec![None; self.gates.len()], &mut Vec::new())
+    }
+
+    /// Evaluates the wire `wire` given the signals evaluated so far.
+    ///
+    /// # Arguments
+    /// * `wire` - The ID of the wire to evaluate.
+    /// * `signals` - The memoized signal of every wire evaluated so far.
+    /// * `path` - The wires being evaluated, each one feeding the previous
+    ///   one, to detect loops.
+    fn evaluate_memoized(&self, wire: u16, signals: &mut [Option<u16>], path: &mut Vec<u16>) -> Result<u16, AocError> {
+        if let Some(signal) = signals[usize::from(wire)] {
+            return Ok(signal);
+        }
+        if let Some(start) = path.iter().position(|&other| other == wire) {
+            let cycle: Vec<_> = path[start..].iter().chain([&wire]).map(|&wire| self.name(wire)).collect();
+            return Err(AocError::unsolvable(format!("wires {} form a loop", cycle.join(" <- "))));
+        }
+        let gate = self.gates[usize::from(wire)]
+            .ok_or_else(|| AocError::unsolvable(format!("wire {} has no signal", self.name(wire))))?;
+        path.push(wire);
+        let mut input = |input: Wire| match input {
+            Wire::Signal(signal) => Ok(signal),
+            Wire::Named(input) => self.evaluate_memoized(input, signals, path),
+        };
+        let signal = match gate {
+            Gate::Assign(x) => input(x)?,
+            Gate::And(x, y) => input(x)? & input(y)?,
+            Gate::Or(x, y) => input(x)? | input(y)?,
+            Gate::LShift(x, n) => input(x)? << n,
+            Gate::RShift(x, n) => input(x)? >> n,
+            Gate::Not(x) => !input(x)?,
+        };
+        path.pop();
+        signals[usize::from(wire)] = Some(signal);
+        Ok(signal)
+    }
+}
+
+// 7th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/7
+//
+// This is a solution to the first part of the puzzle.
+// The signal of wire `a` is found by evaluating the circuit backwards
+// from `a`, memoizing the signal of every wire visited.
+pub fn a(input: &str) -> Result<u16, AocError> {
+    Circuit::parse(input)?.evaluate("a")
+}
+
+// This is a solution to the second part of the puzzle.
+// The signal of wire `a` is fed into wire `b`, and the circuit is
+// evaluated again.
+pub fn b(input: &str) -> Result<u16, AocError> {
+    let mut circuit = Circuit::parse(input)?;
+    let signal = circuit.evaluate("a")?;
+    // Without a wire b, nothing changes and the signal of a stays the same.
+    if let Some(b) = circuit.wire("b") {
+        circuit.set(b, Gate::Assign(Wire::Signal(signal)));
+    }
+    circuit.evaluate("a")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn example_wires() {
+        let circuit = Circuit::parse(EXAMPLES[0].input).unwrap();
+        let expected = [("d", 72), ("e", 507), ("f", 492), ("g", 114), ("h", 65412), ("i", 65079), ("x", 123), ("y", 456)];
+        for (wire, signal) in expected {
+            assert_eq!(circuit.evaluate(wire).unwrap(), signal, "wire {wire}");
+        }
+    }
+
+    #[test]
+    fn loops_are_named() {
+        let circuit = Circuit::parse("1 -> x\nx AND z -> y\ny -> z\nz -> a").unwrap();
+        let err = circuit.evaluate("a").unwrap_err().to_string();
+        assert_eq!(err, "no solution: wires z <- y <- z form a loop");
+    }
+}
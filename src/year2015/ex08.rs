@@ -0,0 +1,263 @@
+//! [Day 8: Matchsticks](https://adventofcode.com/2015/day/8).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex08::{a, b};
+//! let input = [r#""""#, r#""abc""#, r#""aaa\"aaa""#, r#""\x27""#].join("\n");
+//! assert_eq!(a(&input).unwrap(), 12);
+//! assert_eq!(b(&input), 19);
+//! ```
+
+use std::fmt;
+use std::io::BufRead;
+use std::str::CharIndices;
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 8 }
+    fn title(&self) -> &str { "Matchsticks" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { Ok(b(input).into()) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(check(input)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+}
+
+/// The string literals of the examples.
+const LITERALS: &str = r#"""
+"abc"
+"aaa\"aaa"
+"\x27""#;
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: LITERALS, answer: "12" },
+    Example { part: Part::B, input: LITERALS, answer: "19" },
+];
+
+/// An error found while tokenizing a string literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EscapeError {
+    /// The literal is not enclosed in double quotes.
+    Unquoted,
+    /// A double quote appears unescaped inside the literal.
+    UnescapedQuote { position: usize },
+    /// A backslash is followed by something other than `\`, `"` or `x`.
+    InvalidEscape { position: usize, found: char },
+    /// A `\x` escape is not followed by two hex digits.
+    InvalidHex { position: usize },
+    /// The literal ends in the middle of an escape sequence.
+    UnterminatedEscape { position: usize },
+}
+
+impl fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EscapeError::Unquoted => write!(f, "literal is not enclosed in double quotes"),
+            EscapeError::UnescapedQuote { position } =>
+                write!(f, "unescaped double quote at position {position}"),
+            EscapeError::InvalidEscape { position, found } =>
+                write!(f, "invalid escape sequence \\{found} at position {position}"),
+            EscapeError::InvalidHex { position } =>
+                write!(f, "\\x escape at position {position} is not followed by two hex digits"),
+            EscapeError::UnterminatedEscape { position } =>
+                write!(f, "unterminated escape sequence at position {position}"),
+        }
+    }
+}
+
+impl EscapeError {
+    /// Returns the byte offset of the error within the literal, opening
+    /// quote included, or `None` if it concerns the whole literal.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            EscapeError::Unquoted => None,
+            EscapeError::UnescapedQuote { position }
+            | EscapeError::InvalidEscape { position, .. }
+            | EscapeError::InvalidHex { position }
+            | EscapeError::UnterminatedEscape { position } => Some(position),
+        }
+    }
+}
+
+impl std::error::Error for EscapeError {}
+
+/// An iterator over the characters represented by a string literal.
+///
+/// The contents of the literal are tokenized one character or escape
+/// sequence at a time, yielding the character each token stands for.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex08::Tokens;
+/// let chars: Result<String, _> = Tokens::new(r#""a\"\x41""#).unwrap().collect();
+/// assert_eq!(chars.unwrap(), "a\"A");
+/// ```
+pub struct Tokens<'a> {
+    /// The characters of the literal, without the enclosing quotes.
+    chars: CharIndices<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    /// Creates a new [`Tokens`] iterator over the provided literal.
+    ///
+    /// # Arguments
+    /// * `literal` - The literal, including its enclosing double quotes.
+    ///
+    /// # Errors
+    /// Returns [`EscapeError::Unquoted`] if the literal is not quoted.
+    pub fn new(literal: &'a str) -> Result<Self, EscapeError> {
+        if literal.len() < 2 || !literal.starts_with('"') || !literal.ends_with('"') {
+            return Err(EscapeError::Unquoted);
+        }
+        Ok(Tokens { chars: literal[1..literal.len() - 1].char_indices() })
+    }
+
+    /// Reads one hex digit of a `\x` escape started at `position`.
+    fn hex_digit(&mut self, position: usize) -> Result<u32, EscapeError> {
+        match self.chars.next() {
+            None => Err(EscapeError::UnterminatedEscape { position }),
+            Some((_, ch)) => ch.to_digit(16).ok_or(EscapeError::InvalidHex { position }),
+        }
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<char, EscapeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, ch) = self.chars.next()?;
+        // Positions are reported within the whole literal, opening quote included.
+        let position = index + 1;
+        Some(match ch {
+            '"' => Err(EscapeError::UnescapedQuote { position }),
+            '\\' => match self.chars.next() {
+                None => Err(EscapeError::UnterminatedEscape { position }),
+                Some((_, '\\')) => Ok('\\'),
+                Some((_, '"')) => Ok('"'),
+                Some((_, 'x')) => self.hex_digit(position)
+                    .and_then(|high| Ok(high * 16 + self.hex_digit(position)?))
+                    .map(|code| char::from_u32(code).unwrap()),
+                Some((_, found)) => Err(EscapeError::InvalidEscape { position, found }),
+            },
+            ch => Ok(ch),
+        })
+    }
+}
+
+/// Returns the number of characters a string literal represents in memory.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex08::decoded_len;
+/// assert_eq!(decoded_len(r#""aaa\"aaa""#), Ok(7));
+/// assert_eq!(decoded_len(r#""\x27""#), Ok(1));
+/// assert!(decoded_len(r#""\q""#).is_err());
+/// ```
+///
+/// # Errors
+/// Returns an [`EscapeError`] if the literal is malformed.
+pub fn decoded_len(literal: &str) -> Result<usize, EscapeError> {
+    Tokens::new(literal)?.try_fold(0, |len, token| token.map(|_| len + 1))
+}
+
+/// Returns the length of the literal encoding the provided string,
+/// enclosing quotes included.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex08::encoded_len;
+/// assert_eq!(encoded_len(r#""""#), 6);
+/// assert_eq!(encoded_len(r#""\x27""#), 11);
+/// ```
+pub fn encoded_len(string: &str) -> usize {
+    2 + string.chars().map(|ch| if ch == '"' || ch == '\\' { 2 } else { 1 }).sum::<usize>()
+}
+
+/// Tokenizes every literal of the input, locating the first malformed one.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`], with the column of the offending
+/// character when known, if a literal is malformed.
+fn check(input: &str) -> Result<(), AocError> {
+    for (index, line) in input.lines().enumerate() {
+        decoded_len(line).map_err(|err| match err.position() {
+            Some(position) => AocError::parse_at(index + 1, line[..position].chars().count() + 1, &err),
+            None => AocError::parse(index + 1, &err),
+        })?;
+    }
+    Ok(())
+}
+
+// 8th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/8
+//
+// This is a solution to the first part of the puzzle.
+// Each literal is tokenized to count the characters it represents, both
+// sides counting characters rather than bytes.
+pub fn a(input: &str) -> Result<usize, AocError> {
+    let overhead = parse_lines(input, |line| decoded_len(line).map(|decoded| line.chars().count() - decoded))?;
+    Ok(overhead.into_iter().sum())
+}
+
+// Same as the first part, reading the literals one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<usize, AocError> {
+    parse_stream(input, |line| decoded_len(line).map(|decoded| line.chars().count() - decoded)).sum()
+}
+
+// This is a solution to the second part of the puzzle.
+// Each literal is encoded again, escaping its quotes and backslashes.
+pub fn b(input: &str) -> usize {
+    input.lines().map(|line| encoded_len(line) - line.chars().count()).sum()
+}
+
+// Same as the second part, reading the literals one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<usize, AocError> {
+    let mut overhead = 0;
+    for line in input.lines() {
+        let line = line?;
+        overhead += encoded_len(&line) - line.chars().count();
+    }
+    Ok(overhead)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn stream() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn characters_are_not_bytes() {
+        let input = r#""éééééé""#;
+        assert_eq!(a(input).unwrap(), 2);
+        assert_eq!(a_stream(input.as_bytes()).unwrap(), 2);
+        assert_eq!(b(input), 4);
+        assert_eq!(b_stream(input.as_bytes()).unwrap(), 4);
+    }
+}
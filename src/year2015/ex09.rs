@@ -0,0 +1,311 @@
+//! [Day 9: All in a Single Night](https://adventofcode.com/2015/day/9).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex09::{a, b};
+//! let input = "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141";
+//! assert_eq!(a(input).unwrap(), 605);
+//! assert_eq!(b(input).unwrap(), 982);
+//! ```
+
+use std::io::{BufRead, Write};
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Example, Implementation, Part, Solution};
+use crate::util::intern::Interner;
+use crate::util::parse::{number, split};
+use crate::util::tsp::{best_path_route, Algorithm};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 9 }
+    fn title(&self) -> &str { "All in a Single Night" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(Graph::parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+
+    fn solve_explained(&self, part: Part, input: &str, _context: &Context, out: &mut dyn Write) -> Result<Answer, AocError> {
+        let graph = Graph::parse(input)?;
+        let better = match part {
+            Part::A => u32::min,
+            Part::B => u32::max,
+        };
+        let (distance, route) = graph.best_itinerary(better)?;
+        writeln!(out, "{} = {distance}", route.join(" -> "))?;
+        Ok(distance.into())
+    }
+}
+
+/// The alternate implementations of both parts, one per [`Algorithm`].
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "brute-force", part: Part::A, solve: |input| a_with(input, Algorithm::BruteForce).map(Answer::from) },
+    Implementation { name: "held-karp", part: Part::A, solve: |input| a_with(input, Algorithm::HeldKarp).map(Answer::from) },
+    Implementation { name: "brute-force", part: Part::B, solve: |input| b_with(input, Algorithm::BruteForce).map(Answer::from) },
+    Implementation { name: "held-karp", part: Part::B, solve: |input| b_with(input, Algorithm::HeldKarp).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141", answer: "605" },
+    Example { part: Part::B, input: "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141", answer: "982" },
+];
+
+/// An undirected graph of cities and the distances between them.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex09::Graph;
+/// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+/// assert_eq!(graph.cities(), ["London", "Dublin", "Belfast"]);
+/// assert_eq!(graph.distance(1, 2), Some(141));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    /// The ID of each city, which indexes the distances.
+    cities: Interner,
+    /// The distance between each pair of cities, if they are connected.
+    distances: Vec<Vec<Option<u32>>>,
+}
+
+impl Graph {
+    /// Parses a [`Graph`] from lines such as `London to Dublin = 464`.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut graph = Graph::default();
+        parse_lines(input, |line| {
+            let (from, to, distance) = parse_route(line)?;
+            graph.add_route(from, to, distance)
+        })?;
+        Ok(graph)
+    }
+
+    /// Reads a [`Graph`] from lines such as `London to Dublin = 464`, one
+    /// line at a time.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex09::Graph;
+    /// let graph = Graph::read("London to Dublin = 464\nLondon to Belfast = 518".as_bytes()).unwrap();
+    /// assert_eq!(graph.cities(), ["London", "Dublin", "Belfast"]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed, or an
+    /// [`AocError::Io`] if it cannot be read.
+    pub fn read(input: impl BufRead) -> Result<Self, AocError> {
+        let mut graph = Graph::default();
+        for route in parse_stream(input, |line| {
+            let (from, to, distance) = parse_route(line)?;
+            graph.add_route(from, to, distance)
+        }) {
+            route?;
+        }
+        Ok(graph)
+    }
+
+    /// Connects two cities, adding them if they are new.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if there are too many cities.
+    fn add_route(&mut self, from: &str, to: &str, distance: u32) -> Result<(), String> {
+        let from = self.city(from)?;
+        let to = self.city(to)?;
+        self.distances[from][to] = Some(distance);
+        self.distances[to][from] = Some(distance);
+        Ok(())
+    }
+
+    /// Returns the index of the named city, adding it if it is new.
+    fn city(&mut self, name: &str) -> Result<usize, String> {
+        let index = usize::from(self.cities.intern(name)?);
+        if index == self.distances.len() {
+            for row in &mut self.distances {
+                row.push(None);
+            }
+            self.distances.push(vec![None; self.cities.len()]);
+        }
+        Ok(index)
+    }
+
+    /// Returns the names of the cities, indexed by city.
+    pub fn cities(&self) -> &[String] {
+        self.cities.names()
+    }
+
+    /// Returns the distance between two cities, if they are connected.
+    pub fn distance(&self, from: usize, to: usize) -> Option<u32> {
+        self.distances[from][to]
+    }
+
+    /// Returns the length of the best route visiting every city exactly once.
+    ///
+    /// # Arguments
+    /// * `better` - Picks the better of two route lengths, such as [`u32::min`].
+    /// * `algorithm` - The [`Algorithm`] searching for the route.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if no route visits every city,
+    /// or if there are too many cities to search.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex09::Graph;
+    /// use aoc2015::util::tsp::Algorithm;
+    /// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+    /// assert_eq!(graph.best_route(u32::min, Algorithm::HeldKarp).unwrap(), 605);
+    /// assert_eq!(graph.best_route(u32::max, Algorithm::BruteForce).unwrap(), 982);
+    /// ```
+    pub fn best_route(&self, better: fn(u32, u32) -> u32, algorithm: Algorithm) -> Result<u32, AocError> {
+        algorithm.best_path(&self.distances, better)?.ok_or_else(|| AocError::unsolvable("no route visits every city"))
+    }
+
+    /// Returns the best route visiting every city exactly once, with the
+    /// names of its cities in order.
+    ///
+    /// # Arguments
+    /// * `better` - Picks the better of two route lengths, such as [`u32::min`].
+    ///
+    /// # Returns
+    /// The length of the best route and its cities.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if no route visits every city,
+    /// or if there are too many cities to search.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex09::Graph;
+    /// let graph = Graph::parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141").unwrap();
+    /// assert_eq!(graph.best_itinerary(u32::min).unwrap(), (605, vec!["Belfast", "Dublin", "London"]));
+    /// ```
+    pub fn best_itinerary(&self, better: fn(u32, u32) -> u32) -> Result<(u32, Vec<&str>), AocError> {
+        let (distance, route) = best_path_route(&self.distances, better)?.ok_or_else(|| AocError::unsolvable("no route visits every city"))?;
+        Ok((distance, route.into_iter().map(|city| self.cities()[city].as_str()).collect()))
+    }
+}
+
+/// Parses a line such as `London to Dublin = 464` into its two cities and
+/// the distance between them.
+fn parse_route(line: &str) -> Result<(&str, &str, u32), String> {
+    let [route, distance] = split(line, " = ")?;
+    let [from, to] = split(route, " to ")?;
+    Ok((from, to, number(distance)?))
+}
+
+// 9th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/9
+//
+// This is a solution to the first part of the puzzle.
+// The shortest route visiting every city is found with Held–Karp.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    a_with(input, Algorithm::HeldKarp)
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, but looking for the longest route.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    b_with(input, Algorithm::HeldKarp)
+}
+
+// Same as the first part, with a choice of algorithm.
+pub fn a_with(input: &str, algorithm: Algorithm) -> Result<u32, AocError> {
+    Graph::parse(input)?.best_route(u32::min, algorithm)
+}
+
+// Same as the second part, with a choice of algorithm.
+pub fn b_with(input: &str, algorithm: Algorithm) -> Result<u32, AocError> {
+    Graph::parse(input)?.best_route(u32::max, algorithm)
+}
+
+// Same as the first part, reading the routes one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u32, AocError> {
+    Graph::read(input)?.best_route(u32::min, Algorithm::HeldKarp)
+}
+
+// Same as the second part, reading the routes one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u32, AocError> {
+    Graph::read(input)?.best_route(u32::max, Algorithm::HeldKarp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn stream() {
+        for example in EXAMPLES {
+            example.check_stream(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn explanation_shows_the_route() {
+        let mut out = Vec::new();
+        let answer = Solver.solve_explained(Part::B, EXAMPLES[1].input, &Context::default(), &mut out).unwrap();
+        assert_eq!(answer.to_string(), EXAMPLES[1].answer);
+        assert_eq!(String::from_utf8(out).unwrap(), "Belfast -> London -> Dublin = 982\n");
+    }
+
+    #[test]
+    fn itineraries_are_feasible() {
+        let graph = Graph::parse(EXAMPLES[0].input).unwrap();
+        let index = |name: &str| graph.cities().iter().position(|city| city == name).unwrap();
+        for (better, expected) in [(u32::min as fn(u32, u32) -> u32, 605), (u32::max, 982)] {
+            let (distance, route) = graph.best_itinerary(better).unwrap();
+            assert_eq!(distance, expected);
+            let mut visited: Vec<usize> = route.iter().map(|&city| index(city)).collect();
+            let length: u32 = visited.windows(2).map(|leg| graph.distance(leg[0], leg[1]).unwrap()).sum();
+            assert_eq!(length, distance);
+            visited.sort_unstable();
+            assert_eq!(visited, (0..graph.cities().len()).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn brute_force_rejects_more_cities_than_it_can_search() {
+        let input: Vec<String> = (0..13).flat_map(|from| (from + 1..13).map(move |to| format!("c{from} to c{to} = {}", from + to))).collect();
+        let input = input.join("\n");
+        assert!(matches!(a_with(&input, Algorithm::BruteForce), Err(AocError::Unsolvable(_))));
+        assert!(a_with(&input, Algorithm::HeldKarp).is_ok());
+    }
+
+    #[test]
+    fn too_many_cities_are_rejected() {
+        let input: Vec<String> = (0..28).flat_map(|from| (from + 1..28).map(move |to| format!("c{from} to c{to} = {}", from + to))).collect();
+        let input = input.join("\n");
+        assert!(matches!(a(&input), Err(AocError::Unsolvable(_))));
+        assert!(matches!(Solver.solve_explained(Part::B, &input, &Context::default(), &mut Vec::new()), Err(AocError::Unsolvable(_))));
+    }
+}
@@ -0,0 +1,433 @@
+//! [Day 10: Elves Look, Elves Say](https://adventofcode.com/2015/day/10).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex10::{look_and_say, step};
+//! assert_eq!(step(&[1]), [1, 1]);
+//! assert_eq!(step(&[1, 1]), [2, 1]);
+//! assert_eq!(step(&[2, 1]), [1, 2, 1, 1]);
+//! assert_eq!(step(&[1, 2, 1, 1]), [1, 1, 1, 2, 2, 1]);
+//! assert_eq!(step(&[1, 1, 1, 2, 2, 1]), [3, 1, 2, 2, 1, 1]);
+//! assert_eq!(look_and_say("1", 5).unwrap(), 6);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Implementation, Part, Solution};
+use crate::util::natural::Natural;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 10 }
+    fn title(&self) -> &str { "Elves Look, Elves Say" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+}
+
+/// The alternate implementations of both parts.
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "allocating", part: Part::A, solve: |input| a(input).map(Answer::from) },
+    Implementation { name: "buffered", part: Part::A, solve: |input| a_buffered(input).map(Answer::from) },
+    Implementation { name: "conway", part: Part::A, solve: |input| a_conway(input).map(Answer::from) },
+    Implementation { name: "allocating", part: Part::B, solve: |input| b(input).map(Answer::from) },
+    Implementation { name: "buffered", part: Part::B, solve: |input| b_buffered(input).map(Answer::from) },
+    Implementation { name: "conway", part: Part::B, solve: |input| b_conway(input).map(Answer::from) },
+];
+
+/// The largest capacity, in digits, preallocated by [`look_and_say_buffered`]
+/// for each of its buffers. Longer sequences grow the buffers as needed.
+const MAX_PREALLOCATED: usize = 1 << 26;
+
+/// Parses the puzzle input into a sequence of digit values.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input contains anything but
+/// decimal digits.
+fn parse(input: &str) -> Result<Vec<u8>, AocError> {
+    input.chars()
+        .enumerate()
+        .map(|(index, ch)| ch.to_digit(10)
+            .map(|digit| digit as u8)
+            .ok_or_else(|| AocError::parse_at(1, index + 1, format!("invalid digit '{ch}'"))))
+        .collect()
+}
+
+/// Applies one look-and-say step to a sequence of digit values.
+///
+/// The sequence is run-length encoded into a new vector reserving 40% more
+/// capacity than the current sequence, enough for the ~30% growth of each
+/// step so the vector never reallocates.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex10::step;
+/// assert_eq!(step(&[1, 1, 1, 2, 2, 1]), [3, 1, 2, 2, 1, 1]);
+/// ```
+pub fn step(digits: &[u8]) -> Vec<u8> {
+    let mut next = Vec::with_capacity(digits.len() * 14 / 10 + 2);
+    step_into(digits, &mut next);
+    next
+}
+
+/// Applies one look-and-say step to a sequence of digit values, writing
+/// the result to `next` in place of its contents, so that its capacity is
+/// reused.
+///
+/// The length of a run is written in as many digits as it takes, which is
+/// one except in seeds repeating a digit 10 times or more.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex10::step_into;
+/// let mut next = vec![9; 10];
+/// step_into(&[2, 1], &mut next);
+/// assert_eq!(next, [1, 2, 1, 1]);
+/// step_into(&[1; 12], &mut next);
+/// assert_eq!(next, [1, 2, 1]);
+/// ```
+pub fn step_into(digits: &[u8], next: &mut Vec<u8>) {
+    next.clear();
+    for run in digits.chunk_by(|a, b| a == b) {
+        if run.len() < 10 {
+            next.push(run.len() as u8);
+        } else {
+            next.extend(run.len().to_string().bytes().map(|digit| digit - b'0'));
+        }
+        next.push(run[0]);
+    }
+}
+
+/// Returns the length of the look-and-say step of a sequence without
+/// computing it, each run of digits becoming its length and its digit.
+fn step_len(digits: &[u8]) -> usize {
+    digits.chunk_by(|a, b| a == b).map(|run| run.len().ilog10() as usize + 2).sum()
+}
+
+/// Returns the length of the sequence after applying `iterations`
+/// look-and-say steps to `seed`.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex10::look_and_say;
+/// assert_eq!(look_and_say("1", 5).unwrap(), "312211".len());
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the seed contains anything but
+/// decimal digits.
+pub fn look_and_say(seed: &str, iterations: usize) -> Result<usize, AocError> {
+    Ok((0..iterations).fold(parse(seed)?, |digits, iteration| {
+        tracing::trace!(iteration, length = digits.len(), "look-and-say step");
+        step(&digits)
+    }).len())
+}
+
+/// Returns the length of the sequence after applying `iterations`
+/// look-and-say steps to `seed`, like [`look_and_say`], without allocating
+/// between steps.
+///
+/// The steps alternate between two buffers preallocated for the expected
+/// length of the last sequence, which grows by about 30% each step. The
+/// last step is only counted, never written.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex10::look_and_say_buffered;
+/// assert_eq!(look_and_say_buffered("1", 5).unwrap(), "312211".len());
+/// assert_eq!(look_and_say_buffered("1", 0).unwrap(), 1);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the seed contains anything but
+/// decimal digits.
+pub fn look_and_say_buffered(seed: &str, iterations: usize) -> Result<usize, AocError> {
+    let mut digits = parse(seed)?;
+    let Some(written) = iterations.checked_sub(1) else { return Ok(digits.len()) };
+    let growth = 1.31f64.powi(written.min(i32::MAX as usize) as i32);
+    let capacity = ((digits.len() as f64 * growth) as usize).clamp(digits.len(), MAX_PREALLOCATED) + 2;
+    digits.reserve(capacity - digits.len());
+    let mut next = Vec::with_capacity(capacity);
+    for iteration in 0..written {
+        tracing::trace!(iteration, length = digits.len(), "look-and-say step");
+        step_into(&digits, &mut next);
+        std::mem::swap(&mut digits, &mut next);
+    }
+    Ok(step_len(&digits))
+}
+
+/// The length of the prefix of a sequence evolved by [`splits`]. Longer
+/// prefixes prove more splits, but take longer to evolve.
+const PREFIX: usize = 64;
+
+/// The most elements [`solve_n`] discovers before giving up, far more than
+/// the 92 common and 2 transuranic elements of Conway, and the few
+/// exotic ones of unusual seeds.
+const MAX_ELEMENTS: usize = 10_000;
+
+/// The longest element [`solve_n`] accepts before giving up, as a sequence
+/// that does not split grows without bound.
+const MAX_ELEMENT_LEN: usize = 4096;
+
+/// Returns whether a sequence following the digit `last` evolves apart
+/// from it forever.
+///
+/// The last digit of a sequence never changes, as every step ends with
+/// the digit of its last run. The two evolve apart as long as no
+/// descendant of `rest` starts with that digit, which is checked on a
+/// prefix of `rest` until its descendants repeat. A prefix that cannot be
+/// evolved any further is assumed not to split, which is always correct,
+/// if slower.
+fn splits(last: u8, rest: &[u8]) -> bool {
+    let mut prefix = rest.to_vec();
+    // Whether the prefix is cut from a longer sequence, in which case the
+    // last run of its next step may be incomplete.
+    let mut cut = false;
+    let mut seen = HashSet::new();
+    while seen.insert((prefix.clone(), cut)) {
+        if prefix.first().is_none_or(|&first| first == last) {
+            return false;
+        }
+        let mut next = step(&prefix);
+        if cut {
+            next.truncate(next.len().saturating_sub(2));
+        }
+        if next.len() > PREFIX {
+            next.truncate(PREFIX);
+            cut = true;
+        }
+        prefix = next;
+    }
+    true
+}
+
+/// Splits a sequence into the elements evolving apart from each other.
+fn split(digits: &[u8]) -> Vec<&[u8]> {
+    let mut elements = Vec::new();
+    let mut start = 0;
+    for end in 1..digits.len() {
+        if digits[end - 1] != digits[end] && splits(digits[end - 1], &digits[end..]) {
+            elements.push(&digits[start..end]);
+            start = end;
+        }
+    }
+    if start < digits.len() {
+        elements.push(&digits[start..]);
+    }
+    elements
+}
+
+/// The elements of the sequences descending from a seed, along with the
+/// transition matrix giving the elements each one decays into.
+#[derive(Default)]
+struct Elements {
+    ids: HashMap<Vec<u8>, usize>,
+    elements: Vec<Vec<u8>>,
+    /// The sparse rows of the transition matrix: the elements each element
+    /// decays into in one step, and how many times.
+    decays: Vec<Vec<(usize, u64)>>,
+}
+
+impl Elements {
+    /// Returns the ID of an element, giving it the next ID if it is new.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if the element is too long or
+    /// there are too many elements.
+    fn id(&mut self, element: &[u8]) -> Result<usize, AocError> {
+        if let Some(&id) = self.ids.get(element) {
+            return Ok(id);
+        }
+        if element.len() > MAX_ELEMENT_LEN || self.elements.len() >= MAX_ELEMENTS {
+            return Err(AocError::unsolvable("the sequence does not split into few enough elements"));
+        }
+        self.ids.insert(element.to_vec(), self.elements.len());
+        self.elements.push(element.to_vec());
+        Ok(self.elements.len() - 1)
+    }
+
+    /// Discovers the elements descending from a seed and their decays.
+    ///
+    /// # Returns
+    /// The elements, and the ID of the elements of the seed.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are too many elements or
+    /// one is too long.
+    fn discover(seed: &[u8]) -> Result<(Elements, Vec<usize>), AocError> {
+        let mut elements = Elements::default();
+        let seed = split(seed).into_iter().map(|element| elements.id(element)).collect::<Result<_, _>>()?;
+        while elements.decays.len() < elements.elements.len() {
+            let decay = step(&elements.elements[elements.decays.len()]);
+            let mut row: Vec<(usize, u64)> = Vec::new();
+            for element in split(&decay) {
+                let id = elements.id(element)?;
+                match row.iter_mut().find(|(other, _)| *other == id) {
+                    Some((_, count)) => *count += 1,
+                    None => row.push((id, 1)),
+                }
+            }
+            elements.decays.push(row);
+        }
+        tracing::debug!(elements = elements.elements.len(), "discovered the elements");
+        Ok((elements, seed))
+    }
+}
+
+/// Returns the length of the sequence after applying `iterations`
+/// look-and-say steps to `seed`, evolving the counts of its elements
+/// rather than the sequence itself.
+///
+/// Conway showed that look-and-say sequences split into elements that
+/// evolve independently of each other, and that after a few steps every
+/// sequence is made of 92 common elements, plus 2 transuranic ones for
+/// seeds holding digits above 3. The elements and the transition matrix
+/// are discovered from the seed, and then each step multiplies the counts
+/// by the matrix, in `O(iterations × 92²)` time. The length, growing by
+/// about 30% each step, is no longer limited by the memory, nor by the
+/// size of the integers.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex10::solve_n;
+/// assert_eq!(solve_n("1", 5).unwrap().to_u64(), Some("312211".len() as u64));
+/// assert_eq!(solve_n("1", 200).unwrap().to_string(), "217493139530990183249866");
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the seed contains anything but
+/// decimal digits, or an [`AocError::Unsolvable`] if it does not split
+/// into elements, which never happens for seeds of a few digits.
+pub fn solve_n(seed: &str, iterations: usize) -> Result<Natural, AocError> {
+    let (elements, seed) = Elements::discover(&parse(seed)?)?;
+    let mut counts = vec![Natural::default(); elements.elements.len()];
+    for id in seed {
+        counts[id] += &Natural::from(1);
+    }
+    for _ in 0..iterations {
+        let mut next = vec![Natural::default(); counts.len()];
+        for (count, row) in counts.iter().zip(&elements.decays).filter(|(count, _)| !count.is_zero()) {
+            for &(id, multiplicity) in row {
+                next[id] += &(count * multiplicity);
+            }
+        }
+        counts = next;
+    }
+    let mut length = Natural::default();
+    for (count, element) in counts.iter().zip(&elements.elements) {
+        length += &(count * element.len() as u64);
+    }
+    Ok(length)
+}
+
+/// Returns the length of the sequence after `iterations` steps with
+/// [`solve_n`].
+///
+/// # Errors
+/// Returns an [`AocError`] if [`solve_n`] fails or the length does not fit
+/// in a `usize`.
+fn conway_len(seed: &str, iterations: usize) -> Result<usize, AocError> {
+    solve_n(seed, iterations)?.to_u64()
+        .and_then(|length| usize::try_from(length).ok())
+        .ok_or_else(|| AocError::unsolvable("the length of the sequence does not fit in a usize"))
+}
+
+// 10th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/10
+//
+// This is a solution to the first part of the puzzle.
+// The look-and-say step is applied 40 times.
+pub fn a(input: &str) -> Result<usize, AocError> {
+    look_and_say(input, 40)
+}
+
+// This is a solution to the second part of the puzzle.
+// The look-and-say step is applied 50 times.
+pub fn b(input: &str) -> Result<usize, AocError> {
+    look_and_say(input, 50)
+}
+
+// Same as the first part, reusing two buffers.
+pub fn a_buffered(input: &str) -> Result<usize, AocError> {
+    look_and_say_buffered(input, 40)
+}
+
+// Same as the second part, reusing two buffers.
+pub fn b_buffered(input: &str) -> Result<usize, AocError> {
+    look_and_say_buffered(input, 50)
+}
+
+// Same as the first part, evolving the counts of Conway's elements.
+pub fn a_conway(input: &str) -> Result<usize, AocError> {
+    conway_len(input, 40)
+}
+
+// Same as the second part, evolving the counts of Conway's elements.
+pub fn b_conway(input: &str) -> Result<usize, AocError> {
+    conway_len(input, 50)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn look_and_say_steps() {
+        let mut digits = vec![1];
+        for expected in [vec![1, 1], vec![2, 1], vec![1, 2, 1, 1], vec![1, 1, 1, 2, 2, 1], vec![3, 1, 2, 2, 1, 1]] {
+            digits = step(&digits);
+            assert_eq!(digits, expected);
+        }
+    }
+
+    #[test]
+    fn long_runs_are_counted_in_decimal() {
+        assert_eq!(step(&[1; 10]), [1, 0, 1]);
+        assert_eq!(step(&[7; 300]), [3, 0, 0, 7]);
+        let seed = "1111111111";
+        assert_eq!(look_and_say(seed, 1).unwrap(), 3);
+        for iterations in 0..=20 {
+            let length = look_and_say(seed, iterations).unwrap();
+            assert_eq!(look_and_say_buffered(seed, iterations).unwrap(), length, "{iterations}");
+            assert_eq!(conway_len(seed, iterations).unwrap(), length, "{iterations}");
+        }
+    }
+
+    #[test]
+    fn buffered_agrees_with_allocating() {
+        for seed in ["1", "3113322113", "1113222113", "22"] {
+            for iterations in 0..=20 {
+                assert_eq!(look_and_say_buffered(seed, iterations).unwrap(), look_and_say(seed, iterations).unwrap());
+            }
+        }
+        assert!(look_and_say_buffered("12a", 3).is_err());
+    }
+
+    #[test]
+    fn conway_agrees_with_allocating() {
+        for seed in ["1", "3113322113", "1113222113", "22", "55", "123456789", ""] {
+            for iterations in 0..=25 {
+                assert_eq!(conway_len(seed, iterations).unwrap(), look_and_say(seed, iterations).unwrap(), "{seed} {iterations}");
+            }
+        }
+    }
+
+    #[test]
+    fn conway_splits_into_92_elements() {
+        let (elements, _) = Elements::discover(&parse("1113222113").unwrap()).unwrap();
+        assert_eq!(elements.elements.len(), 92);
+        assert_eq!(elements.elements.iter().map(Vec::len).max(), Some(42));
+    }
+
+    #[test]
+    fn conway_runs_a_thousand_steps() {
+        let length = "85844621666229138538809004471738614400628611885555479764315823241154945153550844012682002614389096327649600234763978";
+        assert_eq!(solve_n("1113222113", 1000).unwrap().to_string(), length);
+    }
+}
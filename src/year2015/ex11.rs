@@ -0,0 +1,170 @@
+//! [Day 11: Corporate Policy](https://adventofcode.com/2015/day/11).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex11::{a, is_valid};
+//! assert!(!is_valid("hijklmmn"));
+//! assert!(!is_valid("abbceffg"));
+//! assert!(!is_valid("abbcegjk"));
+//! assert_eq!(a("abcdefgh").unwrap(), "abcdffaa");
+//! assert_eq!(a("ghijklmn").unwrap(), "ghjaabcc");
+//! ```
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 11 }
+    fn title(&self) -> &str { "Corporate Policy" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(check_password(input)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "abcdefgh", answer: "abcdffaa" },
+    Example { part: Part::A, input: "ghijklmn", answer: "ghjaabcc" },
+];
+
+/// The letters that may be mistaken for others and are not allowed.
+const CONFUSING: [u8; 3] = [b'i', b'o', b'l'];
+
+/// The fewest letters holding a straight and two pairs, as in `aabcc`.
+const MIN_LENGTH: usize = 5;
+
+/// Returns whether the provided password meets the security requirements.
+///
+/// A valid password includes a straight of three increasing letters, does
+/// not include any confusing letter (`i`, `o` or `l`), and contains two
+/// different, non-overlapping pairs of letters.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex11::is_valid;
+/// assert!(is_valid("abcdffaa"));
+/// assert!(!is_valid("hijklmmn"));
+/// assert!(!is_valid("abbceffg"));
+/// assert!(!is_valid("abbcegjk"));
+/// ```
+pub fn is_valid(password: &str) -> bool {
+    let bytes = password.as_bytes();
+    let straight = bytes.windows(3).any(|w| w[1] == w[0] + 1 && w[2] == w[1] + 1);
+    let confusing = bytes.iter().any(|byte| CONFUSING.contains(byte));
+    let mut pairs = Vec::new();
+    let mut index = 0;
+    while index + 1 < bytes.len() {
+        if bytes[index] == bytes[index + 1] {
+            if !pairs.contains(&bytes[index]) { pairs.push(bytes[index]); }
+            index += 2;
+        } else {
+            index += 1;
+        }
+    }
+    straight && !confusing && pairs.len() >= 2
+}
+
+/// Increments the provided password as a base-26 number of letters.
+///
+/// Confusing letters are skipped: if the password already contains one, the
+/// first of them is incremented and every letter after it is reset to `a`,
+/// jumping past every password that would still contain it.
+fn increment(password: &mut [u8]) {
+    if let Some(index) = password.iter().position(|byte| CONFUSING.contains(byte)) {
+        password[index] += 1;
+        password[index + 1..].fill(b'a');
+        return;
+    }
+    for byte in password.iter_mut().rev() {
+        if *byte == b'z' {
+            *byte = b'a';
+            continue;
+        }
+        *byte += 1;
+        if CONFUSING.contains(byte) { *byte += 1; }
+        return;
+    }
+}
+
+/// Checks that `password` contains only lowercase letters, and enough of
+/// them for a valid password to follow it.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] locating the first other character, or
+/// if the password is shorter than [`MIN_LENGTH`] letters.
+fn check_password(password: &str) -> Result<(), AocError> {
+    match password.char_indices().find(|(_, ch)| !ch.is_ascii_lowercase()) {
+        Some((index, ch)) => Err(AocError::parse_at(1, index + 1, format!("invalid letter '{ch}'"))),
+        None if password.len() < MIN_LENGTH => {
+            Err(AocError::parse(1, format!("a password of {} letters cannot be valid, at least {MIN_LENGTH} are needed", password.len())))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the next valid password after the provided one.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex11::next_password;
+/// assert_eq!(next_password("abcdefgh").unwrap(), "abcdffaa");
+/// assert_eq!(next_password("ghijklmn").unwrap(), "ghjaabcc");
+/// assert!(next_password("Hunter2").is_err());
+/// assert!(next_password("abcd").is_err());
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the password contains anything but
+/// lowercase letters, or too few of them for any password to be valid.
+pub fn next_password(password: &str) -> Result<String, AocError> {
+    check_password(password)?;
+    let mut password = password.as_bytes().to_vec();
+    loop {
+        increment(&mut password);
+        let candidate = std::str::from_utf8(&password).unwrap();
+        if is_valid(candidate) { return Ok(candidate.to_string()); }
+    }
+}
+
+// 11th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/11
+//
+// This is a solution to the first part of the puzzle.
+// The password is incremented until it meets the requirements.
+pub fn a(input: &str) -> Result<String, AocError> {
+    next_password(input)
+}
+
+// This is a solution to the second part of the puzzle.
+// The password expires again, so the next valid one after that is found.
+pub fn b(input: &str) -> Result<String, AocError> {
+    next_password(&next_password(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn short_passwords_are_rejected() {
+        for password in ["", "abcd"] {
+            assert!(matches!(Solver.check_input(password), Some(Err(AocError::Parse { .. }))), "{password:?}");
+            assert!(matches!(a(password), Err(AocError::Parse { .. })), "{password:?}");
+        }
+        assert_eq!(a("zzzzz").unwrap(), "aabcc");
+    }
+}
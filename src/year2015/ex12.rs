@@ -0,0 +1,500 @@
+//! [Day 12: JSAbacusFramework.io](https://adventofcode.com/2015/day/12).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex12::{a, b};
+//! assert_eq!(a(r#"{"a":{"b":4},"c":-1}"#).unwrap(), 3);
+//! assert_eq!(a(r#"[-1,{"a":1}]"#).unwrap(), 0);
+//! assert_eq!(b(r#"[1,{"c":"red","b":2},3]"#).unwrap(), 4);
+//! assert_eq!(b(r#"{"d":"red","e":[1,2,3,4],"f":5}"#).unwrap(), 0);
+//! assert_eq!(b(r#"[1,"red",5]"#).unwrap(), 6);
+//! ```
+
+use std::fmt;
+use crate::answer::Answer;
+use crate::error::AocError;
+use serde_json::Value;
+use crate::solution::{Example, Implementation, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 12 }
+    fn title(&self) -> &str { "JSAbacusFramework.io" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+}
+
+/// The alternate implementations of both parts: the [`Json`] tree of the
+/// solution, a [`serde_json::Value`] tree, and a [`scan_sum`] building no
+/// tree at all.
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "dom", part: Part::A, solve: |input| a(input).map(Answer::from) },
+    Implementation { name: "serde", part: Part::A, solve: |input| a_serde(input).map(Answer::from) },
+    Implementation { name: "scanner", part: Part::A, solve: |input| a_scanner(input).map(Answer::from) },
+    Implementation { name: "dom", part: Part::B, solve: |input| b(input).map(Answer::from) },
+    Implementation { name: "serde", part: Part::B, solve: |input| b_serde(input).map(Answer::from) },
+    Implementation { name: "scanner", part: Part::B, solve: |input| b_scanner(input).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: r#"[1,2,3]"#, answer: "6" },
+    Example { part: Part::A, input: r#"{"a":2,"b":4}"#, answer: "6" },
+    Example { part: Part::A, input: r#"[[[3]]]"#, answer: "3" },
+    Example { part: Part::A, input: r#"{"a":{"b":4},"c":-1}"#, answer: "3" },
+    Example { part: Part::A, input: r#"{"a":[-1,1]}"#, answer: "0" },
+    Example { part: Part::A, input: r#"[-1,{"a":1}]"#, answer: "0" },
+    Example { part: Part::A, input: r#"[]"#, answer: "0" },
+    Example { part: Part::A, input: r#"{}"#, answer: "0" },
+    Example { part: Part::B, input: r#"[1,2,3]"#, answer: "6" },
+    Example { part: Part::B, input: r#"[1,{"c":"red","b":2},3]"#, answer: "4" },
+    Example { part: Part::B, input: r#"{"d":"red","e":[1,2,3,4],"f":5}"#, answer: "0" },
+    Example { part: Part::B, input: r#"[1,"red",5]"#, answer: "6" },
+];
+
+/// A JSON value.
+///
+/// Only integer numbers are supported, which is all the puzzle needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    /// The members of an object, in document order.
+    Object(Vec<(String, Json)>),
+}
+
+/// An error found while parsing a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset at which the error was found.
+    pub position: usize,
+    /// What the parser expected to find at `position`.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at position {}", self.expected, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A recursive-descent parser over the bytes of a JSON document.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, expected: &'static str) -> Result<T, ParseError> {
+        Err(ParseError { position: self.position, expected })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.position).is_some_and(u8::is_ascii_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.position).copied()
+    }
+
+    fn expect(&mut self, byte: u8, expected: &'static str) -> Result<(), ParseError> {
+        if self.peek() != Some(byte) { return self.error(expected); }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &'static str, value: Json) -> Result<Json, ParseError> {
+        if !self.bytes[self.position..].starts_with(literal.as_bytes()) { return self.error(literal); }
+        self.position += literal.len();
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Json::String(self.string()?)),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            _ => self.error("a value"),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ParseError> {
+        self.expect(b'{', "'{'")?;
+        let mut members = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            if self.peek() != Some(b'"') { return self.error("a member name"); }
+            let name = self.string()?;
+            self.expect(b':', "':'")?;
+            members.push((name, self.value()?));
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => { self.position += 1; return Ok(Json::Object(members)); }
+                _ => return self.error("',' or '}'"),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ParseError> {
+        self.expect(b'[', "'['")?;
+        let mut elements = Vec::new();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Json::Array(elements));
+        }
+        loop {
+            elements.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => { self.position += 1; return Ok(Json::Array(elements)); }
+                _ => return self.error("',' or ']'"),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect(b'"', "'\"'")?;
+        let mut string = String::new();
+        loop {
+            let start = self.position;
+            while self.bytes.get(self.position).is_some_and(|&byte| byte != b'"' && byte != b'\\') {
+                self.position += 1;
+            }
+            string.push_str(std::str::from_utf8(&self.bytes[start..self.position]).unwrap());
+            match self.bytes.get(self.position) {
+                Some(b'"') => { self.position += 1; return Ok(string); }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = match self.bytes.get(self.position) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let code = self.bytes.get(self.position + 1..self.position + 5)
+                                .and_then(|hex| u32::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok())
+                                .and_then(char::from_u32);
+                            match code {
+                                Some(code) => { self.position += 4; code }
+                                None => return self.error("four hex digits"),
+                            }
+                        }
+                        _ => return self.error("an escape sequence"),
+                    };
+                    self.position += 1;
+                    string.push(escaped);
+                }
+                _ => return self.error("'\"'"),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, ParseError> {
+        let start = self.position;
+        if self.bytes[self.position] == b'-' { self.position += 1; }
+        while self.bytes.get(self.position).is_some_and(u8::is_ascii_digit) {
+            self.position += 1;
+        }
+        match std::str::from_utf8(&self.bytes[start..self.position]).unwrap().parse() {
+            Ok(number) => Ok(Json::Number(number)),
+            Err(_) => { self.position = start; self.error("an integer") }
+        }
+    }
+}
+
+impl Json {
+    /// Parses a JSON document.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex12::Json;
+    /// let json = Json::parse(r#"{"a":[1,-2],"b":"red"}"#).unwrap();
+    /// assert_eq!(json, Json::Object(vec![
+    ///     ("a".to_string(), Json::Array(vec![Json::Number(1), Json::Number(-2)])),
+    ///     ("b".to_string(), Json::String("red".to_string())),
+    /// ]));
+    /// assert!(Json::parse("[1,").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] if the document is malformed.
+    pub fn parse(input: &str) -> Result<Json, ParseError> {
+        let mut parser = Parser { bytes: input.as_bytes(), position: 0 };
+        let value = parser.value()?;
+        if parser.peek().is_some() { return parser.error("the end of the document"); }
+        Ok(value)
+    }
+
+    /// Returns the sum of every number in this value.
+    ///
+    /// # Arguments
+    /// * `ignore_red` - Whether to ignore objects with any `"red"` member value,
+    ///   along with everything they contain.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex12::Json;
+    /// let json = Json::parse(r#"[1,{"c":"red","b":2},3]"#).unwrap();
+    /// assert_eq!(json.sum(false), 6);
+    /// assert_eq!(json.sum(true), 4);
+    /// ```
+    pub fn sum(&self, ignore_red: bool) -> i64 {
+        match self {
+            Json::Number(number) => *number,
+            Json::Array(elements) => elements.iter().map(|element| element.sum(ignore_red)).sum(),
+            Json::Object(members) => {
+                let red = Json::String("red".to_string());
+                if ignore_red && members.iter().any(|(_, value)| *value == red) { return 0; }
+                members.iter().map(|(_, value)| value.sum(ignore_red)).sum()
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// The running sum of an object or an array being scanned by [`scan_sum`].
+struct Frame {
+    /// Whether this is an object rather than an array.
+    object: bool,
+    /// The sum of the numbers found in it so far.
+    sum: i64,
+    /// Whether it is an object with a `"red"` member value.
+    red: bool,
+    /// Whether the next string is a member name rather than a value.
+    name_next: bool,
+}
+
+/// Returns the sum of every number of a JSON document in a single pass over
+/// its bytes, without building any [`Json`] value.
+///
+/// Every object and array being scanned keeps the sum of what it contains
+/// so far, which is added to its parent once it closes, unless it turned
+/// out to be an object to ignore. Strings are compared to `"red"` as they
+/// appear in the document, escape sequences included.
+///
+/// # Arguments
+/// * `ignore_red` - Whether to ignore objects with any `"red"` member value,
+///   along with everything they contain.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex12::scan_sum;
+/// assert_eq!(scan_sum(r#"[1,{"c":"red","b":2},3]"#, false), Ok(6));
+/// assert_eq!(scan_sum(r#"[1,{"c":"red","b":2},3]"#, true), Ok(4));
+/// assert_eq!(scan_sum(r#"{"red":[1,"red"],"b":2}"#, true), Ok(3));
+/// assert!(scan_sum("[1,", false).is_err());
+/// ```
+///
+/// # Errors
+/// Returns a [`ParseError`] on unbalanced brackets, unterminated strings,
+/// numbers that are not integers and unexpected bytes. Unlike
+/// [`Json::parse`], the scanner does not check where commas and colons go.
+pub fn scan_sum(input: &str, ignore_red: bool) -> Result<i64, ParseError> {
+    let bytes = input.as_bytes();
+    let error = |position, expected| Err(ParseError { position, expected });
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut total = 0;
+    let mut position = 0;
+    while let Some(&byte) = bytes.get(position) {
+        let mut add = |frames: &mut Vec<Frame>, sum| match frames.last_mut() {
+            Some(frame) => frame.sum += sum,
+            None => total += sum,
+        };
+        match byte {
+            b'{' | b'[' => {
+                frames.push(Frame { object: byte == b'{', sum: 0, red: false, name_next: true });
+                position += 1;
+            }
+            b'}' | b']' => {
+                let Some(frame) = frames.pop().filter(|frame| frame.object == (byte == b'}')) else {
+                    return error(position, "a matching bracket");
+                };
+                add(&mut frames, if ignore_red && frame.red { 0 } else { frame.sum });
+                position += 1;
+            }
+            b'"' => {
+                let start = position + 1;
+                let mut end = start;
+                while let Some(&byte) = bytes.get(end).filter(|&&byte| byte != b'"') {
+                    end += if byte == b'\\' { 2 } else { 1 };
+                }
+                if end >= bytes.len() {
+                    return error(bytes.len(), "'\"'");
+                }
+                if let Some(frame) = frames.last_mut().filter(|frame| frame.object) {
+                    frame.red |= !frame.name_next && &bytes[start..end] == b"red";
+                    frame.name_next = false;
+                }
+                position = end + 1;
+            }
+            b'-' | b'0'..=b'9' => {
+                let start = position;
+                position += 1;
+                while bytes.get(position).is_some_and(u8::is_ascii_digit) {
+                    position += 1;
+                }
+                let Ok(number) = input[start..position].parse() else { return error(start, "an integer") };
+                add(&mut frames, number);
+            }
+            b',' => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.name_next = true;
+                }
+                position += 1;
+            }
+            b':' => position += 1,
+            b't' | b'f' | b'n' => {
+                let Some(literal) = ["true", "false", "null"].into_iter().find(|literal| input[position..].starts_with(literal)) else {
+                    return error(position, "a value");
+                };
+                position += literal.len();
+            }
+            _ if byte.is_ascii_whitespace() => position += 1,
+            _ => return error(position, "a value"),
+        }
+    }
+    match frames.is_empty() {
+        true => Ok(total),
+        false => error(bytes.len(), "a closing bracket"),
+    }
+}
+
+/// Returns the sum of every number in a [`serde_json::Value`].
+///
+/// # Arguments
+/// * `ignore_red` - Whether to ignore objects with any `"red"` member value,
+///   along with everything they contain.
+fn serde_sum(value: &Value, ignore_red: bool) -> i64 {
+    match value {
+        // Other numbers than integers do not appear in the puzzle.
+        Value::Number(number) => number.as_i64().unwrap_or(0),
+        Value::Array(elements) => elements.iter().map(|element| serde_sum(element, ignore_red)).sum(),
+        Value::Object(members) => {
+            if ignore_red && members.values().any(|value| value == "red") { return 0; }
+            members.values().map(|value| serde_sum(value, ignore_red)).sum()
+        }
+        _ => 0,
+    }
+}
+
+/// Converts a [`ParseError`] of `input` into an [`AocError::Parse`],
+/// located on the line and column of the offending byte.
+fn located(input: &str, err: ParseError) -> AocError {
+    let before = &input.as_bytes()[..err.position.min(input.len())];
+    let line = before.iter().filter(|&&byte| byte == b'\n').count();
+    let start = before.iter().rposition(|&byte| byte == b'\n').map_or(0, |newline| newline + 1);
+    let column = String::from_utf8_lossy(&before[start..]).chars().count() + 1;
+    AocError::parse_at(line + 1, column, err)
+}
+
+/// Parses the puzzle input.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`], located on the line of the offending
+/// byte, if the input is not a valid JSON document.
+fn parse(input: &str) -> Result<Json, AocError> {
+    Json::parse(input).map_err(|err| located(input, err))
+}
+
+/// Parses the puzzle input with serde_json.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input is not a valid JSON document.
+fn parse_serde(input: &str) -> Result<Value, AocError> {
+    serde_json::from_str(input).map_err(|err| AocError::parse(err.line(), err))
+}
+
+// 12th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/12
+//
+// This is a solution to the first part of the puzzle.
+// The document is parsed and every number in it is added up.
+pub fn a(input: &str) -> Result<i64, AocError> {
+    Ok(parse(input)?.sum(false))
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, but skipping objects with a "red" value.
+pub fn b(input: &str) -> Result<i64, AocError> {
+    Ok(parse(input)?.sum(true))
+}
+
+// Same as the first part, parsing the document with serde_json.
+pub fn a_serde(input: &str) -> Result<i64, AocError> {
+    Ok(serde_sum(&parse_serde(input)?, false))
+}
+
+// Same as the second part, parsing the document with serde_json.
+pub fn b_serde(input: &str) -> Result<i64, AocError> {
+    Ok(serde_sum(&parse_serde(input)?, true))
+}
+
+// Same as the first part, scanning the document without parsing it.
+pub fn a_scanner(input: &str) -> Result<i64, AocError> {
+    scan_sum(input, false).map_err(|err| located(input, err))
+}
+
+// Same as the second part, scanning the document without parsing it.
+pub fn b_scanner(input: &str) -> Result<i64, AocError> {
+    scan_sum(input, true).map_err(|err| located(input, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn implementations_agree_on_generated_documents() {
+        for seed in 0..20 {
+            let input = crate::gen::generate(12, 200, seed).unwrap();
+            for part in [Part::A, Part::B] {
+                let expected = Solver.solve(part, &input).unwrap();
+                for implementation in IMPLEMENTATIONS.iter().filter(|implementation| implementation.part == part) {
+                    assert_eq!((implementation.solve)(&input).unwrap(), expected, "{} on seed {seed}", implementation.name);
+                }
+            }
+        }
+    }
+}
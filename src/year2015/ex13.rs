@@ -0,0 +1,320 @@
+//! [Day 13: Knights of the Dinner Table](https://adventofcode.com/2015/day/13).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex13::a;
+//! let input = "Alice would gain 54 happiness units by sitting next to Bob.
+//! Alice would lose 79 happiness units by sitting next to Carol.
+//! Alice would lose 2 happiness units by sitting next to David.
+//! Bob would gain 83 happiness units by sitting next to Alice.
+//! Bob would lose 7 happiness units by sitting next to Carol.
+//! Bob would lose 63 happiness units by sitting next to David.
+//! Carol would lose 62 happiness units by sitting next to Alice.
+//! Carol would gain 60 happiness units by sitting next to Bob.
+//! Carol would gain 55 happiness units by sitting next to David.
+//! David would gain 46 happiness units by sitting next to Alice.
+//! David would lose 7 happiness units by sitting next to Bob.
+//! David would gain 41 happiness units by sitting next to Carol.";
+//! assert_eq!(a(input).unwrap(), 330);
+//! ```
+
+use std::io::Write;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, AocError};
+use crate::solution::{Example, Implementation, Part, Solution};
+use crate::util::intern::Interner;
+use crate::util::tsp::{best_cycle_route, Algorithm};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 13 }
+    fn title(&self) -> &str { "Knights of the Dinner Table" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(Table::parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn implementations(&self) -> &'static [Implementation] { IMPLEMENTATIONS }
+
+    fn solve_explained(&self, part: Part, input: &str, _context: &Context, out: &mut dyn Write) -> Result<Answer, AocError> {
+        explain(part, input, out).map(Answer::from)
+    }
+}
+
+/// The alternate implementations of both parts, one per [`Algorithm`].
+const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation { name: "brute-force", part: Part::A, solve: |input| a_with(input, Algorithm::BruteForce).map(Answer::from) },
+    Implementation { name: "held-karp", part: Part::A, solve: |input| a_with(input, Algorithm::HeldKarp).map(Answer::from) },
+    Implementation { name: "brute-force", part: Part::B, solve: |input| b_with(input, Algorithm::BruteForce).map(Answer::from) },
+    Implementation { name: "held-karp", part: Part::B, solve: |input| b_with(input, Algorithm::HeldKarp).map(Answer::from) },
+];
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "Alice would gain 54 happiness units by sitting next to Bob.\nAlice would lose 79 happiness units by sitting next to Carol.\nAlice would lose 2 happiness units by sitting next to David.\nBob would gain 83 happiness units by sitting next to Alice.\nBob would lose 7 happiness units by sitting next to Carol.\nBob would lose 63 happiness units by sitting next to David.\nCarol would lose 62 happiness units by sitting next to Alice.\nCarol would gain 60 happiness units by sitting next to Bob.\nCarol would gain 55 happiness units by sitting next to David.\nDavid would gain 46 happiness units by sitting next to Alice.\nDavid would lose 7 happiness units by sitting next to Bob.\nDavid would gain 41 happiness units by sitting next to Carol.", answer: "330" },
+];
+
+/// The guests of a dinner table and how happy each one is next to the others.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex13::Table;
+/// let table = Table::parse("Alice would gain 54 happiness units by sitting next to Bob.\n\
+///                           Bob would lose 7 happiness units by sitting next to Alice.").unwrap();
+/// assert_eq!(table.guests(), ["Alice", "Bob"]);
+/// assert_eq!(table.happiness(1, 0), -7);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    /// The ID of each guest, which indexes the happiness.
+    guests: Interner,
+    /// The happiness each guest gains sitting next to each other guest.
+    happiness: Vec<Vec<i32>>,
+}
+
+impl Table {
+    /// Parses a [`Table`] from lines such as
+    /// `Alice would gain 54 happiness units by sitting next to Bob.`
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let mut table = Table::default();
+        parse_lines(input, |line| {
+            let (guest, neighbor, amount) = parse_line(line)?;
+            let guest = table.add_guest(guest)?;
+            let neighbor = table.add_guest(neighbor)?;
+            table.happiness[guest][neighbor] = amount;
+            Ok::<_, String>(())
+        })?;
+        Ok(table)
+    }
+
+    /// Adds a guest indifferent to everyone, returning its index.
+    ///
+    /// If the guest is already seated, their index is returned instead.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if there are too many guests.
+    pub fn add_guest(&mut self, name: &str) -> Result<usize, String> {
+        let index = usize::from(self.guests.intern(name)?);
+        if index == self.happiness.len() {
+            for row in &mut self.happiness {
+                row.push(0);
+            }
+            self.happiness.push(vec![0; self.guests.len()]);
+        }
+        Ok(index)
+    }
+
+    /// Returns the names of the guests, indexed by guest.
+    pub fn guests(&self) -> &[String] {
+        self.guests.names()
+    }
+
+    /// Returns the happiness `guest` gains sitting next to `neighbor`.
+    pub fn happiness(&self, guest: usize, neighbor: usize) -> i32 {
+        self.happiness[guest][neighbor]
+    }
+
+    /// Returns the total change in happiness of the best seating arrangement
+    /// around the circular table.
+    ///
+    /// Seating two guests together changes happiness by what both of them
+    /// gain, so the best arrangement is the heaviest cycle through every
+    /// guest, weighing each pair by their combined happiness.
+    ///
+    /// # Arguments
+    /// * `algorithm` - The [`Algorithm`] searching for the arrangement.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are too many guests to
+    /// search.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex13::Table;
+    /// use aoc2015::util::tsp::Algorithm;
+    /// let table = Table::parse("Alice would gain 10 happiness units by sitting next to Bob.\n\
+    ///                           Bob would lose 3 happiness units by sitting next to Alice.\n\
+    ///                           Alice would lose 5 happiness units by sitting next to Carol.\n\
+    ///                           Carol would gain 4 happiness units by sitting next to Alice.\n\
+    ///                           Bob would gain 2 happiness units by sitting next to Carol.\n\
+    ///                           Carol would gain 6 happiness units by sitting next to Bob.").unwrap();
+    /// assert_eq!(table.best_seating(Algorithm::HeldKarp).unwrap(), 14);
+    /// assert_eq!(table.best_seating(Algorithm::BruteForce).unwrap(), 14);
+    /// ```
+    pub fn best_seating(&self, algorithm: Algorithm) -> Result<i32, AocError> {
+        Ok(algorithm.best_cycle(&self.pair_weights(), i32::max)?.unwrap_or(0))
+    }
+
+    /// Returns the best seating arrangement around the circular table, as
+    /// found by [`Table::best_seating`], with the guests in order around
+    /// the table.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if there are too many guests to
+    /// search.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex13::Table;
+    /// let mut table = Table::parse("Alice would gain 10 happiness units by sitting next to Bob.").unwrap();
+    /// table.add_guest("Carol").unwrap();
+    /// let (happiness, guests) = table.best_arrangement().unwrap();
+    /// assert_eq!((happiness, guests[0]), (10, "Alice"));
+    /// assert_eq!(guests.len(), 3);
+    /// ```
+    pub fn best_arrangement(&self) -> Result<(i32, Vec<&str>), AocError> {
+        let (happiness, seats) = best_cycle_route(&self.pair_weights(), i32::max)?.unwrap_or_default();
+        Ok((happiness, seats.into_iter().map(|guest| self.guests()[guest].as_str()).collect()))
+    }
+
+    /// Returns the combined happiness of every pair of guests seated next
+    /// to each other, as the weights of the edges between them.
+    fn pair_weights(&self) -> Vec<Vec<Option<i32>>> {
+        let n = self.guests.len();
+        (0..n)
+            .map(|guest| (0..n).map(|neighbor| Some(self.happiness[guest][neighbor] + self.happiness[neighbor][guest])).collect())
+            .collect()
+    }
+}
+
+/// Parses a line such as
+/// `Alice would gain 54 happiness units by sitting next to Bob.` into the
+/// guest, their neighbor and the happiness the guest gains.
+fn parse_line(line: &str) -> Result<(&str, &str, i32), String> {
+    let words: Vec<_> = line.trim_end_matches('.').split(' ').collect();
+    let [guest, "would", sign, amount, "happiness", "units", "by", "sitting", "next", "to", neighbor] = words[..] else {
+        return Err(format!("invalid line '{line}'"));
+    };
+    let amount: i32 = amount.parse().map_err(|_| format!("invalid happiness amount '{amount}'"))?;
+    match sign {
+        "gain" => Ok((guest, neighbor, amount)),
+        "lose" => Ok((guest, neighbor, -amount)),
+        _ => Err(format!("expected 'gain' or 'lose', found '{sign}'")),
+    }
+}
+
+// 13th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/13
+//
+// This is a solution to the first part of the puzzle.
+// The best seating is the heaviest cycle through every guest.
+pub fn a(input: &str) -> Result<i32, AocError> {
+    a_with(input, Algorithm::HeldKarp)
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, after adding ourselves as an apathetic guest.
+pub fn b(input: &str) -> Result<i32, AocError> {
+    b_with(input, Algorithm::HeldKarp)
+}
+
+// Same as the first part, with a choice of algorithm.
+pub fn a_with(input: &str, algorithm: Algorithm) -> Result<i32, AocError> {
+    Table::parse(input)?.best_seating(algorithm)
+}
+
+// Same as the second part, with a choice of algorithm.
+pub fn b_with(input: &str, algorithm: Algorithm) -> Result<i32, AocError> {
+    table_with_you(input)?.best_seating(algorithm)
+}
+
+/// Parses the table of the second part, where we are seated too.
+fn table_with_you(input: &str) -> Result<Table, AocError> {
+    let mut table = Table::parse(input)?;
+    table.add_guest("You").map_err(AocError::unsolvable)?;
+    Ok(table)
+}
+
+/// Solves a part, writing the best arrangement of the guests and the
+/// happiness of each pair of neighbors to `out`.
+fn explain(part: Part, input: &str, out: &mut dyn Write) -> Result<i32, AocError> {
+    let table = match part {
+        Part::A => Table::parse(input)?,
+        Part::B => table_with_you(input)?,
+    };
+    let (happiness, guests) = table.best_arrangement()?;
+    if !guests.is_empty() {
+        writeln!(out, "{} around the table = {happiness}", guests.join(", "))?;
+    }
+    let index = |name: &str| table.guests().iter().position(|guest| guest == name).unwrap_or_else(|| unreachable!("{name} is seated"));
+    // Two guests are neighbors once, not on both sides.
+    let pairs = match guests.len() {
+        0 | 1 => 0,
+        2 => 1,
+        n => n,
+    };
+    for seat in 0..pairs {
+        let (guest, neighbor) = (guests[seat], guests[(seat + 1) % guests.len()]);
+        let (from, to) = (index(guest), index(neighbor));
+        let (gain, back) = (table.happiness(from, to), table.happiness(to, from));
+        writeln!(out, "{guest} next to {neighbor}: {gain} + {back} = {}", gain + back)?;
+    }
+    Ok(happiness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn implementations() {
+        for implementation in IMPLEMENTATIONS {
+            for example in EXAMPLES.iter().filter(|example| example.part == implementation.part) {
+                example.check_implementation(implementation).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn explanation_shows_the_seating() {
+        let mut out = Vec::new();
+        let answer = Solver.solve_explained(Part::A, EXAMPLES[0].input, &Context::default(), &mut out).unwrap();
+        assert_eq!(answer.to_string(), "330");
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "Alice, David, Carol, Bob around the table = 330");
+        assert_eq!(lines[1..], ["Alice next to David: -2 + 46 = 44", "David next to Carol: 41 + 55 = 96", "Carol next to Bob: 60 + -7 = 53", "Bob next to Alice: 83 + 54 = 137"]);
+    }
+
+    #[test]
+    fn arrangements_are_feasible() {
+        for table in [Table::parse(EXAMPLES[0].input).unwrap(), table_with_you(EXAMPLES[0].input).unwrap()] {
+            let (happiness, guests) = table.best_arrangement().unwrap();
+            assert_eq!(happiness, table.best_seating(Algorithm::BruteForce).unwrap());
+            let index = |name: &str| table.guests().iter().position(|guest| guest == name).unwrap();
+            let mut seats: Vec<usize> = guests.iter().map(|&guest| index(guest)).collect();
+            let total: i32 = (0..seats.len())
+                .map(|seat| (seats[seat], seats[(seat + 1) % seats.len()]))
+                .map(|(guest, neighbor)| table.happiness(guest, neighbor) + table.happiness(neighbor, guest))
+                .sum();
+            assert_eq!(total, happiness);
+            seats.sort_unstable();
+            assert_eq!(seats, (0..table.guests().len()).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn too_many_guests_are_rejected() {
+        let guests: Vec<String> = (0..28).map(|guest| format!("G{guest}")).collect();
+        let input: Vec<String> = guests.iter()
+            .flat_map(|guest| guests.iter().filter(move |neighbor| *neighbor != guest).map(move |neighbor| format!("{guest} would gain 1 happiness units by sitting next to {neighbor}.")))
+            .collect();
+        let input = input.join("\n");
+        assert!(matches!(a(&input), Err(AocError::Unsolvable(_))));
+        assert!(matches!(Solver.solve_explained(Part::A, &input, &Context::default(), &mut Vec::new()), Err(AocError::Unsolvable(_))));
+    }
+}
@@ -0,0 +1,208 @@
+//! [Day 14: Reindeer Olympics](https://adventofcode.com/2015/day/14).
+//!
+//! # Examples
+//! The examples of the puzzle text, whose race lasts 1000 seconds:
+//! ```
+//! use aoc2015::year2015::ex14::{parse, race_points, winning_distance};
+//! let reindeer = parse("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+//! Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.").unwrap();
+//! assert_eq!(winning_distance(&reindeer, 1000), 1120);
+//! assert_eq!(race_points(&reindeer, 1000), [312, 689]);
+//! ```
+
+use std::str::FromStr;
+use crate::answer::Answer;
+use crate::config::PuzzleConfig;
+use crate::context::Context;
+use crate::error::{parse_lines, AocError};
+use crate::parse_line;
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 14 }
+    fn title(&self) -> &str { "Reindeer Olympics" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn example_config(&self, _part: Part) -> PuzzleConfig { PuzzleConfig { race_duration: EXAMPLE_RACE_DURATION, ..PuzzleConfig::PUZZLE } }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let (reindeer, seconds) = (parse(input)?, context.config.race_duration);
+        match part {
+            Part::A => Ok(winning_distance(&reindeer, seconds).into()),
+            Part::B => Ok(race_points(&reindeer, seconds).into_iter().max().unwrap_or(0).into()),
+        }
+    }
+}
+
+/// The duration of the race in the puzzle, in seconds.
+pub const RACE_DURATION: u32 = 2503;
+
+/// The duration of the race of the examples of the puzzle text, in seconds.
+const EXAMPLE_RACE_DURATION: u32 = 1000;
+
+/// The examples of the puzzle text, raced for [`EXAMPLE_RACE_DURATION`]
+/// seconds.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\nDancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.", answer: "1120" },
+    Example { part: Part::B, input: "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\nDancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.", answer: "689" },
+];
+
+/// A reindeer alternating between flying and resting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reindeer {
+    /// The name of this reindeer.
+    pub name: String,
+    /// The speed of this reindeer while flying, in km/s.
+    pub speed: u32,
+    /// How long this reindeer can fly before resting, in seconds.
+    pub fly_time: u32,
+    /// How long this reindeer must rest before flying again, in seconds.
+    pub rest_time: u32,
+}
+
+impl FromStr for Reindeer {
+    type Err = String;
+
+    /// Parses a line such as
+    /// `Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.`,
+    /// whose fly/rest cycle must last at least a second.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let reindeer = parse_line!("{name} can fly {speed} km/s for {fly_time} seconds, but then must rest for {rest_time} seconds.", line
+            => Reindeer { name, speed, fly_time, rest_time })
+            .map_err(|err| err.to_string())?;
+        match reindeer.fly_time.checked_add(reindeer.rest_time) {
+            Some(0) => Err(format!("{} neither flies nor rests", reindeer.name)),
+            None => Err(format!("the fly/rest cycle of {} is too long", reindeer.name)),
+            Some(_) => Ok(reindeer),
+        }
+    }
+}
+
+impl Reindeer {
+    /// Returns the distance this reindeer has flown after `seconds` seconds.
+    ///
+    /// The distance is computed directly from the number of complete
+    /// fly/rest cycles and the flying time of the last partial cycle.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex14::Reindeer;
+    /// let comet: Reindeer = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.".parse().unwrap();
+    /// assert_eq!(comet.distance_after(1000), 1120);
+    /// ```
+    pub fn distance_after(&self, seconds: u32) -> u32 {
+        let cycle = self.fly_time + self.rest_time;
+        let flying = seconds / cycle * self.fly_time + (seconds % cycle).min(self.fly_time);
+        flying * self.speed
+    }
+
+    /// Returns whether this reindeer is flying during the given second,
+    /// counting from 0.
+    fn is_flying(&self, second: u32) -> bool {
+        second % (self.fly_time + self.rest_time) < self.fly_time
+    }
+}
+
+/// Parses the reindeer of the puzzle input.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input is malformed.
+pub fn parse(input: &str) -> Result<Vec<Reindeer>, AocError> {
+    parse_lines(input, str::parse)
+}
+
+/// Returns the distance flown by the leading reindeer after `seconds` seconds.
+pub fn winning_distance(reindeer: &[Reindeer], seconds: u32) -> u32 {
+    reindeer.iter().map(|reindeer| reindeer.distance_after(seconds)).max().unwrap_or(0)
+}
+
+/// Simulates a race scoring one point per second for every leading reindeer.
+///
+/// # Arguments
+/// * `reindeer` - The reindeer racing.
+/// * `seconds` - The duration of the race.
+///
+/// # Returns
+/// The points of each reindeer at the end of the race.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex14::{parse, race_points};
+/// let reindeer = parse("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\n\
+///                       Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.").unwrap();
+/// assert_eq!(race_points(&reindeer, 1000), [312, 689]);
+/// ```
+pub fn race_points(reindeer: &[Reindeer], seconds: u32) -> Vec<u32> {
+    let mut distances = vec![0; reindeer.len()];
+    let mut points = vec![0; reindeer.len()];
+    for second in 0..seconds {
+        for (distance, reindeer) in distances.iter_mut().zip(reindeer) {
+            if reindeer.is_flying(second) { *distance += reindeer.speed; }
+        }
+        let lead = distances.iter().copied().max().unwrap_or(0);
+        for (points, &distance) in points.iter_mut().zip(&distances) {
+            if distance == lead { *points += 1; }
+        }
+    }
+    points
+}
+
+// 14th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/14
+//
+// This is a solution to the first part of the puzzle.
+// The distance of each reindeer at the end of the race is computed directly.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    Ok(winning_distance(&parse(input)?, RACE_DURATION))
+}
+
+// This is a solution to the second part of the puzzle.
+// The race is simulated second by second to award points to the leaders.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    Ok(race_points(&parse(input)?, RACE_DURATION).into_iter().max().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REINDEER: &str = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\n\
+                            Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn race_after_1000_seconds() {
+        let reindeer = parse(REINDEER).unwrap();
+        assert_eq!(winning_distance(&reindeer, 1000), 1120);
+        assert_eq!(race_points(&reindeer, 1000), [312, 689]);
+    }
+
+    #[test]
+    fn empty_cycles_are_rejected() {
+        let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\n\
+                     Vixen can fly 8 km/s for 0 seconds, but then must rest for 0 seconds.";
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: Vixen neither flies nor rests");
+        assert!(matches!(Solver.check_input(input), Some(Err(AocError::Parse { line: 2, .. }))));
+        assert!("Vixen can fly 8 km/s for 0 seconds, but then must rest for 4294967295 seconds.".parse::<Reindeer>().is_ok());
+        assert!("Vixen can fly 8 km/s for 1 seconds, but then must rest for 4294967295 seconds.".parse::<Reindeer>().is_err());
+    }
+
+    #[test]
+    fn names_may_have_several_words() {
+        let reindeer: Reindeer = "Santa Claus can fly 3 km/s for 5 seconds, but then must rest for 7 seconds.".parse().unwrap();
+        assert_eq!((reindeer.name.as_str(), reindeer.speed, reindeer.fly_time, reindeer.rest_time), ("Santa Claus", 3, 5, 7));
+    }
+}
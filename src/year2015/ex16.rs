@@ -0,0 +1,216 @@
+//! [Day 16: Aunt Sue](https://adventofcode.com/2015/day/16).
+//!
+//! # Examples
+//! Matching Sues against the readout of the MFCSAM:
+//! ```
+//! use aoc2015::year2015::ex16::{a, b};
+//! let input = "Sue 1: trees: 9, goldfish: 2, akitas: 0\nSue 2: children: 3, cars: 2, perfumes: 1";
+//! assert_eq!(a(input).unwrap(), 2);
+//! assert_eq!(b(input).unwrap(), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 16 }
+    fn title(&self) -> &str { "Aunt Sue" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse_lines(input, Sue::parse).map(drop)) }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+}
+
+/// The compounds detected by the MFCSAM in the gift.
+pub const READOUT: [(&str, u32); 10] = [
+    ("children", 3),
+    ("cats", 7),
+    ("samoyeds", 2),
+    ("pomeranians", 3),
+    ("akitas", 0),
+    ("vizslas", 0),
+    ("goldfish", 5),
+    ("trees", 3),
+    ("cars", 2),
+    ("perfumes", 1),
+];
+
+/// The comparison rules of the outdated retroencabulator of the second part.
+pub const RANGES: [(&str, Comparison); 4] = [
+    ("cats", Comparison::Greater),
+    ("trees", Comparison::Greater),
+    ("pomeranians", Comparison::Fewer),
+    ("goldfish", Comparison::Fewer),
+];
+
+/// How a remembered attribute of a [`Sue`] is compared against a readout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// The attribute matches the exact readout.
+    Exact,
+    /// The attribute matches readouts lower than itself.
+    Greater,
+    /// The attribute matches readouts greater than itself.
+    Fewer,
+}
+
+impl Comparison {
+    /// Returns whether a remembered `known` value matches a `readout` value.
+    pub fn matches(self, known: u32, readout: u32) -> bool {
+        match self {
+            Comparison::Exact => known == readout,
+            Comparison::Greater => known > readout,
+            Comparison::Fewer => known < readout,
+        }
+    }
+}
+
+/// One of the aunts named Sue, along with what is remembered of her.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sue {
+    /// The number of this Sue.
+    pub number: u32,
+    /// The known attributes of this Sue.
+    pub attributes: HashMap<String, u32>,
+}
+
+impl Sue {
+    /// Parses a line such as `Sue 1: cars: 9, akitas: 3, goldfish: 0`.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the line is malformed.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let (name, attributes) = line.split_once(": ").ok_or("missing attributes")?;
+        let number = name.strip_prefix("Sue ")
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("invalid Sue '{name}'"))?;
+        let attributes = attributes.split(", ")
+            .map(|attribute| {
+                let (name, value) = attribute.split_once(": ").ok_or_else(|| format!("invalid attribute '{attribute}'"))?;
+                let value = value.parse().map_err(|_| format!("invalid value '{value}' for {name}"))?;
+                Ok((name.to_string(), value))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Sue { number, attributes })
+    }
+
+    /// Returns whether every known attribute of this Sue matches the readout.
+    ///
+    /// # Arguments
+    /// * `readout` - The value detected for each compound.
+    /// * `rules` - The [`Comparison`] for each compound, [`Comparison::Exact`]
+    ///   being used for compounds without a rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex16::{Comparison, Sue};
+    /// let sue = Sue::parse("Sue 7: cats: 9, cars: 2").unwrap();
+    /// assert!(!sue.matches(&[("cats", 7), ("cars", 2)], &[]));
+    /// assert!(sue.matches(&[("cats", 7), ("cars", 2)], &[("cats", Comparison::Greater)]));
+    /// ```
+    pub fn matches(&self, readout: &[(&str, u32)], rules: &[(&str, Comparison)]) -> bool {
+        readout.iter().all(|&(compound, value)| {
+            let Some(&known) = self.attributes.get(compound) else { return true };
+            let comparison = rules.iter()
+                .find(|(name, _)| *name == compound)
+                .map_or(Comparison::Exact, |&(_, comparison)| comparison);
+            comparison.matches(known, value)
+        })
+    }
+}
+
+/// Returns the number of the first Sue matching the readout under the rules.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex16::find_sue;
+/// let input = "Sue 1: cats: 1, trees: 3\nSue 2: cats: 7, trees: 3";
+/// assert_eq!(find_sue(input, &[("cats", 7), ("trees", 3)], &[]).unwrap(), Some(2));
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input is malformed.
+pub fn find_sue(input: &str, readout: &[(&str, u32)], rules: &[(&str, Comparison)]) -> Result<Option<u32>, AocError> {
+    let sues = parse_lines(input, Sue::parse)?;
+    Ok(sues.into_iter().find(|sue| sue.matches(readout, rules)).map(|sue| sue.number))
+}
+
+/// Returns the number of the first Sue matching the readout under the rules,
+/// reading the Sues one line at a time and stopping at the first match.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex16::find_sue_stream;
+/// let input = "Sue 1: cats: 1, trees: 3\nSue 2: cats: 7, trees: 3";
+/// assert_eq!(find_sue_stream(input.as_bytes(), &[("cats", 7), ("trees", 3)], &[]).unwrap(), Some(2));
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if a line before the match is malformed,
+/// or an [`AocError::Io`] if it cannot be read.
+pub fn find_sue_stream(input: impl BufRead, readout: &[(&str, u32)], rules: &[(&str, Comparison)]) -> Result<Option<u32>, AocError> {
+    for sue in parse_stream(input, Sue::parse) {
+        let sue = sue?;
+        if sue.matches(readout, rules) {
+            return Ok(Some(sue.number));
+        }
+    }
+    Ok(None)
+}
+
+// 16th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/16
+//
+// This is a solution to the first part of the puzzle.
+// The Sue whose known attributes all equal the readout is searched.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    find_sue(input, &READOUT, &[])?.ok_or_else(|| AocError::unsolvable("no Sue matches the readout"))
+}
+
+// This is a solution to the second part of the puzzle.
+// Some compounds of the readout are ranges rather than exact values.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    find_sue(input, &READOUT, &RANGES)?.ok_or_else(|| AocError::unsolvable("no Sue matches the readout"))
+}
+
+// Same as the first part, reading the Sues one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u32, AocError> {
+    find_sue_stream(input, &READOUT, &[])?.ok_or_else(|| AocError::unsolvable("no Sue matches the readout"))
+}
+
+// Same as the second part, reading the Sues one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u32, AocError> {
+    find_sue_stream(input, &READOUT, &RANGES)?.ok_or_else(|| AocError::unsolvable("no Sue matches the readout"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readout_rules() {
+        let input = "Sue 1: cats: 7, trees: 3\nSue 2: cats: 8, trees: 3";
+        assert_eq!(find_sue(input, &[("cats", 7), ("trees", 3)], &[]).unwrap(), Some(1));
+        assert_eq!(find_sue(input, &[("cats", 7), ("trees", 3)], &[("cats", Comparison::Greater)]).unwrap(), Some(2));
+    }
+
+    #[test]
+    fn stream_stops_at_match() {
+        let input = "Sue 1: cats: 7, trees: 3\nnot a Sue";
+        assert_eq!(find_sue_stream(input.as_bytes(), &[("cats", 7)], &[]).unwrap(), Some(1));
+        assert!(find_sue_stream(input.as_bytes(), &[("cats", 8)], &[]).is_err());
+    }
+}
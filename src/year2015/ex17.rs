@@ -0,0 +1,137 @@
+//! [Day 17: No Such Thing as Too Much](https://adventofcode.com/2015/day/17).
+//!
+//! # Examples
+//! The examples of the puzzle text, storing 25 liters:
+//! ```
+//! use aoc2015::year2015::ex17::combinations;
+//! let ways = combinations(&[20, 15, 10, 5, 5], 25);
+//! assert_eq!(ways.iter().sum::<u64>(), 4);
+//! assert_eq!(ways.iter().find(|&&ways| ways > 0), Some(&3));
+//! ```
+
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, AocError};
+use crate::config::PuzzleConfig;
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 17 }
+    fn title(&self) -> &str { "No Such Thing as Too Much" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+    fn example_config(&self, _part: Part) -> PuzzleConfig { PuzzleConfig { eggnog_liters: EXAMPLE_LITERS, ..PuzzleConfig::PUZZLE } }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let ways = combinations(&parse(input)?, context.config.eggnog_liters);
+        match part {
+            Part::A => Ok(ways.iter().sum::<u64>().into()),
+            Part::B => Ok(ways.into_iter().find(|&ways| ways > 0).unwrap_or(0).into()),
+        }
+    }
+}
+
+/// The liters of eggnog to store in the puzzle.
+pub const TARGET_LITERS: usize = 150;
+
+/// The liters of eggnog to store in the examples of the puzzle text.
+const EXAMPLE_LITERS: usize = 25;
+
+/// The examples of the puzzle text, storing [`EXAMPLE_LITERS`] liters.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "20\n15\n10\n5\n5", answer: "4" },
+    Example { part: Part::B, input: "20\n15\n10\n5\n5", answer: "3" },
+];
+
+/// Parses the capacity of each container from the puzzle input.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if a capacity is not a number.
+pub fn parse(input: &str) -> Result<Vec<usize>, AocError> {
+    parse_lines(input, |line| line.parse().map_err(|_| format!("invalid container capacity '{line}'")))
+}
+
+/// Counts the combinations of containers holding exactly `target` liters,
+/// grouped by how many containers they use.
+///
+/// The counts are computed with a dynamic programming table over
+/// (liters, containers used), adding one container at a time as in a 0/1
+/// knapsack, in `O(n^2 * target)` time instead of enumerating all `2^n`
+/// subsets.
+///
+/// # Arguments
+/// * `containers` - The capacity of each container.
+/// * `target` - The liters to store.
+///
+/// # Returns
+/// The number of combinations using each number of containers, indexed by
+/// that number.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex17::combinations;
+/// assert_eq!(combinations(&[20, 15, 10, 5, 5], 25), [0, 0, 3, 1, 0, 0]);
+/// ```
+pub fn combinations(containers: &[usize], target: usize) -> Vec<u64> {
+    let n = containers.len();
+    // ways[liters][count] is the number of combinations of `count`
+    // containers, among those seen so far, holding exactly `liters`.
+    let mut ways = vec![vec![0u64; n + 1]; target + 1];
+    ways[0][0] = 1;
+    for (seen, &capacity) in containers.iter().enumerate() {
+        for liters in (capacity..=target).rev() {
+            for count in (1..=seen + 1).rev() {
+                ways[liters][count] += ways[liters - capacity][count - 1];
+            }
+        }
+    }
+    ways.swap_remove(target)
+}
+
+// 17th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/17
+//
+// This is a solution to the first part of the puzzle.
+// Every combination holding the eggnog is counted, whatever its size.
+pub fn a(input: &str) -> Result<u64, AocError> {
+    Ok(combinations(&parse(input)?, TARGET_LITERS).iter().sum())
+}
+
+// This is a solution to the second part of the puzzle.
+// Only the combinations using the fewest containers are counted.
+pub fn b(input: &str) -> Result<u64, AocError> {
+    Ok(combinations(&parse(input)?, TARGET_LITERS).into_iter().find(|&ways| ways > 0).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn containers_for_25_liters() {
+        let ways = combinations(&parse("20\n15\n10\n5\n5").unwrap(), 25);
+        assert_eq!(ways.iter().sum::<u64>(), 4);
+        assert_eq!(ways.into_iter().find(|&ways| ways > 0), Some(3));
+    }
+
+    #[test]
+    fn configured_liters() {
+        let config = PuzzleConfig { eggnog_liters: 25, ..PuzzleConfig::PUZZLE };
+        let context = Context { config: &config, ..Context::default() };
+        assert_eq!(Solver.solve_in(Part::A, "20\n15\n10\n5\n5", &context).unwrap(), Answer::from(4u64));
+        assert_eq!(Solver.solve_in(Part::B, "20\n15\n10\n5\n5", &context).unwrap(), Answer::from(3u64));
+    }
+}
@@ -0,0 +1,181 @@
+//! [Day 18: Like a GIF For Your Yard](https://adventofcode.com/2015/day/18).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex18::animate;
+//! let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+//! assert_eq!(animate(input, 4, false).unwrap(), 4);
+//! assert_eq!(animate(input, 5, true).unwrap(), 17);
+//! ```
+
+use crate::year2015::ex06::Grid;
+use crate::util::geom::Rect;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::AocError;
+use crate::config::PuzzleConfig;
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 18 }
+    fn title(&self) -> &str { "Like a GIF For Your Yard" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn example_config(&self, part: Part) -> PuzzleConfig {
+        let animation_steps = match part {
+            Part::A => EXAMPLE_STEPS_A,
+            Part::B => EXAMPLE_STEPS_B,
+        };
+        PuzzleConfig { animation_steps, ..PuzzleConfig::PUZZLE }
+    }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        animate(input, context.config.animation_steps, part == Part::B).map(Answer::from)
+    }
+}
+
+/// The number of steps to animate in the puzzle.
+pub const STEPS: usize = 100;
+
+/// The number of steps to animate in the example of the first part.
+const EXAMPLE_STEPS_A: usize = 4;
+
+/// The number of steps to animate in the example of the second part.
+const EXAMPLE_STEPS_B: usize = 5;
+
+/// The examples of the puzzle text, animated for [`EXAMPLE_STEPS_A`] and
+/// [`EXAMPLE_STEPS_B`] steps.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..", answer: "4" },
+    Example { part: Part::B, input: ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..", answer: "17" },
+];
+
+/// Parses the initial configuration of the lights into a [`Grid`].
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input is empty, not rectangular,
+/// or contains anything but `#` and `.`.
+pub fn parse(input: &str) -> Result<Grid, AocError> {
+    let lines: Vec<_> = input.lines().collect();
+    let width = lines.first().map_or(0, |line| line.len());
+    if width == 0 { return Err(AocError::parse(1, "empty grid")); }
+    let mut grid = Grid::new(width, lines.len());
+    for (y, line) in lines.iter().enumerate() {
+        if line.len() != width {
+            return Err(AocError::parse(y + 1, format!("expected {width} lights, found {}", line.len())));
+        }
+        for (x, light) in line.chars().enumerate() {
+            match light {
+                '#' => grid.set(x, y, true),
+                '.' => {}
+                _ => return Err(AocError::parse_at(y + 1, x + 1, format!("invalid light '{light}'"))),
+            }
+        }
+    }
+    Ok(grid)
+}
+
+/// Turns on the four corners of the grid.
+fn light_corners(grid: &mut Grid) {
+    let corners = Rect::sized(grid.width(), grid.height()).corners().unwrap_or_else(|| unreachable!("grids have lights"));
+    for corner in corners {
+        grid.set(corner.x, corner.y, true);
+    }
+}
+
+/// Returns the endless succession of states of the animated lights,
+/// starting with the initial configuration.
+///
+/// # Arguments
+/// * `input` - The initial configuration of the lights.
+/// * `stuck_corners` - Whether the corner lights are stuck on.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex18::states;
+/// let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+/// let counts: Vec<_> = states(input, false).unwrap().take(5).map(|grid| grid.count()).collect();
+/// assert_eq!(counts, [15, 11, 8, 4, 4]);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the initial configuration is malformed.
+pub fn states(input: &str, stuck_corners: bool) -> Result<impl Iterator<Item = Grid>, AocError> {
+    let mut grid = parse(input)?;
+    if stuck_corners { light_corners(&mut grid); }
+    Ok(std::iter::successors(Some(grid), move |grid| {
+        let mut next = grid.clone();
+        next.step();
+        if stuck_corners { light_corners(&mut next); }
+        Some(next)
+    }))
+}
+
+/// Animates the lights and returns how many are on at the end.
+///
+/// # Arguments
+/// * `input` - The initial configuration of the lights.
+/// * `steps` - The number of steps to animate.
+/// * `stuck_corners` - Whether the corner lights are stuck on.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex18::animate;
+/// let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+/// assert_eq!(animate(input, 4, false).unwrap(), 4);
+/// assert_eq!(animate(input, 5, true).unwrap(), 17);
+/// ```
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the initial configuration is malformed.
+pub fn animate(input: &str, steps: usize, stuck_corners: bool) -> Result<u32, AocError> {
+    let last = states(input, stuck_corners)?.nth(steps).unwrap();
+    Ok(last.count())
+}
+
+// 18th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/18
+//
+// This is a solution to the first part of the puzzle.
+// The lights are animated on the bitfield grid of day 6.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    animate(input, STEPS, false)
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, but with the corners stuck on.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    animate(input, STEPS, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIGHTS: &str = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn animation_in_4_steps() {
+        assert_eq!(animate(LIGHTS, 4, false).unwrap(), 4);
+    }
+
+    #[test]
+    fn animation_with_stuck_corners_in_5_steps() {
+        assert_eq!(animate(LIGHTS, 5, true).unwrap(), 17);
+    }
+}
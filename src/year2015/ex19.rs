@@ -0,0 +1,280 @@
+//! [Day 19: Medicine for Rudolph](https://adventofcode.com/2015/day/19).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex19::{a, b};
+//! assert_eq!(a("H => HO\nH => OH\nO => HH\n\nHOH").unwrap(), 4);
+//! assert_eq!(a("H => HO\nH => OH\nO => HH\n\nHOHOHO").unwrap(), 7);
+//! assert_eq!(b("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH").unwrap(), 3);
+//! assert_eq!(b("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO").unwrap(), 6);
+//! ```
+
+use std::collections::HashSet;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, AocError};
+use crate::solution::{Example, Part, Solution};
+use crate::util::intern::Interner;
+use crate::util::rng::XorShift;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 19 }
+    fn title(&self) -> &str { "Medicine for Rudolph" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(Grammar::parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        match part {
+            Part::A => self.part_a(input),
+            Part::B => fabricate(input, &mut context.rng()).map(Answer::from),
+        }
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "H => HO\nH => OH\nO => HH\n\nHOH", answer: "4" },
+    Example { part: Part::A, input: "H => HO\nH => OH\nO => HH\n\nHOHOHO", answer: "7" },
+    Example { part: Part::B, input: "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH", answer: "3" },
+    Example { part: Part::B, input: "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO", answer: "6" },
+];
+
+/// The number of shuffled rule orders tried before giving up a reduction.
+const MAX_RESTARTS: usize = 1000;
+
+/// A molecule, as the IDs of its atoms in the [`Grammar`].
+pub type Molecule = Vec<u16>;
+
+/// The replacement rules of the Red-Nosed Reindeer machine.
+///
+/// Molecules are handled as sequences of atoms, each atom being an
+/// uppercase letter followed by lowercase ones, such as `Ca`, or the `e`
+/// the machine starts from.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex19::Grammar;
+/// let (grammar, molecule) = Grammar::parse("H => HO\nH => OH\nO => HH\n\nHOH").unwrap();
+/// let (from, to) = &grammar.replacements[1];
+/// assert_eq!((grammar.spell(&[*from]), grammar.spell(to)), ("H".to_string(), "OH".to_string()));
+/// assert_eq!(grammar.spell(&molecule), "HOH");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    /// The ID of each atom.
+    pub atoms: Interner,
+    /// Each replacement rule, as a pair of the replaced atom and the
+    /// replacing molecule.
+    pub replacements: Vec<(u16, Molecule)>,
+}
+
+impl Grammar {
+    /// Parses the replacement rules and the medicine molecule of the input.
+    ///
+    /// # Returns
+    /// A tuple of the [`Grammar`] and the medicine molecule.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<(Grammar, Molecule), AocError> {
+        let Some((rules, molecule)) = input.split_once("\n\n") else {
+            return Err(AocError::parse(input.lines().count() + 1, "missing medicine molecule"));
+        };
+        let mut atoms = Interner::default();
+        let replacements = parse_lines(rules, |line| {
+            let (from, to) = line.split_once(" => ").ok_or_else(|| format!("invalid replacement '{line}'"))?;
+            let [from] = split_atoms(from, &mut atoms)?[..] else {
+                return Err(format!("expected a single atom to replace, found '{from}'"));
+            };
+            Ok((from, split_atoms(to, &mut atoms)?))
+        })?;
+        let molecule = split_atoms(molecule.trim(), &mut atoms)
+            .map_err(|message| AocError::parse(rules.lines().count() + 2, message))?;
+        tracing::debug!(rules = replacements.len(), atoms = molecule.len(), "parsed the grammar");
+        Ok((Grammar { atoms, replacements }, molecule))
+    }
+
+    /// Returns the text of `molecule`, for error messages and debug output.
+    pub fn spell(&self, molecule: &[u16]) -> String {
+        molecule.iter().map(|&atom| self.atoms.name(atom)).collect()
+    }
+
+    /// Returns every distinct molecule obtained by applying one replacement
+    /// to `molecule`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex19::Grammar;
+    /// let (grammar, molecule) = Grammar::parse("H => HO\nH => OH\nO => HH\n\nHOH").unwrap();
+    /// assert_eq!(grammar.single_replacements(&molecule).len(), 4);
+    /// let (grammar, molecule) = Grammar::parse("H => HO\nH => OH\nO => HH\n\nHOHOHO").unwrap();
+    /// assert_eq!(grammar.single_replacements(&molecule).len(), 7);
+    /// ```
+    pub fn single_replacements(&self, molecule: &[u16]) -> HashSet<Molecule> {
+        let mut molecules = HashSet::new();
+        for (from, to) in &self.replacements {
+            for index in (0..molecule.len()).filter(|&index| molecule[index] == *from) {
+                molecules.insert([&molecule[..index], to, &molecule[index + 1..]].concat());
+            }
+        }
+        molecules
+    }
+
+    /// Greedily reduces `molecule` back to `e` by applying the replacements
+    /// in reverse, always using the first rule of `order` that applies.
+    ///
+    /// Rules replacing `e` only apply to the whole molecule, as `e` is only
+    /// ever the starting point.
+    ///
+    /// # Arguments
+    /// * `molecule` - The molecule to reduce.
+    /// * `order` - The indices of the rules, in order of preference.
+    ///
+    /// # Returns
+    /// The number of steps taken to reach `e`, or `None` if the reduction
+    /// got stuck or went round in a cycle, as rules replacing one atom by
+    /// another can.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex19::Grammar;
+    /// let (grammar, molecule) = Grammar::parse("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH").unwrap();
+    /// assert_eq!(grammar.reduce(&molecule, &[2, 4, 0, 1, 3]), Some(3));
+    /// assert_eq!(grammar.reduce(&molecule, &[0, 1]), None);
+    /// ```
+    pub fn reduce(&self, molecule: &[u16], order: &[usize]) -> Option<usize> {
+        let start = self.atoms.get("e")?;
+        let mut molecule = molecule.to_vec();
+        let mut seen = HashSet::new();
+        let mut steps = 0;
+        while molecule != [start] {
+            if !seen.insert(molecule.clone()) {
+                tracing::trace!(steps, remaining = %self.spell(&molecule), "reduction went round in a cycle");
+                return None;
+            }
+            let reduced = order.iter().map(|&rule| &self.replacements[rule]).find_map(|(from, to)| {
+                if *from == start {
+                    (molecule == *to).then(|| vec![start])
+                } else {
+                    let index = molecule.windows(to.len()).position(|window| window == to.as_slice())?;
+                    Some([&molecule[..index], &[*from], &molecule[index + to.len()..]].concat())
+                }
+            });
+            let Some(reduced) = reduced else {
+                tracing::trace!(steps, remaining = %self.spell(&molecule), "reduction got stuck");
+                return None;
+            };
+            molecule = reduced;
+            steps += 1;
+        }
+        Some(steps)
+    }
+
+    /// Returns the number of steps to fabricate `molecule` starting from `e`.
+    ///
+    /// The molecule is [reduced](Grammar::reduce) greedily, trying rules with
+    /// longer replacements first. Whenever the reduction gets stuck it is
+    /// abandoned and restarted from the whole molecule with the rule order
+    /// shuffled by `rng`: a random restart, which keeps none of the choices
+    /// of the attempt that got stuck.
+    ///
+    /// # Returns
+    /// The number of steps, or `None` if no reduction reached `e` after
+    /// [`MAX_RESTARTS`] attempts.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::util::rng::XorShift;
+    /// use aoc2015::year2015::ex19::Grammar;
+    /// let (grammar, molecule) = Grammar::parse("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO").unwrap();
+    /// assert_eq!(grammar.fabrication_steps(&molecule, &mut XorShift::new(7)), Some(6));
+    /// ```
+    pub fn fabrication_steps(&self, molecule: &[u16], rng: &mut XorShift) -> Option<usize> {
+        let mut order: Vec<usize> = (0..self.replacements.len()).collect();
+        order.sort_by_key(|&rule| std::cmp::Reverse(self.replacements[rule].1.len()));
+        for restart in 0..MAX_RESTARTS {
+            if let Some(steps) = self.reduce(molecule, &order) {
+                tracing::debug!(restarts = restart, steps, "reduced the molecule to e");
+                return Some(steps);
+            }
+            rng.shuffle(&mut order);
+        }
+        None
+    }
+}
+
+/// Splits `molecule` into its atoms, interning them.
+///
+/// # Errors
+/// Returns a description of the problem if the molecule is empty or if
+/// there are too many distinct atoms.
+fn split_atoms(molecule: &str, atoms: &mut Interner) -> Result<Molecule, String> {
+    if molecule.is_empty() {
+        return Err("empty molecule".to_string());
+    }
+    let mut ids = Vec::new();
+    let mut rest = molecule;
+    while let Some(first) = rest.chars().next() {
+        let end = first.len_utf8() + rest[first.len_utf8()..].find(|c: char| !c.is_ascii_lowercase()).unwrap_or(rest.len() - first.len_utf8());
+        ids.push(atoms.intern(&rest[..end])?);
+        rest = &rest[end..];
+    }
+    Ok(ids)
+}
+
+// 19th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/19
+//
+// This is a solution to the first part of the puzzle.
+// Every single replacement is applied and the distinct results counted.
+pub fn a(input: &str) -> Result<usize, AocError> {
+    let (grammar, molecule) = Grammar::parse(input)?;
+    Ok(grammar.single_replacements(&molecule).len())
+}
+
+// This is a solution to the second part of the puzzle.
+// The medicine is reduced back to `e` by applying the rules in reverse,
+// with the rule orders of the restarts drawn from the default seed.
+pub fn b(input: &str) -> Result<usize, AocError> {
+    fabricate(input, &mut Context::default().rng())
+}
+
+/// Solves the second part with the rule orders of the restarts drawn from
+/// `rng`.
+fn fabricate(input: &str, rng: &mut XorShift) -> Result<usize, AocError> {
+    let (grammar, molecule) = Grammar::parse(input)?;
+    grammar.fabrication_steps(&molecule, rng).ok_or_else(|| AocError::unsolvable("unable to fabricate the medicine"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn any_seed_finds_the_steps() {
+        let input = "e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO";
+        for seed in [1, 7, 2015, u64::MAX] {
+            let context = Context { seed, ..Context::default() };
+            assert_eq!(Solver.solve_in(Part::B, input, &context).unwrap(), Answer::from(6usize));
+        }
+    }
+
+    #[test]
+    fn cyclic_reductions_are_abandoned() {
+        assert!(matches!(b("e => HH\nH => O\nO => H\n\nO"), Err(AocError::Unsolvable(_))));
+    }
+}
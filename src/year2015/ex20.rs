@@ -0,0 +1,186 @@
+//! [Day 20: Infinite Elves and Infinite Houses](https://adventofcode.com/2015/day/20).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex20::a;
+//! assert_eq!(a("70").unwrap(), 4);
+//! assert_eq!(a("120").unwrap(), 6);
+//! assert_eq!(a("150").unwrap(), 8);
+//! ```
+
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::AocError;
+use crate::progress::{NoProgress, ProgressSink};
+use crate::solution::{Example, Part, Solution};
+use crate::util::checkpoint::{Checkpoint, CheckpointStore, NoCheckpoint};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 20 }
+    fn title(&self) -> &str { "Infinite Elves and Infinite Houses" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let delivery = match part {
+            Part::A => UNLIMITED,
+            Part::B => LIMITED,
+        };
+        let target = match context.config.target_presents {
+            Some(target) => target,
+            None => parse(input)?,
+        };
+        Ok(lowest_house(target, delivery, context.progress, context.checkpoint).into())
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "10", answer: "1" },
+    Example { part: Part::A, input: "70", answer: "4" },
+    Example { part: Part::A, input: "120", answer: "6" },
+    Example { part: Part::A, input: "150", answer: "8" },
+];
+
+/// How the elves deliver presents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delivery {
+    /// The presents each elf delivers per house, multiplied by the elf number.
+    pub presents_per_elf: u32,
+    /// The number of houses each elf visits, or `None` if unlimited.
+    pub houses_per_elf: Option<usize>,
+}
+
+/// The deliveries of the first part: unlimited houses, 10 presents per elf.
+pub const UNLIMITED: Delivery = Delivery { presents_per_elf: 10, houses_per_elf: None };
+
+/// The deliveries of the second part: 50 houses, 11 presents per elf.
+pub const LIMITED: Delivery = Delivery { presents_per_elf: 11, houses_per_elf: Some(50) };
+
+/// The fewest houses sieved at once.
+const MIN_SEGMENT: usize = 1 << 14;
+
+/// Adds the presents of every elf to the houses
+/// `start..start + presents.len()`, `presents[0]` being house `start`.
+fn sieve(delivery: Delivery, start: usize, presents: &mut [u32]) {
+    let end = start + presents.len();
+    for elf in 1..end {
+        let first = start.div_ceil(elf).max(1) * elf;
+        let last = delivery.houses_per_elf.map_or(end - 1, |houses| (elf * houses).min(end - 1));
+        for house in (first..=last).step_by(elf) {
+            presents[house - start] += elf as u32 * delivery.presents_per_elf;
+        }
+    }
+}
+
+/// Returns the lowest house receiving at least `target` presents.
+///
+/// Presents are accumulated in a segmented sieve: each elf adds its
+/// presents to every house of the segment it visits, so every house total
+/// is known without factorizing. Segments grow with the houses already
+/// sieved, and the search stops at the first segment with a house
+/// receiving enough presents. It never goes past house
+/// `target / presents_per_elf`, rounded up, as its own elf brings enough
+/// presents to that house.
+///
+/// The number of houses sieved is reported to `progress` and saved to
+/// `checkpoint` after each segment, and a search resumes from the house
+/// saved by an interrupted search for the same target.
+///
+/// # Arguments
+/// * `target` - The presents the house must receive.
+/// * `delivery` - How the elves deliver presents.
+/// * `progress` - Receives the number of houses sieved.
+/// * `checkpoint` - Saves the progress of the search, and holds the one
+///   to resume from.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex20::{lowest_house, UNLIMITED};
+/// use aoc2015::progress::NoProgress;
+/// use aoc2015::util::checkpoint::NoCheckpoint;
+/// assert_eq!(lowest_house(70, UNLIMITED, &NoProgress, &NoCheckpoint), 4);
+/// assert_eq!(lowest_house(150, UNLIMITED, &NoProgress, &NoCheckpoint), 8);
+/// ```
+pub fn lowest_house(target: u32, delivery: Delivery, progress: &dyn ProgressSink, checkpoint: &dyn CheckpointStore) -> usize {
+    let limit = target.div_ceil(delivery.presents_per_elf).max(1) as usize;
+    let mut start = checkpoint.load()
+        .filter(|saved| saved.get("target") == Some(u64::from(target)))
+        .and_then(|saved| usize::try_from(saved.get("house")?).ok())
+        .unwrap_or(0)
+        .min(limit);
+    if start > 0 { tracing::info!(house = start, "resumed the search"); }
+    loop {
+        let end = (start + (start / 8).max(MIN_SEGMENT)).min(limit + 1);
+        tracing::debug!(start, end, "sieving houses");
+        progress.report(start as u64, Some(limit as u64 + 1));
+        let mut presents = vec![0u32; end - start];
+        sieve(delivery, start, &mut presents);
+        if let Some(offset) = presents.iter().position(|&total| total >= target) {
+            return start + offset;
+        }
+        start = end;
+        let mut saved = Checkpoint::new();
+        saved.set("house", start as u64);
+        saved.set("target", u64::from(target));
+        checkpoint.save(&saved);
+    }
+}
+
+/// Parses the target number of presents from the puzzle input.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input is not a number.
+fn parse(input: &str) -> Result<u32, AocError> {
+    let input = input.trim();
+    input.parse().map_err(|_| AocError::parse(1, format!("invalid number of presents '{input}'")))
+}
+
+// 20th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/20
+//
+// This is a solution to the first part of the puzzle.
+// The presents of every house are accumulated in a sieve.
+pub fn a(input: &str) -> Result<usize, AocError> {
+    Ok(lowest_house(parse(input)?, UNLIMITED, &NoProgress, &NoCheckpoint))
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, with lazier elves stopping after 50 houses.
+pub fn b(input: &str) -> Result<usize, AocError> {
+    Ok(lowest_house(parse(input)?, LIMITED, &NoProgress, &NoCheckpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::checkpoint::MemoryCheckpoint;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn resumes_from_a_checkpoint() {
+        let target = 3_400_000;
+        let store = MemoryCheckpoint::default();
+        let house = lowest_house(target, UNLIMITED, &NoProgress, &store);
+        assert!(store.load().and_then(|saved| saved.get("house")).is_some_and(|saved| saved <= house as u64));
+        assert_eq!(lowest_house(target, UNLIMITED, &NoProgress, &store), house);
+        // Houses below the checkpoint are not sieved again.
+        let mut saved = Checkpoint::new();
+        saved.set("house", house as u64 + 1);
+        saved.set("target", u64::from(target));
+        assert!(lowest_house(target, UNLIMITED, &NoProgress, &MemoryCheckpoint::new(saved)) > house);
+    }
+}
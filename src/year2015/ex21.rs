@@ -0,0 +1,223 @@
+//! [Day 21: RPG Simulator 20XX](https://adventofcode.com/2015/day/21).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex21::{player_wins, Stats};
+//! let player = Stats { hit_points: 8, damage: 5, armor: 5 };
+//! let boss = Stats { hit_points: 12, damage: 7, armor: 2 };
+//! assert!(player_wins(player, boss));
+//! ```
+
+use crate::answer::Answer;
+use crate::error::{parse_lines, AocError};
+use crate::solution::Solution;
+use crate::util::combinatorics::k_subsets;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 21 }
+    fn title(&self) -> &str { "RPG Simulator 20XX" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(Stats::parse(input).map(drop)) }
+}
+
+/// An item sold by the shop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub name: &'static str,
+    pub cost: u32,
+    pub damage: u32,
+    pub armor: u32,
+}
+
+const fn item(name: &'static str, cost: u32, damage: u32, armor: u32) -> Item {
+    Item { name, cost, damage, armor }
+}
+
+/// The weapons sold by the shop. Exactly one must be bought.
+pub const WEAPONS: [Item; 5] = [
+    item("Dagger", 8, 4, 0),
+    item("Shortsword", 10, 5, 0),
+    item("Warhammer", 25, 6, 0),
+    item("Longsword", 40, 7, 0),
+    item("Greataxe", 74, 8, 0),
+];
+
+/// The armor sold by the shop. At most one may be bought.
+pub const ARMOR: [Item; 5] = [
+    item("Leather", 13, 0, 1),
+    item("Chainmail", 31, 0, 2),
+    item("Splintmail", 53, 0, 3),
+    item("Bandedmail", 75, 0, 4),
+    item("Platemail", 102, 0, 5),
+];
+
+/// The rings sold by the shop. At most two different ones may be bought.
+pub const RINGS: [Item; 6] = [
+    item("Damage +1", 25, 1, 0),
+    item("Damage +2", 50, 2, 0),
+    item("Damage +3", 100, 3, 0),
+    item("Defense +1", 20, 0, 1),
+    item("Defense +2", 40, 0, 2),
+    item("Defense +3", 80, 0, 3),
+];
+
+/// The hit points the player starts with.
+pub const PLAYER_HIT_POINTS: u32 = 100;
+
+/// The stats of a fighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hit_points: u32,
+    pub damage: u32,
+    pub armor: u32,
+}
+
+impl Stats {
+    /// Parses the stats of the boss from the puzzle input.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let values = parse_lines(input, |line| {
+            let (_, value) = line.split_once(": ").ok_or_else(|| format!("invalid stat '{line}'"))?;
+            value.parse().map_err(|_| format!("invalid stat value '{value}'"))
+        })?;
+        let [hit_points, damage, armor] = values[..] else {
+            return Err(AocError::parse(values.len().min(3) + 1, "expected exactly 3 stats"));
+        };
+        Ok(Stats { hit_points, damage, armor })
+    }
+
+    /// Returns the number of turns this fighter needs to defeat `opponent`.
+    fn turns_to_defeat(&self, opponent: &Stats) -> u32 {
+        let damage = self.damage.saturating_sub(opponent.armor).max(1);
+        opponent.hit_points.div_ceil(damage)
+    }
+}
+
+/// Returns whether the player defeats the boss.
+///
+/// The player attacks first, so they win if they need no more turns to
+/// defeat the boss than the boss needs to defeat them.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex21::{player_wins, Stats};
+/// let player = Stats { hit_points: 8, damage: 5, armor: 5 };
+/// let boss = Stats { hit_points: 12, damage: 7, armor: 2 };
+/// assert!(player_wins(player, boss));
+/// ```
+pub fn player_wins(player: Stats, boss: Stats) -> bool {
+    player.turns_to_defeat(&boss) <= boss.turns_to_defeat(&player)
+}
+
+/// Returns every loadout the shop allows, as the items bought.
+///
+/// A loadout has one weapon, zero or one armor, and zero to two rings.
+pub fn loadouts() -> Vec<Vec<Item>> {
+    let ring_sets: Vec<Vec<Item>> = (0..=2).flat_map(|count| k_subsets(&RINGS, count)).collect();
+    let mut loadouts = Vec::new();
+    for weapon in WEAPONS {
+        for armor in std::iter::once(None).chain(ARMOR.iter().map(Some)) {
+            for rings in &ring_sets {
+                let mut loadout = vec![weapon];
+                loadout.extend(armor);
+                loadout.extend(rings);
+                loadouts.push(loadout);
+            }
+        }
+    }
+    loadouts
+}
+
+/// Returns the cost and the [`Stats`] of the player wearing `loadout`.
+pub fn equip(loadout: &[Item]) -> (u32, Stats) {
+    let sum = |stat: fn(&Item) -> u32| loadout.iter().map(stat).sum();
+    (sum(|item| item.cost), Stats { hit_points: PLAYER_HIT_POINTS, damage: sum(|item| item.damage), armor: sum(|item| item.armor) })
+}
+
+/// Returns the cheapest loadout with which the player defeats `boss`.
+///
+/// # Returns
+/// The cost of the loadout and its items, or `None` if no loadout wins.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex21::{cheapest_win, Stats};
+/// let boss = Stats { hit_points: 12, damage: 7, armor: 2 };
+/// let (cost, loadout) = cheapest_win(boss).unwrap();
+/// assert_eq!((cost, loadout[0].name), (8, "Dagger"));
+/// ```
+pub fn cheapest_win(boss: Stats) -> Option<(u32, Vec<Item>)> {
+    loadouts().into_iter()
+        .filter(|loadout| player_wins(equip(loadout).1, boss))
+        .map(|loadout| (equip(&loadout).0, loadout))
+        .min_by_key(|&(cost, _)| cost)
+}
+
+/// Returns the most expensive loadout with which the player loses to `boss`.
+///
+/// # Returns
+/// The cost of the loadout and its items, or `None` if every loadout wins.
+pub fn dearest_loss(boss: Stats) -> Option<(u32, Vec<Item>)> {
+    loadouts().into_iter()
+        .filter(|loadout| !player_wins(equip(loadout).1, boss))
+        .map(|loadout| (equip(&loadout).0, loadout))
+        .max_by_key(|&(cost, _)| cost)
+}
+
+// 21st day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/21
+//
+// This is a solution to the first part of the puzzle.
+// Every loadout is tried, keeping the cheapest one that wins.
+pub fn a(input: &str) -> Result<u32, AocError> {
+    cheapest_win(Stats::parse(input)?).map(|(cost, _)| cost).ok_or_else(|| AocError::unsolvable("no loadout wins"))
+}
+
+// This is a solution to the second part of the puzzle.
+// Every loadout is tried, keeping the most expensive one that loses.
+pub fn b(input: &str) -> Result<u32, AocError> {
+    dearest_loss(Stats::parse(input)?).map(|(cost, _)| cost).ok_or_else(|| AocError::unsolvable("no loadout loses"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_wins_example_fight() {
+        let player = Stats { hit_points: 8, damage: 5, armor: 5 };
+        let boss = Stats { hit_points: 12, damage: 7, armor: 2 };
+        assert!(player_wins(player, boss));
+    }
+
+    /// Asserts that `loadout` is one the shop allows and costs `cost`.
+    fn assert_allowed(cost: u32, loadout: &[Item]) {
+        let count = |shelf: &[Item]| loadout.iter().filter(|item| shelf.contains(item)).count();
+        assert_eq!(count(&WEAPONS), 1, "{loadout:?}");
+        assert!(count(&ARMOR) <= 1, "{loadout:?}");
+        assert!(count(&RINGS) <= 2, "{loadout:?}");
+        assert_eq!(count(&WEAPONS) + count(&ARMOR) + count(&RINGS), loadout.len(), "{loadout:?}");
+        assert_eq!(loadout.iter().map(|item| item.cost).sum::<u32>(), cost);
+    }
+
+    #[test]
+    fn witnesses_are_feasible() {
+        for boss in [Stats { hit_points: 100, damage: 8, armor: 2 }, Stats { hit_points: 104, damage: 8, armor: 1 }] {
+            let (cost, loadout) = cheapest_win(boss).unwrap();
+            assert_allowed(cost, &loadout);
+            assert!(player_wins(equip(&loadout).1, boss));
+
+            let (cost, loadout) = dearest_loss(boss).unwrap();
+            assert_allowed(cost, &loadout);
+            assert!(!player_wins(equip(&loadout).1, boss));
+        }
+    }
+}
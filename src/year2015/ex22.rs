@@ -0,0 +1,311 @@
+//! [Day 22: Wizard Simulator 20XX](https://adventofcode.com/2015/day/22).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex22::{least_mana_to_win, GameState};
+//! assert_eq!(least_mana_to_win(GameState::new(10, 250, 13, 8)), Some(226));
+//! assert_eq!(least_mana_to_win(GameState::new(10, 250, 14, 8)), Some(641));
+//! ```
+
+use crate::answer::Answer;
+use crate::error::{parse_lines, AocError};
+use crate::solution::Solution;
+use crate::util::search::astar_path;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 22 }
+    fn title(&self) -> &str { "Wizard Simulator 20XX" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(GameState::parse(input).map(drop)) }
+}
+
+/// The hit points the player starts with.
+pub const PLAYER_HIT_POINTS: i32 = 50;
+
+/// The mana the player starts with.
+pub const PLAYER_MANA: i32 = 500;
+
+/// A spell the player can cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Spell {
+    MagicMissile,
+    Drain,
+    Shield,
+    Poison,
+    Recharge,
+}
+
+impl Spell {
+    /// Every spell, from cheapest to most expensive.
+    pub const ALL: [Spell; 5] = [Spell::MagicMissile, Spell::Drain, Spell::Shield, Spell::Poison, Spell::Recharge];
+
+    /// Returns the mana it costs to cast this spell.
+    pub fn cost(self) -> i32 {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
+    }
+}
+
+/// The result of a round of the fight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The boss was defeated, after spending the given mana in the round.
+    Won(i32),
+    /// The player was defeated.
+    Lost,
+    /// The fight goes on from the provided [`GameState`].
+    Ongoing(GameState),
+}
+
+/// The state of the fight at the start of a player turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GameState {
+    pub player_hit_points: i32,
+    pub mana: i32,
+    pub boss_hit_points: i32,
+    pub boss_damage: i32,
+    /// The remaining turns of each effect.
+    pub shield: u8,
+    pub poison: u8,
+    pub recharge: u8,
+    /// Whether the player loses a hit point at the start of each of their turns.
+    pub hard: bool,
+}
+
+impl GameState {
+    /// Creates the [`GameState`] at the start of a fight.
+    ///
+    /// # Arguments
+    /// * `player_hit_points` - The hit points of the player.
+    /// * `mana` - The mana of the player.
+    /// * `boss_hit_points` - The hit points of the boss.
+    /// * `boss_damage` - The damage the boss deals each turn.
+    pub fn new(player_hit_points: i32, mana: i32, boss_hit_points: i32, boss_damage: i32) -> Self {
+        GameState { player_hit_points, mana, boss_hit_points, boss_damage, shield: 0, poison: 0, recharge: 0, hard: false }
+    }
+
+    /// Parses the boss of the puzzle input into the starting [`GameState`].
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Parse`] if the input is malformed.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let values = parse_lines(input, |line| {
+            let (_, value) = line.split_once(": ").ok_or_else(|| format!("invalid stat '{line}'"))?;
+            value.parse().map_err(|_| format!("invalid stat value '{value}'"))
+        })?;
+        let [hit_points, damage] = values[..] else {
+            return Err(AocError::parse(values.len().min(2) + 1, "expected exactly 2 stats"));
+        };
+        Ok(GameState::new(PLAYER_HIT_POINTS, PLAYER_MANA, hit_points, damage))
+    }
+
+    /// Applies the active effects at the start of a turn.
+    fn apply_effects(&mut self) {
+        if self.poison > 0 { self.boss_hit_points -= 3; }
+        if self.recharge > 0 { self.mana += 101; }
+        self.shield = self.shield.saturating_sub(1);
+        self.poison = self.poison.saturating_sub(1);
+        self.recharge = self.recharge.saturating_sub(1);
+    }
+
+    /// Returns a lower bound of the mana the player still has to spend to
+    /// defeat the boss.
+    ///
+    /// No spell deals damage more cheaply than a poison effect lasting to
+    /// the end, so finishing the boss costs at least the price of its hit
+    /// points at that rate, once the active poison has done its part.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex22::GameState;
+    /// assert_eq!(GameState::new(10, 250, 18, 8).mana_lower_bound(), 173);
+    /// ```
+    pub fn mana_lower_bound(&self) -> i32 {
+        let remaining = (self.boss_hit_points - 3 * i32::from(self.poison)).max(0);
+        remaining * Spell::Poison.cost() / 18
+    }
+
+    /// Plays a round where the player casts `spell` and the boss attacks back.
+    ///
+    /// # Returns
+    /// The [`Outcome`] of the round, or `None` if the spell cannot be cast,
+    /// either because the player lacks the mana or because its effect would
+    /// still be active.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex22::{GameState, Outcome, Spell};
+    /// let state = GameState::new(10, 250, 13, 8);
+    /// let Some(Outcome::Ongoing(state)) = state.cast(Spell::Poison) else { panic!() };
+    /// assert_eq!(state.player_hit_points, 2);
+    /// assert_eq!(state.cast(Spell::Poison), None);
+    /// assert_eq!(state.cast(Spell::MagicMissile), Some(Outcome::Won(53)));
+    /// ```
+    pub fn cast(&self, spell: Spell) -> Option<Outcome> {
+        let mut state = *self;
+        if state.hard {
+            state.player_hit_points -= 1;
+            if state.player_hit_points <= 0 { return Some(Outcome::Lost); }
+        }
+        state.apply_effects();
+        if state.boss_hit_points <= 0 { return Some(Outcome::Won(0)); }
+        if state.mana < spell.cost() { return None; }
+        state.mana -= spell.cost();
+        match spell {
+            Spell::MagicMissile => state.boss_hit_points -= 4,
+            Spell::Drain => { state.boss_hit_points -= 2; state.player_hit_points += 2; }
+            Spell::Shield if state.shield == 0 => state.shield = 6,
+            Spell::Poison if state.poison == 0 => state.poison = 6,
+            Spell::Recharge if state.recharge == 0 => state.recharge = 5,
+            _ => return None,
+        }
+        if state.boss_hit_points <= 0 { return Some(Outcome::Won(spell.cost())); }
+
+        let armor = if state.shield > 0 { 7 } else { 0 };
+        state.apply_effects();
+        if state.boss_hit_points <= 0 { return Some(Outcome::Won(spell.cost())); }
+        state.player_hit_points -= (state.boss_damage - armor).max(1);
+        if state.player_hit_points <= 0 { return Some(Outcome::Lost); }
+        Some(Outcome::Ongoing(state))
+    }
+}
+
+/// Returns the least mana the player can spend and still win the fight.
+///
+/// Fights are explored with the [`astar`](crate::util::search::astar)
+/// search, ordering states by the mana spent to reach them plus the
+/// [least mana](GameState::mana_lower_bound) needed to finish the boss, so
+/// the first win found is the cheapest one.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex22::{least_mana_to_win, GameState};
+/// assert_eq!(least_mana_to_win(GameState::new(10, 250, 13, 8)), Some(226));
+/// assert_eq!(least_mana_to_win(GameState::new(10, 250, 14, 8)), Some(641));
+/// ```
+pub fn least_mana_to_win(start: GameState) -> Option<i32> {
+    cheapest_win(start).map(|(mana, _)| mana)
+}
+
+/// Returns the cheapest way for the player to win the fight, as found by
+/// [`least_mana_to_win`].
+///
+/// # Returns
+/// The mana spent and the spells cast, in order, or `None` if the player
+/// cannot win. The boss may die to an effect at the start of a player
+/// turn, before the next spell is cast.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex22::{cheapest_win, GameState, Spell};
+/// assert_eq!(cheapest_win(GameState::new(10, 250, 13, 8)), Some((226, vec![Spell::Poison, Spell::MagicMissile])));
+/// ```
+pub fn cheapest_win(start: GameState) -> Option<(i32, Vec<Spell>)> {
+    let heuristic = |state: &Option<GameState>| state.map_or(0, |state| state.mana_lower_bound());
+    let (fights, mana) = astar_path(Some(start), rounds, heuristic, Option::is_none)?;
+    // Each round is replayed to find the spell leading to the next fight,
+    // a fight won by the effects alone needing none.
+    let spells = fights.windows(2).filter_map(|pair| {
+        let state = pair[0].expect("only the last fight is won");
+        Spell::ALL.into_iter().find(|&spell| round(state, spell) == Some((pair[1], spell.cost())))
+    });
+    Some((mana, spells.collect()))
+}
+
+/// Returns the fights following each spell the player can cast from
+/// `state` without losing, along with the mana spent, a `None` fight
+/// standing for a won one.
+///
+/// # Panics
+/// Panics if the fight is already won.
+fn rounds(state: &Option<GameState>) -> impl Iterator<Item = (Option<GameState>, i32)> {
+    let state = state.expect("won fights have no rounds left");
+    Spell::ALL.into_iter().filter_map(move |spell| round(state, spell))
+}
+
+/// Returns the fight following `spell` cast from `state` and the mana
+/// spent, a `None` fight standing for a won one, or `None` if the spell
+/// cannot be cast or the player loses.
+fn round(state: GameState, spell: Spell) -> Option<(Option<GameState>, i32)> {
+    match state.cast(spell)? {
+        Outcome::Won(mana) => Some((None, mana)),
+        Outcome::Ongoing(next) => Some((Some(next), spell.cost())),
+        Outcome::Lost => None,
+    }
+}
+
+// 22nd day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/22
+//
+// This is a solution to the first part of the puzzle.
+// The cheapest winning sequence of spells is searched with A*.
+pub fn a(input: &str) -> Result<i32, AocError> {
+    least_mana_to_win(GameState::parse(input)?).ok_or_else(|| AocError::unsolvable("the boss cannot be defeated"))
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, on hard difficulty.
+pub fn b(input: &str) -> Result<i32, AocError> {
+    let start = GameState { hard: true, ..GameState::parse(input)? };
+    least_mana_to_win(start).ok_or_else(|| AocError::unsolvable("the boss cannot be defeated"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::search::dijkstra;
+
+    #[test]
+    fn example_fights() {
+        assert_eq!(least_mana_to_win(GameState::new(10, 250, 13, 8)), Some(226));
+        assert_eq!(least_mana_to_win(GameState::new(10, 250, 14, 8)), Some(641));
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        for boss_hit_points in [13, 14, 30, 51, 58] {
+            for hard in [false, true] {
+                let start = GameState { hard, ..GameState::new(PLAYER_HIT_POINTS, PLAYER_MANA, boss_hit_points, 9) };
+                let expected = dijkstra(Some(start), rounds, Option::is_none).map(|(_, mana)| mana);
+                assert_eq!(least_mana_to_win(start), expected, "boss with {boss_hit_points} hit points");
+            }
+        }
+    }
+
+    #[test]
+    fn cheapest_wins_replay_to_a_win() {
+        for boss_hit_points in [13, 14, 30, 51, 58] {
+            for hard in [false, true] {
+                let start = GameState { hard, ..GameState::new(PLAYER_HIT_POINTS, PLAYER_MANA, boss_hit_points, 9) };
+                let Some((mana, spells)) = cheapest_win(start) else { continue };
+                let mut fight = Some(start);
+                let mut spent = 0;
+                for &spell in &spells {
+                    let state = fight.expect("no spell is cast once the boss is defeated");
+                    match state.cast(spell) {
+                        Some(Outcome::Ongoing(next)) => fight = Some(next),
+                        Some(Outcome::Won(cost)) if cost > 0 => fight = None,
+                        outcome => panic!("casting {spell:?} led to {outcome:?}"),
+                    }
+                    spent += spell.cost();
+                }
+                // Otherwise, the effects finish the boss on the next turn.
+                if let Some(state) = fight {
+                    assert_eq!(state.cast(Spell::MagicMissile), Some(Outcome::Won(0)));
+                }
+                assert_eq!(spent, mana, "boss with {boss_hit_points} hit points");
+            }
+        }
+    }
+}
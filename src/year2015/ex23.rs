@@ -0,0 +1,485 @@
+//! [Day 23: Opening the Turing Lock](https://adventofcode.com/2015/day/23).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex23::{parse_program, Register, Vm};
+//! let mut vm = Vm::new(parse_program("inc a\njio a, +2\ntpl a\ninc a").unwrap());
+//! vm.run().unwrap();
+//! assert_eq!(vm.register(Register::A), 2);
+//! ```
+
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use crate::answer::Answer;
+use crate::error::{parse_lines, parse_stream, AocError};
+use crate::solution::{Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 23 }
+    fn title(&self) -> &str { "Opening the Turing Lock" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse_program(input).map(drop)) }
+
+    fn solve_stream(&self, part: Part, reader: &mut dyn BufRead) -> Result<Answer, AocError> {
+        match part {
+            Part::A => a_stream(reader).map(Answer::from),
+            Part::B => b_stream(reader).map(Answer::from),
+        }
+    }
+}
+
+/// One of the two registers of the [`Vm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A,
+    B,
+}
+
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" => Ok(Register::A),
+            "b" => Ok(Register::B),
+            _ => Err(format!("Invalid register {s}")),
+        }
+    }
+}
+
+/// An instruction of the [`Vm`]. Jump offsets are relative to the instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Halves the register.
+    Hlf(Register),
+    /// Triples the register.
+    Tpl(Register),
+    /// Increments the register.
+    Inc(Register),
+    /// Jumps by the offset.
+    Jmp(isize),
+    /// Jumps by the offset if the register is even.
+    Jie(Register, isize),
+    /// Jumps by the offset if the register is one.
+    Jio(Register, isize),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    /// Parses an instruction such as `jio a, +2`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (opcode, operands) = line.split_once(' ').ok_or_else(|| format!("Invalid instruction {line}"))?;
+        let offset = |s: &str| s.parse::<isize>().map_err(|_| format!("Invalid offset {s}"));
+        let conditional = || -> Result<(Register, isize), String> {
+            let (register, jump) = operands.split_once(", ").ok_or_else(|| format!("Invalid instruction {line}"))?;
+            Ok((register.parse()?, offset(jump)?))
+        };
+        match opcode {
+            "hlf" => Ok(Instruction::Hlf(operands.parse()?)),
+            "tpl" => Ok(Instruction::Tpl(operands.parse()?)),
+            "inc" => Ok(Instruction::Inc(operands.parse()?)),
+            "jmp" => Ok(Instruction::Jmp(offset(operands)?)),
+            "jie" => conditional().map(|(register, jump)| Instruction::Jie(register, jump)),
+            "jio" => conditional().map(|(register, jump)| Instruction::Jio(register, jump)),
+            _ => Err(format!("Invalid opcode {opcode}")),
+        }
+    }
+}
+
+/// Parses a program into its [`Instruction`]s.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the program is malformed.
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, AocError> {
+    parse_lines(input, str::parse)
+}
+
+/// Reads a program into its [`Instruction`]s, one line at a time.
+///
+/// The instructions themselves are kept, as jumps may go back to any of
+/// them, but the text of the program never is as a whole.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the program is malformed, or an
+/// [`AocError::Io`] if it cannot be read.
+pub fn read_program(input: impl BufRead) -> Result<Vec<Instruction>, AocError> {
+    parse_stream(input, str::parse).collect()
+}
+
+/// The operation of an [`Instruction`], regardless of its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Hlf,
+    Tpl,
+    Inc,
+    Jmp,
+    Jie,
+    Jio,
+}
+
+impl Opcode {
+    /// Every opcode, in the order of [`Instruction`].
+    pub const ALL: [Opcode; 6] = [Opcode::Hlf, Opcode::Tpl, Opcode::Inc, Opcode::Jmp, Opcode::Jie, Opcode::Jio];
+
+    /// Returns the mnemonic of this opcode, such as `jio`.
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Hlf => "hlf",
+            Opcode::Tpl => "tpl",
+            Opcode::Inc => "inc",
+            Opcode::Jmp => "jmp",
+            Opcode::Jie => "jie",
+            Opcode::Jio => "jio",
+        }
+    }
+}
+
+impl Instruction {
+    /// Returns the [`Opcode`] of this instruction.
+    pub fn opcode(self) -> Opcode {
+        match self {
+            Instruction::Hlf(_) => Opcode::Hlf,
+            Instruction::Tpl(_) => Opcode::Tpl,
+            Instruction::Inc(_) => Opcode::Inc,
+            Instruction::Jmp(_) => Opcode::Jmp,
+            Instruction::Jie(..) => Opcode::Jie,
+            Instruction::Jio(..) => Opcode::Jio,
+        }
+    }
+}
+
+/// The number of instructions of each [`Opcode`] a [`Vm`] executed,
+/// recorded by [`Vm::run_traced`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// The count of each opcode, indexed like [`Opcode::ALL`].
+    counts: [u64; 6],
+}
+
+impl Profile {
+    /// Returns the number of instructions executed with this opcode.
+    pub fn count(&self, opcode: Opcode) -> u64 {
+        self.counts[opcode as usize]
+    }
+
+    /// Returns the number of instructions executed.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<_> = Opcode::ALL.iter().map(|&opcode| format!("{} {}", opcode.mnemonic(), self.count(opcode))).collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+/// The state of a [`Vm`], as seen by the observer of
+/// [`Vm::run_with_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// The values of registers `a` and `b`.
+    pub registers: [u64; 2],
+    /// The index of the next instruction to execute.
+    pub pc: isize,
+    /// The number of instructions executed so far.
+    pub steps: u64,
+}
+
+impl State {
+    /// Returns the value of a register.
+    pub fn register(&self, register: Register) -> u64 {
+        self.registers[register as usize]
+    }
+}
+
+impl fmt::Display for State {
+    /// Formats the state as a line of the trajectory dumped by
+    /// [`Vm::run_traced`], such as `12 pc=3 a=2 b=0`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} pc={} a={} b={}", self.steps, self.pc, self.registers[0], self.registers[1])
+    }
+}
+
+/// The most instructions a [`Vm`] executes before giving up on the program
+/// halting, far more than the puzzle's program needs.
+pub const MAX_STEPS: u64 = 10_000_000;
+
+/// The computer running Jane Marie's program.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex23::{parse_program, Register, Vm};
+/// let mut vm = Vm::new(parse_program("inc a\njio a, +2\ntpl a\ninc a").unwrap());
+/// vm.run().unwrap();
+/// assert_eq!(vm.register(Register::A), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vm {
+    /// The instructions of the program.
+    program: Vec<Instruction>,
+    /// The registers, the program counter and the step count.
+    state: State,
+}
+
+impl Vm {
+    /// Creates a [`Vm`] ready to run `program` with both registers at 0.
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Vm { program, state: State { registers: [0; 2], pc: 0, steps: 0 } }
+    }
+
+    /// Returns the value of a register.
+    pub fn register(&self, register: Register) -> u64 {
+        self.state.register(register)
+    }
+
+    /// Sets the value of a register.
+    pub fn set_register(&mut self, register: Register, value: u64) {
+        self.state.registers[register as usize] = value;
+    }
+
+    /// Returns the index of the next instruction to execute.
+    pub fn pc(&self) -> isize {
+        self.state.pc
+    }
+
+    /// Returns the [`State`] of this [`Vm`].
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Returns the next instruction to execute, or `None` once the program
+    /// counter has left the program.
+    pub fn next_instruction(&self) -> Option<Instruction> {
+        usize::try_from(self.state.pc).ok().and_then(|pc| self.program.get(pc)).copied()
+    }
+
+    /// Executes the next instruction.
+    ///
+    /// # Returns
+    /// Whether an instruction was executed, which is `false` once the
+    /// program counter has left the program.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if the instruction overflows a
+    /// register or the program counter, leaving the [`State`] as it was
+    /// before it.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex23::{parse_program, Register, Vm};
+    /// let mut vm = Vm::new(parse_program("tpl a").unwrap());
+    /// vm.set_register(Register::A, u64::MAX);
+    /// assert!(vm.step().is_err());
+    /// vm.set_register(Register::A, 2);
+    /// assert_eq!((vm.step().unwrap(), vm.step().unwrap()), (true, false));
+    /// assert_eq!(vm.register(Register::A), 6);
+    /// ```
+    pub fn step(&mut self) -> Result<bool, AocError> {
+        let Some(instruction) = self.next_instruction() else {
+            return Ok(false);
+        };
+        let overflow = || AocError::unsolvable(format!("{} overflowed, at {}", instruction.opcode().mnemonic(), self.state));
+        let mut registers = self.state.registers;
+        let mut jump = 1;
+        match instruction {
+            Instruction::Hlf(r) => registers[r as usize] /= 2,
+            Instruction::Tpl(r) => registers[r as usize] = registers[r as usize].checked_mul(3).ok_or_else(overflow)?,
+            Instruction::Inc(r) => registers[r as usize] = registers[r as usize].checked_add(1).ok_or_else(overflow)?,
+            Instruction::Jmp(offset) => jump = offset,
+            Instruction::Jie(r, offset) => if registers[r as usize].is_multiple_of(2) { jump = offset },
+            Instruction::Jio(r, offset) => if registers[r as usize] == 1 { jump = offset },
+        }
+        let pc = self.state.pc.checked_add(jump).ok_or_else(overflow)?;
+        self.state = State { registers, pc, steps: self.state.steps + 1 };
+        Ok(true)
+    }
+
+    /// Checks that the program has not run for too long to be expected to
+    /// halt.
+    fn check_steps(&self) -> Result<(), AocError> {
+        if self.state.steps >= MAX_STEPS {
+            return Err(AocError::unsolvable(format!("the program did not halt within {MAX_STEPS} steps, at {}", self.state)));
+        }
+        Ok(())
+    }
+
+    /// Runs the program until the program counter leaves it.
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if an instruction overflows, or
+    /// if the program is still running after [`MAX_STEPS`] instructions.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex23::{parse_program, Vm};
+    /// assert!(Vm::new(parse_program("jmp +0").unwrap()).run().is_err());
+    /// ```
+    pub fn run(&mut self) -> Result<(), AocError> {
+        while self.step()? {
+            self.check_steps()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the program until the program counter leaves it, calling
+    /// `observer` with the [`State`] after each instruction.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex23::{parse_program, Register, Vm};
+    /// let mut vm = Vm::new(parse_program("inc a\njio a, +2\ntpl a\ninc a").unwrap());
+    /// let mut trajectory = Vec::new();
+    /// vm.run_with_observer(|state| trajectory.push((state.pc, state.register(Register::A)))).unwrap();
+    /// assert_eq!(trajectory, [(1, 1), (3, 1), (4, 2)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Unsolvable`] if an instruction overflows, or
+    /// if the program is still running after [`MAX_STEPS`] instructions.
+    pub fn run_with_observer(&mut self, mut observer: impl FnMut(&State)) -> Result<(), AocError> {
+        while self.step()? {
+            observer(&self.state);
+            self.check_steps()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the program until the program counter leaves it, counting the
+    /// instructions executed by opcode.
+    ///
+    /// # Arguments
+    /// * `trajectory` - Receives the initial [`State`] and the state after
+    ///   each instruction, one line each, if provided.
+    ///
+    /// # Returns
+    /// The [`Profile`] of the instructions executed.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc2015::year2015::ex23::{parse_program, Opcode, Vm};
+    /// let mut vm = Vm::new(parse_program("inc a\njio a, +2\ntpl a\ninc a").unwrap());
+    /// let mut trajectory = Vec::new();
+    /// let profile = vm.run_traced(Some(&mut trajectory)).unwrap();
+    /// assert_eq!((profile.count(Opcode::Inc), profile.count(Opcode::Tpl), profile.total()), (2, 0, 3));
+    /// assert_eq!(String::from_utf8(trajectory).unwrap().lines().last(), Some("3 pc=4 a=2 b=0"));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`AocError::Io`] if the trajectory cannot be written, in
+    /// which case the program stops there, or an [`AocError::Unsolvable`]
+    /// if an instruction overflows or the program is still running after
+    /// [`MAX_STEPS`] instructions.
+    pub fn run_traced(&mut self, mut trajectory: Option<&mut dyn Write>) -> Result<Profile, AocError> {
+        let mut profile = Profile::default();
+        if let Some(writer) = trajectory.as_mut() {
+            writeln!(writer, "{}", self.state)?;
+        }
+        while let Some(instruction) = self.next_instruction() {
+            profile.counts[instruction.opcode() as usize] += 1;
+            self.step()?;
+            if let Some(writer) = trajectory.as_mut() {
+                writeln!(writer, "{}", self.state)?;
+            }
+            self.check_steps()?;
+        }
+        Ok(profile)
+    }
+}
+
+// 23rd day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/23
+//
+// This is a solution to the first part of the puzzle.
+// The program is run and register `b` read at the end.
+pub fn a(input: &str) -> Result<u64, AocError> {
+    run(parse_program(input)?, 0)
+}
+
+// Same as the first part, reading the program one line at a time.
+pub fn a_stream(input: impl BufRead) -> Result<u64, AocError> {
+    run(read_program(input)?, 0)
+}
+
+// This is a solution to the second part of the puzzle.
+// Same as the first part, starting with register `a` at 1.
+pub fn b(input: &str) -> Result<u64, AocError> {
+    run(parse_program(input)?, 1)
+}
+
+// Same as the second part, reading the program one line at a time.
+pub fn b_stream(input: impl BufRead) -> Result<u64, AocError> {
+    run(read_program(input)?, 1)
+}
+
+/// Runs a program with register `a` starting at `a`, returning the final
+/// value of register `b`.
+fn run(program: Vec<Instruction>, a: u64) -> Result<u64, AocError> {
+    let mut vm = Vm::new(program);
+    vm.set_register(Register::A, a);
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        let profile = vm.run_traced(None)?;
+        tracing::debug!(%profile, "ran the program");
+    } else {
+        vm.run()?;
+    }
+    Ok(vm.register(Register::B))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_program() {
+        let mut vm = Vm::new(parse_program("inc a\njio a, +2\ntpl a\ninc a").unwrap());
+        vm.run().unwrap();
+        assert_eq!(vm.register(Register::A), 2);
+    }
+
+    #[test]
+    fn observer_sees_every_step() {
+        let program = parse_program("inc b\njio b, +2\ntpl b\ninc b\njie b, +2\ninc a").unwrap();
+        let mut observed = Vec::new();
+        Vm::new(program.clone()).run_with_observer(|state| observed.push(*state)).unwrap();
+        let mut traced = Vec::new();
+        let profile = Vm::new(program).run_traced(Some(&mut traced)).unwrap();
+        let traced = String::from_utf8(traced).unwrap();
+        assert_eq!(traced.lines().skip(1).collect::<Vec<_>>(), observed.iter().map(State::to_string).collect::<Vec<_>>());
+        assert_eq!(profile.total(), observed.len() as u64);
+        assert_eq!(profile.to_string(), "hlf 0, tpl 0, inc 2, jmp 0, jie 1, jio 1");
+    }
+
+    #[test]
+    fn stream() {
+        let program = "inc b\njio b, +2\ntpl b\ninc b";
+        for part in [Part::A, Part::B] {
+            assert_eq!(Solver.solve_stream(part, &mut program.as_bytes()).unwrap(), Solver.solve(part, program).unwrap());
+        }
+    }
+
+    #[test]
+    fn endless_programs_are_stopped() {
+        let program = parse_program("inc b\njmp +0").unwrap();
+        let err = Vm::new(program.clone()).run().unwrap_err();
+        assert_eq!(err.to_string(), format!("no solution: the program did not halt within {MAX_STEPS} steps, at {MAX_STEPS} pc=1 a=0 b=1"));
+        assert!(matches!(Vm::new(program.clone()).run_with_observer(|_| {}), Err(AocError::Unsolvable(_))));
+        assert!(matches!(Vm::new(program).run_traced(None), Err(AocError::Unsolvable(_))));
+        assert!(matches!(a("jmp +0"), Err(AocError::Unsolvable(_))));
+    }
+
+    #[test]
+    fn overflows_are_reported() {
+        let err = a("inc a\ntpl a\njmp -1").unwrap_err();
+        assert_eq!(err.to_string(), "no solution: tpl overflowed, at 81 pc=1 a=12157665459056928801 b=0");
+    }
+}
@@ -0,0 +1,235 @@
+//! [Day 24: It Hangs in the Balance](https://adventofcode.com/2015/day/24).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex24::{a, b};
+//! let input = "1\n2\n3\n4\n5\n7\n8\n9\n10\n11";
+//! assert_eq!(a(input).unwrap(), 99);
+//! assert_eq!(b(input).unwrap(), 44);
+//! ```
+
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::error::{parse_lines, AocError};
+use crate::progress::{NoProgress, ProgressSink};
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 24 }
+    fn title(&self) -> &str { "It Hangs in the Balance" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { b(input).map(Answer::from) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+
+    fn solve_in(&self, part: Part, input: &str, context: &Context) -> Result<Answer, AocError> {
+        let groups = match part {
+            Part::A => 3,
+            Part::B => 4,
+        };
+        best_entanglement(&parse(input)?, groups, context.progress)
+            .map(Answer::from)
+            .ok_or_else(|| AocError::unsolvable("the packages cannot be balanced"))
+    }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "1\n2\n3\n4\n5\n7\n8\n9\n10\n11", answer: "99" },
+    Example { part: Part::B, input: "1\n2\n3\n4\n5\n7\n8\n9\n10\n11", answer: "44" },
+];
+
+/// Parses the package weights from the puzzle input, heaviest first.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if a weight is not a number.
+pub fn parse(input: &str) -> Result<Vec<u64>, AocError> {
+    let mut weights = parse_lines(input, |line| line.parse::<u64>().map_err(|_| format!("invalid weight '{line}'")))?;
+    weights.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(weights)
+}
+
+/// Collects every subset of `size` weights adding up to `target`.
+///
+/// # Arguments
+/// * `weights` - The weights to pick from, heaviest first.
+/// * `size` - The number of weights left to pick.
+/// * `target` - The weight left to reach.
+/// * `subset` - The weights picked so far.
+/// * `subsets` - Where the complete subsets are collected.
+fn subsets(weights: &[u64], size: usize, target: u64, subset: &mut Vec<u64>, subsets: &mut Vec<Vec<u64>>) {
+    if size == 0 {
+        if target == 0 { subsets.push(subset.clone()); }
+        return;
+    }
+    for (index, &weight) in weights.iter().enumerate() {
+        // The remaining weights are lighter, so they cannot do better.
+        if weight * (size as u64) < target { break; }
+        if weight > target || weights.len() - index < size { continue; }
+        subset.push(weight);
+        self::subsets(&weights[index + 1..], size - 1, target - weight, subset, subsets);
+        subset.pop();
+    }
+}
+
+/// Splits `weights` into `groups` groups weighing `target` each, filling
+/// one group at a time by backtracking.
+///
+/// # Arguments
+/// * `weights` - The weights to split, heaviest first.
+/// * `groups` - The number of groups to fill.
+/// * `target` - The weight of each group.
+///
+/// # Returns
+/// The weights of each group, or `None` if they cannot be split that way.
+fn partition(weights: &[u64], groups: usize, target: u64) -> Option<Vec<Vec<u64>>> {
+    /// Fills the last of the `filled` groups, then the `groups` left after it.
+    fn fill(weights: &[u64], used: &mut [bool], filled: &mut Vec<Vec<u64>>, groups: usize, target: u64, left: u64, start: usize) -> bool {
+        if left == 0 {
+            if groups == 0 { return true; }
+            filled.push(Vec::new());
+            if fill(weights, used, filled, groups - 1, target, target, 0) { return true; }
+            filled.pop();
+            return false;
+        }
+        for index in start..weights.len() {
+            if used[index] || weights[index] > left { continue; }
+            used[index] = true;
+            filled.last_mut().expect("a group is being filled").push(weights[index]);
+            if fill(weights, used, filled, groups, target, left - weights[index], index + 1) { return true; }
+            filled.last_mut().expect("a group is being filled").pop();
+            used[index] = false;
+            // A fresh group fails the same way whichever weight it starts with.
+            if left == target { break; }
+        }
+        false
+    }
+    if weights.iter().sum::<u64>() != target * groups as u64 { return None; }
+    if groups == 0 { return Some(Vec::new()); }
+    let mut filled = vec![Vec::new()];
+    fill(weights, &mut vec![false; weights.len()], &mut filled, groups - 1, target, target, 0).then_some(filled)
+}
+
+/// Returns the quantum entanglement of the ideal first group when splitting
+/// the packages into `groups` groups of equal weight.
+///
+/// The first group must have as few packages as possible, and then the
+/// lowest quantum entanglement (product of its weights). Candidate first
+/// groups are generated by increasing size, and within a size by increasing
+/// entanglement, until one leaves packages that can be split into the
+/// remaining groups.
+///
+/// # Arguments
+/// * `weights` - The package weights, heaviest first.
+/// * `groups` - The number of groups to split the packages into.
+/// * `progress` - Receives the number of candidates checked for the
+///   current group size, out of the candidates of that size.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex24::{best_entanglement, parse};
+/// use aoc2015::progress::NoProgress;
+/// let weights = parse("1\n2\n3\n4\n5\n7\n8\n9\n10\n11").unwrap();
+/// assert_eq!(best_entanglement(&weights, 3, &NoProgress), Some(99));
+/// assert_eq!(best_entanglement(&weights, 4, &NoProgress), Some(44));
+/// ```
+pub fn best_entanglement(weights: &[u64], groups: usize, progress: &dyn ProgressSink) -> Option<u64> {
+    best_grouping(weights, groups, progress).map(|(entanglement, _)| entanglement)
+}
+
+/// Returns the ideal split of the packages into `groups` groups of equal
+/// weight, as found by [`best_entanglement`].
+///
+/// # Arguments
+/// The same as [`best_entanglement`].
+///
+/// # Returns
+/// The quantum entanglement of the first group and the weights of every
+/// group, the first one first, or `None` if the packages cannot be split.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex24::{best_grouping, parse};
+/// use aoc2015::progress::NoProgress;
+/// let weights = parse("1\n2\n3\n4\n5\n7\n8\n9\n10\n11").unwrap();
+/// let (entanglement, groups) = best_grouping(&weights, 3, &NoProgress).unwrap();
+/// assert_eq!((entanglement, &groups[0]), (99, &vec![11, 9]));
+/// assert!(groups.iter().all(|group| group.iter().sum::<u64>() == 20));
+/// ```
+pub fn best_grouping(weights: &[u64], groups: usize, progress: &dyn ProgressSink) -> Option<(u64, Vec<Vec<u64>>)> {
+    let total: u64 = weights.iter().sum();
+    if groups == 0 || !total.is_multiple_of(groups as u64) { return None; }
+    let target = total / groups as u64;
+    for size in 1..=weights.len() {
+        let mut candidates = Vec::new();
+        subsets(weights, size, target, &mut Vec::new(), &mut candidates);
+        candidates.sort_by_cached_key(|group| group.iter().product::<u64>());
+        let total = candidates.len() as u64;
+        tracing::debug!(size, candidates = total, "checking groups");
+        for (checked, group) in candidates.into_iter().enumerate() {
+            progress.report(checked as u64, Some(total));
+            let rest: Vec<u64> = {
+                let mut rest = weights.to_vec();
+                for weight in &group {
+                    let index = rest.iter().position(|w| w == weight).unwrap();
+                    rest.remove(index);
+                }
+                rest
+            };
+            if let Some(others) = partition(&rest, groups - 1, target) {
+                let entanglement = group.iter().product();
+                return Some((entanglement, std::iter::once(group).chain(others).collect()));
+            }
+        }
+    }
+    None
+}
+
+// 24th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/24
+//
+// This is a solution to the first part of the puzzle.
+// The packages are split into three groups.
+pub fn a(input: &str) -> Result<u64, AocError> {
+    best_entanglement(&parse(input)?, 3, &NoProgress).ok_or_else(|| AocError::unsolvable("the packages cannot be balanced"))
+}
+
+// This is a solution to the second part of the puzzle.
+// The packages are split into four groups, one going into the trunk.
+pub fn b(input: &str) -> Result<u64, AocError> {
+    best_entanglement(&parse(input)?, 4, &NoProgress).ok_or_else(|| AocError::unsolvable("the packages cannot be balanced"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn groupings_are_feasible() {
+        let weights = parse(EXAMPLES[0].input).unwrap();
+        for groups in [3, 4] {
+            let (entanglement, grouping) = best_grouping(&weights, groups, &NoProgress).unwrap();
+            assert_eq!(grouping.len(), groups);
+            let target = weights.iter().sum::<u64>() / groups as u64;
+            assert!(grouping.iter().all(|group| group.iter().sum::<u64>() == target), "{grouping:?}");
+            // Any group could go first, so none can be smaller than the first one.
+            assert!(grouping.iter().all(|group| group.len() >= grouping[0].len()), "{grouping:?}");
+            assert_eq!(grouping[0].iter().product::<u64>(), entanglement);
+            let mut packed: Vec<u64> = grouping.concat();
+            packed.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(packed, weights);
+        }
+    }
+}
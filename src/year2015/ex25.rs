@@ -0,0 +1,177 @@
+//! [Day 25: Let It Snow](https://adventofcode.com/2015/day/25).
+//!
+//! # Examples
+//! The examples of the puzzle text:
+//! ```
+//! use aoc2015::year2015::ex25::{a, code_at};
+//! assert_eq!(code_at(1, 1), Some(20151125));
+//! assert_eq!(code_at(6, 6), Some(27995004));
+//! assert_eq!(a("Enter the code at row 2, column 1.").unwrap(), 31916031);
+//! ```
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Part, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u32 { 2015 }
+    fn day(&self) -> u32 { 25 }
+    fn title(&self) -> &str { "Let It Snow" }
+    fn part_a(&self, input: &str) -> Result<Answer, AocError> { a(input).map(Answer::from) }
+    fn part_b(&self, input: &str) -> Result<Answer, AocError> { Ok(b(input).into()) }
+    fn check_input(&self, input: &str) -> Option<Result<(), AocError>> { Some(parse(input).map(drop)) }
+    fn examples(&self) -> &'static [Example] { EXAMPLES }
+}
+
+/// The examples of the puzzle text.
+const EXAMPLES: &[Example] = &[
+    Example { part: Part::A, input: "Enter the code at row 1, column 1.", answer: "20151125" },
+    Example { part: Part::A, input: "Enter the code at row 2, column 1.", answer: "31916031" },
+    Example { part: Part::A, input: "Enter the code at row 6, column 6.", answer: "27995004" },
+];
+
+/// The first code of the grid.
+const FIRST_CODE: u64 = 20151125;
+/// The factor each code is multiplied by to get the next one.
+const FACTOR: u64 = 252533;
+/// The modulus each code is reduced by.
+const MODULUS: u64 = 33554393;
+
+lazy_static! {
+    /// The regex used to extract the row and the column from the input.
+    static ref EX25_REGEX: Regex = Regex::new(r"row (\d+), column (\d+)").unwrap();
+}
+
+/// Parses the row and the column of the requested code.
+///
+/// # Errors
+/// Returns an [`AocError::Parse`] if the input does not mention a row and a
+/// column, both starting at 1.
+fn parse(input: &str) -> Result<(u64, u64), AocError> {
+    let captures = EX25_REGEX.captures(input).ok_or_else(|| AocError::parse(1, "missing row and column"))?;
+    let number = |index: usize| match captures[index].parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(AocError::parse(1, format!("invalid position '{}'", &captures[index]))),
+    };
+    Ok((number(1)?, number(2)?))
+}
+
+/// Returns the 0-based position of a code in the order codes are filled in,
+/// going up each diagonal from the first column.
+///
+/// The index is computed in `u128`, as it is about half the square of the
+/// diagonal, which overflows a `u64` past the 2^32nd diagonal.
+///
+/// # Returns
+/// The index, or `None` for a row or column of 0, or if the index does not
+/// even fit in a `u128`, which only happens when the row and the column add
+/// up to more than about 2^64.5.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex25::index;
+/// assert_eq!(index(1, 1), Some(0));
+/// assert_eq!(index(4, 2), Some(11));
+/// assert_eq!(index(1 << 40, 1), Some(((1 << 80) - (1 << 40)) / 2));
+/// assert_eq!(index(u64::MAX, u64::MAX), None);
+/// assert_eq!(index(0, 1), None);
+/// ```
+pub fn index(row: u64, column: u64) -> Option<u128> {
+    if row == 0 || column == 0 {
+        return None;
+    }
+    let diagonal = u128::from(row) + u128::from(column) - 1;
+    Some(diagonal.checked_mul(diagonal - 1)? / 2 + u128::from(column) - 1)
+}
+
+/// Computes `base^exponent mod modulus` by repeated squaring.
+///
+/// The products are computed in `u128`, so that any `u64` modulus works.
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex25::pow_mod;
+/// assert_eq!(pow_mod(3, 4, 5), 1);
+/// assert_eq!(pow_mod(2, 10, 1000), 24);
+/// assert_eq!(pow_mod(2, 1 << 100, u64::MAX), 1);
+/// ```
+///
+/// # Panics
+/// Panics if `modulus` is 0.
+pub fn pow_mod(base: u64, exponent: u128, modulus: u64) -> u64 {
+    let multiply = |a: u64, b: u64| (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64;
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 { result = multiply(result, base); }
+        base = multiply(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Returns the code at the provided position of the grid, however far
+/// beyond the puzzle input.
+///
+/// As each code is the previous one multiplied by a constant, the code at
+/// index `n` is the first code times the constant to the `n`th power, all
+/// modulo the modulus.
+///
+/// # Returns
+/// The code, or `None` if the position has no [`index`].
+///
+/// # Examples
+/// ```
+/// use aoc2015::year2015::ex25::code_at;
+/// assert_eq!(code_at(1, 1), Some(20151125));
+/// assert_eq!(code_at(6, 6), Some(27995004));
+/// assert!(code_at(u64::MAX / 2, u64::MAX / 2).is_some());
+/// assert_eq!(code_at(u64::MAX, u64::MAX), None);
+/// ```
+pub fn code_at(row: u64, column: u64) -> Option<u64> {
+    Some(FIRST_CODE * pow_mod(FACTOR, index(row, column)?, MODULUS) % MODULUS)
+}
+
+// 25th day of Advent of Code 2015
+//
+// https://adventofcode.com/2015/day/25
+//
+// This is a solution to the first part of the puzzle.
+// The code is computed directly from its position in the grid.
+pub fn a(input: &str) -> Result<u64, AocError> {
+    let (row, column) = parse(input)?;
+    code_at(row, column).ok_or_else(|| AocError::unsolvable(format!("the index of row {row}, column {column} is too large")))
+}
+
+// There is no second part to the last puzzle.
+pub fn b(_input: &str) -> String {
+    "Merry Christmas!".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        for example in EXAMPLES {
+            example.check(&Solver).unwrap();
+        }
+    }
+
+    #[test]
+    fn code_at_follows_the_filling_order() {
+        let mut code = FIRST_CODE;
+        for diagonal in 1..=30 {
+            for column in 1..=diagonal {
+                assert_eq!(code_at(diagonal + 1 - column, column), Some(code));
+                code = code * FACTOR % MODULUS;
+            }
+        }
+    }
+}
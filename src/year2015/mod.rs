@@ -0,0 +1,65 @@
+//! Solutions to [Advent of Code 2015](https://adventofcode.com/2015).
+//!
+//! Each day lives in its own `exNN` module exposing `a` and `b` solvers for
+//! both parts of the puzzle, plus a `Solver` implementing [`Solution`],
+//! registered by [`days`].
+
+use crate::solution::Solution;
+
+pub mod ex01;
+pub mod ex02;
+pub mod ex03;
+pub mod ex04;
+pub mod ex05;
+pub mod ex06;
+pub mod ex07;
+pub mod ex08;
+pub mod ex09;
+pub mod ex10;
+pub mod ex11;
+pub mod ex12;
+pub mod ex13;
+pub mod ex14;
+pub mod ex16;
+pub mod ex17;
+pub mod ex18;
+pub mod ex19;
+pub mod ex20;
+pub mod ex21;
+pub mod ex22;
+pub mod ex23;
+pub mod ex24;
+pub mod ex25;
+
+/// The year of the puzzles of this module.
+pub const YEAR: u32 = 2015;
+
+/// Returns the [`Solution`] of every implemented day of 2015, ordered by day.
+pub fn days() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(ex01::Solver),
+        Box::new(ex02::Solver),
+        Box::new(ex03::Solver),
+        Box::new(ex04::Solver),
+        Box::new(ex05::Solver),
+        Box::new(ex06::Solver),
+        Box::new(ex07::Solver),
+        Box::new(ex08::Solver),
+        Box::new(ex09::Solver),
+        Box::new(ex10::Solver),
+        Box::new(ex11::Solver),
+        Box::new(ex12::Solver),
+        Box::new(ex13::Solver),
+        Box::new(ex14::Solver),
+        Box::new(ex16::Solver),
+        Box::new(ex17::Solver),
+        Box::new(ex18::Solver),
+        Box::new(ex19::Solver),
+        Box::new(ex20::Solver),
+        Box::new(ex21::Solver),
+        Box::new(ex22::Solver),
+        Box::new(ex23::Solver),
+        Box::new(ex24::Solver),
+        Box::new(ex25::Solver),
+    ]
+}
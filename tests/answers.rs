@@ -0,0 +1,86 @@
+//! Regression tests checking the answers of every implemented day.
+//!
+//! Two sources of expected answers are checked:
+//! * `tests/examples.toml`, the examples of the puzzle descriptions.
+//! * `answers.toml`, the answers to the personal puzzle inputs. As inputs
+//!   are not meant to be shared, this file is not tracked and days whose
+//!   input is not cached under `data/` are skipped. Each answer is checked
+//!   both on the whole input and streaming the input file. The file maps
+//!   each day to the answers of its parts:
+//!
+//! ```toml
+//! [day01]
+//! a = "280"
+//! b = "1797"
+//! ```
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use aoc2015::answer::Answer;
+use aoc2015::days;
+use aoc2015::input::cache_path;
+use aoc2015::solution::Part;
+use aoc2015::year2015::YEAR;
+use toml::{Table, Value};
+
+/// Reads and parses a TOML file.
+fn read_toml(path: &str) -> Table {
+    let content = fs::read_to_string(path).unwrap_or_else(|err| panic!("Unable to read {path}: {err}"));
+    content.parse().unwrap_or_else(|err| panic!("Unable to parse {path}: {err}"))
+}
+
+/// Returns the string value of `key` in `table`.
+fn string<'a>(table: &'a Table, key: &str) -> &'a str {
+    table.get(key).and_then(Value::as_str).unwrap_or_else(|| panic!("Missing string {key}"))
+}
+
+/// Runs one part of a day and returns its answer.
+fn solve(day: u32, part: Part, input: &str) -> Answer {
+    let solution = days::find(YEAR, day).unwrap_or_else(|| panic!("Day {day} is not implemented"));
+    solution.solve(part, input).unwrap_or_else(|err| panic!("Day {day} part {part} failed: {err}"))
+}
+
+/// Runs one part of a day, streaming its cached input, and returns its answer.
+fn solve_stream(day: u32, part: Part) -> Answer {
+    let solution = days::find(YEAR, day).unwrap_or_else(|| panic!("Day {day} is not implemented"));
+    let mut reader = BufReader::new(File::open(cache_path(YEAR, day)).unwrap());
+    solution.solve_stream(part, &mut reader).unwrap_or_else(|err| panic!("Day {day} part {part} failed: {err}"))
+}
+
+#[test]
+fn examples() {
+    let examples = read_toml("tests/examples.toml");
+    let examples = examples["example"].as_array().expect("Missing examples");
+    for example in examples {
+        let example = example.as_table().expect("Bad example");
+        let day = example["day"].as_integer().expect("Bad day") as u32;
+        let part: Part = string(example, "part").parse().unwrap();
+        let input = string(example, "input");
+        let expected: Answer = string(example, "answer").parse().unwrap();
+        assert_eq!(solve(day, part, input), expected, "day {day} part {part} on {input:?}");
+    }
+}
+
+#[test]
+fn personal_answers() {
+    if !Path::new("answers.toml").exists() {
+        eprintln!("answers.toml not found, skipping");
+        return;
+    }
+    for (key, answers) in read_toml("answers.toml") {
+        let day: u32 = key.strip_prefix("day").and_then(|day| day.parse().ok())
+            .unwrap_or_else(|| panic!("Bad day {key}"));
+        let Ok(input) = fs::read_to_string(cache_path(YEAR, day)) else {
+            eprintln!("No input for day {day}, skipping");
+            continue;
+        };
+        let answers = answers.as_table().unwrap_or_else(|| panic!("Bad answers for {key}"));
+        for (part, expected) in answers {
+            let part: Part = part.parse().unwrap();
+            let expected: Answer = expected.as_str().expect("Bad answer").parse().unwrap();
+            assert_eq!(solve(day, part, input.trim_end()), expected, "day {day} part {part}");
+            assert_eq!(solve_stream(day, part), expected, "day {day} part {part} streamed");
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Fuzz tests of the line parsers: malformed inputs must be rejected with
+//! an error, never with a panic, and the diagnostics of `check-input` must
+//! render for any of them.
+//!
+//! The inputs are either random text or valid inputs made by
+//! [`aoc2015::gen`] with random edits, which get much deeper into the
+//! parsers than random text does. Set `PROPTEST_CASES` to fuzz longer than
+//! the default 256 cases.
+
+use aoc2015::days;
+use aoc2015::diagnostic::Diagnostic;
+use aoc2015::gen::generate;
+use aoc2015::year2015::{ex02, ex06, ex07, ex09, ex14, ex16, ex23, YEAR};
+use proptest::prelude::*;
+
+/// A function parsing an input, discarding the result.
+type Parser = fn(&str);
+
+/// Each day whose parser is fuzzed, along with its [`Parser`].
+const PARSERS: [(u32, Parser); 7] = [
+    // Day 2 has no public parser, and its answers are cheap to compute.
+    (2, |input| { let _ = (ex02::a(input), ex02::b(input)); }),
+    (6, |input| { let _ = input.lines().map(str::parse::<ex06::Instruction>).collect::<Vec<_>>(); }),
+    (7, |input| { let _ = ex07::Circuit::parse(input); }),
+    (9, |input| { let _ = ex09::Graph::parse(input); }),
+    (14, |input| { let _ = ex14::parse(input); }),
+    (16, |input| { let _ = input.lines().map(ex16::Sue::parse).collect::<Vec<_>>(); }),
+    (23, |input| { let _ = ex23::parse_program(input); }),
+];
+
+/// A random edit of an input.
+#[derive(Debug, Clone)]
+enum Edit {
+    /// Inserts text at a position.
+    Insert(usize, String),
+    /// Removes up to a number of characters from a position.
+    Remove(usize, usize),
+    /// Repeats up to a number of characters from a position.
+    Repeat(usize, usize),
+}
+
+impl Edit {
+    /// Applies this edit to `input`, positions being taken modulo its
+    /// length and moved back to the previous character boundary.
+    fn apply(&self, input: &mut String) {
+        let boundary = |input: &String, position: usize| {
+            let mut position = position % (input.len() + 1);
+            while !input.is_char_boundary(position) { position -= 1; }
+            position
+        };
+        match self {
+            Edit::Insert(position, text) => input.insert_str(boundary(input, *position), text),
+            Edit::Remove(position, length) => {
+                let start = boundary(input, *position);
+                let end = boundary(input, (start + length).min(input.len()));
+                input.replace_range(start..end, "");
+            }
+            Edit::Repeat(position, length) => {
+                let start = boundary(input, *position);
+                let end = boundary(input, (start + length).min(input.len()));
+                let repeated = input[start..end].to_string();
+                input.insert_str(end, &repeated);
+            }
+        }
+    }
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    // Inserted text favors digits, signs and separators, plus a few
+    // multibyte characters to catch slicing in the middle of one.
+    let text = "([0-9]{1,12}|[-+ ,:x>=\n]|[a-zA-Z]{1,4}|[é€😀])";
+    prop_oneof![
+        (any::<usize>(), text).prop_map(|(position, text)| Edit::Insert(position, text)),
+        (any::<usize>(), 1..20usize).prop_map(|(position, length)| Edit::Remove(position, length)),
+        (any::<usize>(), 1..20usize).prop_map(|(position, length)| Edit::Repeat(position, length)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn random_text_does_not_panic(parser in 0..PARSERS.len(), input in "[ -~\n]{0,200}") {
+        (PARSERS[parser].1)(&input);
+    }
+
+    #[test]
+    fn edited_inputs_do_not_panic(
+        parser in 0..PARSERS.len(),
+        seed in any::<u64>(),
+        edits in proptest::collection::vec(edit(), 1..8),
+    ) {
+        let (day, parse) = PARSERS[parser];
+        let mut input = generate(day, 8, seed).unwrap();
+        for edit in &edits {
+            edit.apply(&mut input);
+        }
+        parse(&input);
+    }
+
+    #[test]
+    fn generated_inputs_pass_the_check(day in 1..=25u32, seed in any::<u64>()) {
+        let (Some(solution), Some(input)) = (days::find(YEAR, day), generate(day, 8, seed)) else { return Ok(()) };
+        if let Some(Err(err)) = solution.check_input(&input) {
+            prop_assert!(false, "day {day}: {err} in {input:?}");
+        }
+    }
+
+    #[test]
+    fn diagnostics_of_edited_inputs_render(
+        day in 1..=25u32,
+        seed in any::<u64>(),
+        edits in proptest::collection::vec(edit(), 1..8),
+    ) {
+        let (Some(solution), Some(mut input)) = (days::find(YEAR, day), generate(day, 8, seed)) else { return Ok(()) };
+        for edit in &edits {
+            edit.apply(&mut input);
+        }
+        if let Some(Err(err)) = solution.check_input(&input) {
+            if let Some(diagnostic) = Diagnostic::locate(&input, &err) {
+                prop_assert!(diagnostic.to_string().starts_with("error: "));
+            }
+        }
+    }
+}